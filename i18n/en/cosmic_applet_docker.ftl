@@ -10,13 +10,42 @@ stopped = Stopped
 running = Running
 container-logs = Container Logs
 loading = Loading...
+filter-logs = Filter lines...
+regex = Regex
+timestamps = Timestamps
+local-time = Local time
+wrap-lines = Wrap
+log-tail = Lines to load
+log-tail-lines = Last {$lines}
+log-tail-all = All lines
+log-since = Time range
+log-since-minutes = {$minutes} min
+log-since-hours = {$hours} h
+log-since-start = Since last start
+log-since-all = Any time
+log-range = Show a time range
+load-range = Load range
+follow-live = Follow live logs
+now = Now
+log-range-invalid = Enter times as YYYY-MM-DD HH:MM
+log-range-order = The range must end after it starts
+log-matches = {$count} matching
 search-placeholder = Filter containers...
 compose-group = {$name} ({$running}/{$total})
+project-up = All services running
+project-partial = Some services are not running
+project-failing = A service is unhealthy or restarting
+project-stopped = Stopped
+service-replicas = {$name} ×{$total} ({$running} running)
 other-containers = Other
 stop-all = Stop All
+pause-all = Pause all
+unpause-all = Resume all
 start-all = Start All
 delete = Delete
-confirm-delete = Delete {$name}?
+confirm-action = {$action} {$name}?
+type-to-confirm = Type {$name}
+all-containers = all containers
 confirm-yes = Yes
 confirm-no = No
 copy-id = Copy ID
@@ -24,9 +53,250 @@ open-browser = Open in Browser
 details = Details
 container-details = Container Details
 environment = Environment Variables
+copy-env-file = Copy as .env
+skip-secrets = Skip secrets
+last-exit-code = Last exit code
+restart-count = Restarts
+created = Created
+started = Started
+finished = Finished
+runtime = Runtime
+hostname = Hostname
+platform = Platform
+platform-emulated = {$platform} (emulated)
+security = Security
+risk-privileged = Runs in privileged mode
+risk-capabilities = Added capabilities: {$caps}
+risk-host-network = Shares the host network
+risk-host-pid = Shares the host PID namespace
+risk-root-user = Runs as root
+devices = Devices
+all-devices = all
+default-driver = default
+health-check = Health check
+health-check-timing = Every {$interval}, {$timeout} timeout, {$retries} retries, {$start} start period
+no-probes = No probes have run yet
+probe-passed = Passed {$when}
+probe-failed = Failed {$when} (exit code {$code})
 volumes = Volumes
 networks = Networks
 ports = Ports
 no-data = None
 container-stopped = Container {$name} stopped unexpectedly
+container-oom-killed = Container {$name} was killed: out of memory
+container-crash-looping = Container {$name} is crash looping ({$count} exits in {$minutes} minutes)
+crash-looping = Crash looping: {$count} exits in the last {$minutes} minutes
+show-logs = Show logs
 container-unhealthy = Container {$name} is unhealthy
+container-started = Container {$name} started
+container-recovered = Container {$name} is healthy again
+settings = Settings
+decimal-units = Show sizes in MB/GB instead of MiB/GiB
+memory-as-percent = Show container memory as a percentage of its limit
+normalize-cpu = Show CPU as a share of the whole host instead of per core
+show-panel-stats = Show CPU and memory in the panel
+redact-secrets = Hide secrets in logs and environment
+strip-log-colors = Show logs without colors
+log-buffer-lines = Log lines kept in memory
+redaction-patterns = Redaction patterns (regular expressions)
+redaction-pattern-placeholder = Add a pattern...
+add = Add
+reset-defaults = Reset to defaults
+confirmations = Confirmations
+confirm-normal = Start, stop and restart
+confirm-destructive = Delete, down and prune
+confirm-dangerous = Force kill and volume removal
+confirmation-never = Don't ask
+confirmation-ask = Ask
+confirmation-type-name = Type name to confirm
+docker-permission-denied = The Docker socket is only accessible to root
+use-elevated-access = Use elevated access
+files = Files
+parent-directory = Parent directory
+empty-directory = Empty directory
+download = Download
+container-path = Path in container, e.g. /var/log/app.log
+saved-to = Saved to {$path}
+upload = Upload
+upload-here = Upload a file into this directory
+uploaded-to = Uploaded to {$path}
+processes = Processes
+kill-process = Kill
+overview = Overview
+raw-json = Raw JSON
+search-keys = Search keys...
+copy = Copy
+compare = Compare containers
+compare-hint = Pick two containers to compare
+identical = Identical
+image = Image
+labels = Labels
+registries = Registry logins
+registry-server = Registry, e.g. ghcr.io
+registry-username = Username
+registry-password = Password or token
+add-registry = Save login
+pull-image = Pull image
+pulling = Pulling {$image}...
+pulled = Pulled {$image}
+memory-limit = Memory limit
+unlimited = Unlimited
+export-logs = Export logs
+save-logs = Save logs
+export-stats = Export stats
+export-stats-tooltip = Export stats as CSV (or JSON, by saving with a .json extension)
+pop-out = Open in a window
+project-logs = Project logs
+project-logs-title = {$name} logs
+count-errors = Count errors in the background
+log-alerts = Log alerts
+alert-pattern = Regex, e.g. OutOfMemoryError
+add-alert = Add alert
+log-alert = Log alert: {$name}
+pin-project = Pin to top
+pinned-projects = Pinned projects
+pinned-projects-empty = Pin a Compose project from its group header to keep it at the top.
+unpin = Unpin
+move-up = Move up
+move-down = Move down
+autostart = Start with session
+autostart-empty = No containers are started with the session.
+autostart-group = Compose group {$name}
+autostart-started = Started: {$names}
+autostart-failed = Failed: {$names}
+start-with-session = Start with session
+rename = Rename
+new-name = New name
+cancel = Cancel
+open-shell = Open shell
+terminal-command = Terminal command
+run-command = Run a command, e.g. env
+run = Run
+exit-code = {"["}exited with code {$code}{"]"}
+force-remove = Force remove
+remove-volumes = Also remove anonymous volumes
+duplicate = Duplicate
+port-offset = Port offset
+invalid-port-offset = Port offset must be a number up to 65535
+duplicated = Created {$name}
+cpu-limit = CPU limit
+cores = {$cores} cores
+total-usage = CPU {$cpu}% · MEM {$memory} in total
+memory-of-limit = {$used} of {$limit} memory ({$percent}%)
+cpu-history = CPU
+memory-history = Memory
+sparkline-peak = {$label}, peak {$peak}% over the last samples
+current-usage = Using {$cpu}% CPU · {$memory} memory
+memory-swap-limit = Memory + swap: {$limit}
+cpu-shares = CPU shares: {$shares}
+pids-limited = Processes: {$pids} of {$limit}
+pids-unlimited = Processes: {$pids} (no limit)
+at-limit = Running at its limit, likely throttled
+restart-policy = Restart policy
+restart-no = No
+restart-on-failure = On failure
+restart-unless-stopped = Unless stopped
+restart-always = Always
+select = Select containers
+selected-count = {$count} selected
+bulk-delete-running = ({$count} running, will be force-removed)
+user = User
+command = Command
+restart-unhealthy = Restart unhealthy
+unhealthy-containers = all unhealthy containers
+images = Images
+prune = Prune
+prune-dangling = Prune dangling
+dangling-images = dangling images
+reclaimed = Reclaimed {$size}
+used-by = used by {$count}
+check-image-updates = Check for image updates
+notify-image-updates = Notify about image updates
+notify-container-starts = Notify when containers start outside the applet
+notify-recovery = Notify when unhealthy containers recover
+quiet-hours = Quiet hours
+quiet-hours-window = Hold notifications between
+quiet-hours-invalid = Enter both times as HH:MM, e.g. 22:00
+held-notifications = {$count} notifications during quiet hours
+clear = Clear
+image-updates = Image updates available
+image-update-available = A newer image is available upstream
+tag = Tag
+new-tag = repository:tag
+tagged = Tagged as {$tag}
+push-image = Push to registry
+pushing = Pushing {$image}...
+pushed = Pushed {$image}
+layers = Layers
+size-breakdown = Size
+image-size-summary = {$total} total, {$layers} layers with content out of {$steps} build steps
+ago = {$age} ago
+up-for = Up {$duration}
+uptime-days = {$days} d {$hours} h
+uptime-hours = {$hours} h {$minutes} min
+uptime-minutes = {$minutes} min
+uptime-seconds = less than a minute
+run-container = Run container
+local-images = Local images
+name = Name
+optional = Optional
+starting = Starting { $image }…
+export-image = Save image to archive
+writing-archive = Saving { $name } — { $written } written
+outdated = Outdated
+recreate = Recreate
+recreate-tooltip = A newer image with this tag exists locally — recreate the container from it
+volumes = Volumes
+show-sizes = Show sizes
+prune-unused = Prune unused
+no-unused-volumes = No unused volumes
+unused-volumes = { $count } unused volumes
+volumes-pruned = Removed { $count } volumes, reclaimed { $size }
+volumes-removed = Removed { $count } volumes
+create-volume = Create volume
+driver = Driver
+labels-hint = Labels, e.g. env=dev, team=web
+volume-created = Created volume { $name }
+image-built = Built {$date}
+mac = MAC {$mac}
+gateway = gateway {$gateway}
+aliases = Aliases: {$aliases}
+bind-mount = Bind mount
+named-volume = Named volume
+other-mount = Other mount
+open-folder = Open folder
+folder-needs-root = { $path } is only readable by root. Open it with: pkexec cosmic-files { $path }
+unused = Not used by any container
+unused-volume = Safe to delete: no container uses this volume
+backup-volume = Back up to archive
+connected-count = { $count } connected
+disconnect = Disconnect
+connect = Connect
+alias-optional = Alias (optional)
+disk-usage = Disk usage
+containers = Containers
+build-cache = Build cache
+total = Total
+refresh = Refresh
+reclaimable = { $size } reclaimable
+largest-containers = Largest containers
+large-writable-layer = Writable layer is unusually large; the container may be writing data outside its volumes
+with-image = {$size} with image
+writable-layer-size = Writable layer has grown to {$size}
+writable-layer-growth = Writable layer grew by {$growth} in the last hour, to {$size}
+free-up-space = Free up space
+stopped-containers = Stopped containers
+dangling-images-option = Dangling images
+unused-networks = Unused networks
+unused-data = unused Docker data
+start-docker = Start Docker
+compose-up = Compose up
+compose-down = Compose down
+compose-done = Done
+remove-orphans = Remove orphans
+orphan-tooltip = This service is no longer in the compose file
+close = Close
+update-stack = Update stack (pull and up)
+stack-updated = { $name } is up to date
+stack-update-failed = Updating { $name } failed
+open-compose-file = Open compose file
@@ -0,0 +1,228 @@
+use cosmic::iced::Color;
+use std::borrow::Cow;
+
+const ESC: char = '\x1b';
+
+/// The eight standard colors followed by their bright variants
+const PALETTE: [(u8, u8, u8); 16] = [
+    (85, 85, 85),
+    (205, 66, 66),
+    (92, 178, 84),
+    (204, 168, 51),
+    (77, 128, 217),
+    (178, 102, 191),
+    (64, 178, 191),
+    (191, 191, 191),
+    (128, 128, 128),
+    (242, 102, 102),
+    (128, 217, 115),
+    (242, 217, 89),
+    (115, 166, 250),
+    (217, 140, 230),
+    (102, 217, 230),
+    (242, 242, 242),
+];
+
+/// A run of log text sharing one style
+#[derive(Debug, Clone, PartialEq)]
+pub struct StyledRun {
+    pub text: String,
+    pub color: Option<Color>,
+    pub bold: bool,
+}
+
+/// Removes escape sequences, leaving the plain text.
+pub fn strip(input: &str) -> Cow<'_, str> {
+    if !input.contains(ESC) {
+        return Cow::Borrowed(input);
+    }
+    Cow::Owned(parse(input).into_iter().map(|run| run.text).collect())
+}
+
+/// Splits text into styled runs following SGR color and bold codes. Other
+/// escape sequences (cursor movement, titles) are dropped.
+pub fn parse(input: &str) -> Vec<StyledRun> {
+    let mut runs = Vec::new();
+    let mut current = StyledRun {
+        text: String::new(),
+        color: None,
+        bold: false,
+    };
+    let mut chars = input.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != ESC {
+            current.text.push(c);
+            continue;
+        }
+        match chars.next() {
+            // Control sequence: parameters up to a final byte in @..~
+            Some('[') => {
+                let mut params = String::new();
+                let mut terminator = None;
+                for c in chars.by_ref() {
+                    if ('@'..='~').contains(&c) {
+                        terminator = Some(c);
+                        break;
+                    }
+                    params.push(c);
+                }
+                if terminator != Some('m') {
+                    continue;
+                }
+                let (color, bold) = apply_sgr(&params, current.color, current.bold);
+                if color != current.color || bold != current.bold {
+                    let next = StyledRun {
+                        text: String::new(),
+                        color,
+                        bold,
+                    };
+                    let finished = std::mem::replace(&mut current, next);
+                    if !finished.text.is_empty() {
+                        runs.push(finished);
+                    }
+                }
+            }
+            // Operating system command, ended by BEL or ESC \
+            Some(']') => {
+                while let Some(c) = chars.next() {
+                    if c == '\x07' {
+                        break;
+                    }
+                    if c == ESC && chars.peek() == Some(&'\\') {
+                        chars.next();
+                        break;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if !current.text.is_empty() {
+        runs.push(current);
+    }
+    runs
+}
+
+fn apply_sgr(params: &str, mut color: Option<Color>, mut bold: bool) -> (Option<Color>, bool) {
+    let codes: Vec<u16> = params
+        .split(';')
+        .map(|code| code.parse().unwrap_or(0))
+        .collect();
+    let mut codes = codes.into_iter();
+
+    while let Some(code) = codes.next() {
+        match code {
+            0 => {
+                color = None;
+                bold = false;
+            }
+            1 => bold = true,
+            22 => bold = false,
+            30..=37 => color = Some(palette(code as usize - 30)),
+            39 => color = None,
+            90..=97 => color = Some(palette(code as usize - 90 + 8)),
+            38 => match codes.next() {
+                Some(5) => color = codes.next().map(|index| indexed(index as usize)),
+                Some(2) => {
+                    let mut channel = || codes.next().unwrap_or(0).min(255) as u8;
+                    color = Some(Color::from_rgb8(channel(), channel(), channel()));
+                }
+                _ => {}
+            },
+            // Backgrounds aren't rendered, but their parameters must not be read as codes
+            48 => match codes.next() {
+                Some(5) => {
+                    codes.next();
+                }
+                Some(2) => codes.by_ref().take(3).for_each(drop),
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+    (color, bold)
+}
+
+fn palette(index: usize) -> Color {
+    let (r, g, b) = PALETTE[index];
+    Color::from_rgb8(r, g, b)
+}
+
+/// Resolves an entry of the 256-color table
+fn indexed(index: usize) -> Color {
+    match index {
+        0..=15 => palette(index),
+        16..=231 => {
+            let level = |n: usize| if n == 0 { 0 } else { (55 + n * 40) as u8 };
+            let index = index - 16;
+            Color::from_rgb8(level(index / 36), level(index / 6 % 6), level(index % 6))
+        }
+        _ => {
+            let gray = (8 + (index.min(255) - 232) * 10) as u8;
+            Color::from_rgb8(gray, gray, gray)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(text: &str, color: Option<Color>, bold: bool) -> StyledRun {
+        StyledRun {
+            text: text.to_string(),
+            color,
+            bold,
+        }
+    }
+
+    #[test]
+    fn plain_text_is_borrowed() {
+        assert!(matches!(strip("no escapes"), Cow::Borrowed("no escapes")));
+    }
+
+    #[test]
+    fn strip_drops_sequences() {
+        assert_eq!(strip("\x1b[1;31merror\x1b[0m: failed"), "error: failed");
+        assert_eq!(strip("\x1b]0;title\x07text"), "text");
+        assert_eq!(strip("\x1b]0;title\x1b\\text"), "text");
+        assert_eq!(strip("\x1b[2Kcleared"), "cleared");
+    }
+
+    #[test]
+    fn splits_runs_on_color_and_bold() {
+        assert_eq!(
+            parse("a\x1b[31mb\x1b[1mc\x1b[0md"),
+            vec![
+                run("a", None, false),
+                run("b", Some(palette(1)), false),
+                run("c", Some(palette(1)), true),
+                run("d", None, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn bright_and_extended_colors() {
+        assert_eq!(parse("\x1b[91mx")[0].color, Some(palette(9)));
+        assert_eq!(parse("\x1b[38;5;196mx")[0].color, Some(indexed(196)));
+        assert_eq!(
+            parse("\x1b[38;2;1;2;3mx")[0].color,
+            Some(Color::from_rgb8(1, 2, 3))
+        );
+    }
+
+    #[test]
+    fn background_parameters_are_not_read_as_codes() {
+        // 48;5;1 must not turn bold on through its trailing 1
+        assert_eq!(parse("\x1b[48;5;1mx"), vec![run("x", None, false)]);
+        // 48;2;0;0;0 must not reset through its zeros
+        assert_eq!(
+            parse("\x1b[1;31;48;2;0;0;0mx"),
+            vec![run("x", Some(palette(1)), true)]
+        );
+        assert_eq!(parse("\x1b[1;44mx"), vec![run("x", None, true)]);
+    }
+}
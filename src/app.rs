@@ -1,72 +1,629 @@
-use crate::config::APP_ID;
+use crate::ansi;
+use crate::compare::{self, ComparableConfig, DiffEntry};
+use crate::config::{Config, LogAlert, APP_ID};
 use crate::docker::{
-    self, ContainerDetails, ContainerInfo, ContainerState, ContainerStats, DockerEvent,
-    HealthStatus, PortMapping,
+    self, ComposeAction, ComposeProject, ContainerDetails, ContainerInfo, ContainerInspection,
+    ContainerState, ContainerStats, DeviceRequestInfo, DiskUsage, DockerEvent, FileEntry,
+    HealthCheck, HealthStatus, ImageInfo, ImageLayer, LogSeverity, LogSince, LogStream, LogWindow,
+    MountKind, NetworkInfo, PortMapping, ProcessInfo, PruneTargets, RemoveFlags, RestartPolicy,
+    SecurityRisk, VolumeInfo,
 };
+use crate::elevate;
 use crate::fl;
+use crate::policy::{ActionCategory, Confirmation};
+use crate::portal;
+use crate::redact::{self, Redactor};
+use crate::registry::{self, RegistryEntry};
+use crate::service::{self, DockerUnit};
 use cosmic::app::Core;
+use cosmic::cosmic_config::{self, CosmicConfigEntry};
+use cosmic::iced::font::Weight;
 use cosmic::iced::platform_specific::shell::commands::popup::{destroy_popup, get_popup};
+use cosmic::iced::widget::scrollable::{Direction, Scrollbar};
+use cosmic::iced::widget::text::{Span, Wrapping};
+use cosmic::iced::widget::{rich_text, span};
 use cosmic::iced::window::Id;
-use cosmic::iced::{Alignment, Length, Limits, Subscription};
+use cosmic::iced::{Alignment, Color, Font, Length, Limits, Size, Subscription};
 use cosmic::iced_runtime::core::window;
 use cosmic::widget::{self, scrollable, text};
 use cosmic::{Action, Element, Task};
-use std::collections::{BTreeMap, HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::path::PathBuf;
+
+/// Writable layers above this many bytes are flagged as growing abnormally large
+const LARGE_WRITABLE_LAYER: i64 = 1024 * 1024 * 1024;
+
+/// Writable layers that grew by more than this many bytes within `LAYER_GROWTH_WINDOW`
+/// are flagged too, however small they still are
+const LAYER_GROWTH: i64 = 256 * 1024 * 1024;
+
+const LAYER_GROWTH_WINDOW: std::time::Duration = std::time::Duration::from_secs(60 * 60);
+
+/// Containers listed by size in the disk usage view
+const LARGEST_CONTAINERS: usize = 10;
+
+/// Stats samples kept per container for the sparklines
+const STATS_HISTORY: usize = 30;
+
+/// Bar glyphs a sparkline is drawn with, lowest first
+const SPARK_BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Minimum time between two notifications for the same alert
+const LOG_ALERT_COOLDOWN: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// Unexpected exits within `CRASH_LOOP_WINDOW` that make a container crash looping
+const CRASH_LOOP_DEATHS: usize = 3;
+
+/// How long exits are remembered; a loop ends after this long without one
+const CRASH_LOOP_WINDOW: std::time::Duration = std::time::Duration::from_secs(300);
+
+/// Notifications kept for the quiet hours summary; older ones are dropped
+const HELD_NOTIFICATIONS: usize = 50;
+
+/// "Stopped unexpectedly" notifications whose buttons are listened for at once, each
+/// on its own thread; beyond this they are shown without buttons
+const ACTIONABLE_NOTIFICATIONS: usize = 3;
+
+/// How long a "stopped unexpectedly" notification stays up waiting for a click
+const NOTIFICATION_ACTION_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10 * 60);
+
+/// Log lines per service included in a compose group export
+const LOG_EXPORT_TAIL: usize = 1000;
+
+/// Tail lengths offered in the log view; `None` loads the whole history
+const LOG_TAILS: [Option<usize>; 5] = [Some(100), Some(200), Some(1000), Some(5000), None];
+
+/// How far back the log view reaches
+const LOG_SINCE: [LogSince; 5] = [
+    LogSince::LastStart,
+    LogSince::Last(300),
+    LogSince::Last(3600),
+    LogSince::Last(86_400),
+    LogSince::All,
+];
+
+/// Height of one unwrapped line in the log pane
+const LOG_LINE_HEIGHT: f32 = 20.0;
+
+/// Lines built above the viewport so scrolling up doesn't show blank space
+const LOG_OVERSCAN: usize = 20;
+
+/// Lines built per frame: the 400px pane plus overscan on both sides
+const LOG_RENDERED_LINES: usize = 60;
+
+/// Lines built per frame in the popped out log window, enough for a tall window
+const LOG_RENDERED_LINES_DETACHED: usize = 120;
+
+/// Log buffer sizes offered in the settings
+const LOG_BUFFER_SIZES: [usize; 4] = [1000, 5000, 20_000, 50_000];
+
+/// How dates are typed in the log range inputs, in local time
+const LOG_RANGE_FORMAT: &str = "%Y-%m-%d %H:%M";
+const LOG_RANGE_PLACEHOLDER: &str = "YYYY-MM-DD HH:MM";
+
+/// Lines kept in the project log view before the oldest are dropped
+const PROJECT_LOG_LINES: usize = 1000;
+
+/// Prefix colors for services in the project log view
+const SERVICE_COLORS: [(f32, f32, f32); 6] = [
+    (0.38, 0.69, 0.94),
+    (0.56, 0.80, 0.42),
+    (0.94, 0.71, 0.35),
+    (0.80, 0.52, 0.90),
+    (0.35, 0.80, 0.76),
+    (0.94, 0.48, 0.52),
+];
 
 #[derive(Debug, Clone)]
 pub enum Message {
     TogglePopup,
     PopupClosed(Id),
+    PopOutLogs,
     DockerEvent(DockerEvent),
     StartContainer(String),
     StopContainer(String),
     RestartContainer(String),
     ActionCompleted(Result<String, String>),
     ShowLogs(String, String),
+    ShowProjectLogs(String),
+    LogFilterChanged(String),
+    ToggleLogTimestamps(bool),
+    ToggleLogRangeEditor,
+    LogRangeFromInput(String),
+    LogRangeToInput(String),
+    ApplyLogRange,
+    ClearLogRange,
+    LogRangeLoaded(String, Result<Vec<(LogStream, String)>, String>),
+    ToggleLogWrap(bool),
+    LogScrolled(f32),
+    SetLogBufferSize(usize),
+    ToggleLogStream(LogStream, bool),
+    SetLogTail(usize),
+    SetLogSince(usize),
+    ToggleLogLocalTime(bool),
+    ToggleLogFilterRegex(bool),
     BackToList,
     OpenInBrowser(u16),
+    OpenFolder(String),
     SearchChanged(String),
     ClearSearch,
     ToggleGroup(String),
+    ToggleService(String),
     StopAll,
     StartAll,
+    RestartUnhealthy,
+    RecreateContainer(String),
+    /// ID of the replaced container and the new one's ID with whether to start it
+    ContainerRecreated(String, Result<(String, bool), String>),
+    ShowImages,
+    ImagesLoaded(Result<Vec<ImageInfo>, String>),
+    RemoveImage(String),
+    ImageRemoved(String, Result<String, String>),
+    PruneImages,
+    ShowDiskUsage,
+    DiskUsageLoaded(Result<DiskUsage, String>),
+    SetPruneTargets(PruneTargets),
+    FreeUpSpace,
+    SpaceFreed(Result<i64, String>),
+    ShowNetworks,
+    NetworksLoaded(Result<Vec<NetworkInfo>, String>),
+    RemoveNetwork(String),
+    DisconnectNetwork(String),
+    ConnectNetworkPicked(usize),
+    ConnectAliasInput(String),
+    ConnectNetwork,
+    NetworkConnectionChanged(Result<String, String>),
+    NetworkRemoved(String, Result<String, String>),
+    ShowVolumes,
+    VolumesLoaded(Result<Vec<VolumeInfo>, String>),
+    LoadVolumeSizes,
+    VolumeSizesLoaded(Result<HashMap<String, i64>, String>),
+    RemoveVolume(String),
+    ToggleCreateVolume,
+    NewVolumeNameInput(String),
+    NewVolumeDriverInput(String),
+    NewVolumeLabelsInput(String),
+    CreateVolume,
+    VolumeCreated(Result<String, String>),
+    FindUnusedVolumes,
+    UnusedVolumesFound(Result<Vec<String>, String>),
+    /// Prunes unused volumes; carries the names shown when confirming
+    PruneVolumes(Vec<String>),
+    /// Removed volume names and per-volume failures
+    VolumesPruned(Vec<String>, Vec<String>),
+    VolumeRemoved(String, Result<String, String>),
+    ShowRun,
+    RunImageInput(String),
+    RunImagePicked(usize),
+    RunNameInput(String),
+    RunPortsInput(String),
+    RunEnvInput(String),
+    AddRunEnv,
+    RemoveRunEnv(usize),
+    RunContainer,
+    ContainerCreated(Result<String, String>),
+    ContainerRun(Result<String, String>),
+    ShowImageDetails(String),
+    ImageHistoryLoaded(Result<(Vec<ImageLayer>, i64), String>),
+    BackFromImageDetails,
+    StartTag(String),
+    TagInput(String),
+    SubmitTag,
+    CancelTag,
+    ImageTagged(Result<String, String>),
+    /// Image ID and the `repo:tag` reference to push
+    PushImage(String, String),
+    ImagePushed(String, Result<String, String>),
+    ExportImage(String),
+    BackupVolume(String),
+    ArchiveExportStarted(ArchiveKind, String, u64, Result<PathBuf, String>),
+    ImagesPruned(Result<i64, String>),
+    ToggleSelectionMode,
+    ToggleSelected(String, bool),
+    RunBulkAction(BulkAction),
     StopGroup(String),
     StartGroup(String),
-    DeleteContainer(String),
-    ConfirmDelete(String),
-    CancelDelete,
+    PauseGroup(String),
+    UnpauseGroup(String),
+    ExportGroupLogs(String),
+    SaveLogs,
+    ExportStats,
+    StatsExported(Result<String, String>),
+    RunCompose(String, ComposeAction),
+    ComposeServicesLoaded(String, Result<HashSet<String>, String>),
+    ContainersInspected(HashMap<String, ContainerInspection>),
+    /// A button of the "stopped unexpectedly" notification was clicked, or it was closed
+    StoppedNotificationAction(String, String, String),
+    /// Redraws time-relative text such as uptimes
+    Tick,
+    RemoveOrphans(String),
+    CloseComposeOutput,
+    OpenComposeFile(PathBuf),
+    ToggleErrorWatch(String, bool),
+    CopyEnvFile,
+    ToggleEnvSkipSecrets(bool),
+    AlertPatternInput(String),
+    AddLogAlert,
+    RemoveLogAlert(usize),
+    OpenShell(String),
+    ExecInput(String),
+    RunExec,
+    ShellLaunched(Result<(), String>),
+    TerminalCommandInput(String),
+    DuplicateNameInput(String),
+    DuplicatePortOffsetInput(String),
+    DuplicateContainer,
+    ContainerDuplicated(Result<String, String>),
+    StartRename(String, String),
+    RenameInput(String),
+    SubmitRename,
+    CancelRename,
+    ContainerRenamed(Result<(String, String), String>),
+    ToggleAutostartContainer(String, bool),
+    ToggleAutostartGroup(String),
+    TogglePinGroup(String),
+    /// Moves a pinned project from one position to another
+    MovePinnedGroup(usize, usize),
+    AutostartFinished(Result<(Vec<String>, Vec<String>), String>),
+    LogsExported(Result<String, String>),
+    DeleteContainer(String, RemoveFlags),
+    SetRemoveFlags(RemoveFlags),
+    RequestAction(Box<Message>),
+    ConfirmInputChanged(String),
+    ConfirmAction,
+    CancelAction,
     CopyContainerId(String),
     ShowDetails(String, String),
     DetailsReceived(Result<(String, ContainerDetails), String>),
+    SetDetailsTab(DetailsTab),
+    JsonSearchChanged(String),
+    CopyText(String),
+    ProcessesReceived(Result<(String, Vec<ProcessInfo>), String>),
+    KillProcess(String),
+    ProcessKilled(Result<String, String>),
+    ShowCompare,
+    SelectCompareLeft(usize),
+    SelectCompareRight(usize),
+    CompareLoaded(Result<(ComparableConfig, ComparableConfig), String>),
+    ShowSettings,
+    ConfigChanged(Config),
+    TogglePanelStats(bool),
+    ToggleDecimalUnits(bool),
+    ToggleMemoryAsPercent(bool),
+    ToggleNormalizeCpu(bool),
+    ToggleStripLogColors(bool),
+    ToggleRedaction(bool),
+    ToggleImageUpdateChecks(bool),
+    ToggleImageUpdateNotifications(bool),
+    ToggleStartNotifications(bool),
+    ToggleRecoveryNotifications(bool),
+    ToggleQuietHours(bool),
+    QuietHoursStartInput(String),
+    QuietHoursEndInput(String),
+    ClearHeldNotifications,
+    RedactionPatternInput(String),
+    AddRedactionPattern,
+    RemoveRedactionPattern(usize),
+    ResetRedactionPatterns,
+    RegistryServerInput(String),
+    RegistryUsernameInput(String),
+    RegistryPasswordInput(String),
+    AddRegistry,
+    RegistryAdded(Result<RegistryEntry, String>),
+    RemoveRegistry(usize),
+    PullImage(String),
+    MemoryLimitChanged(f64),
+    ApplyMemoryLimit,
+    MemoryLimitApplied(Result<i64, String>),
+    CpuLimitChanged(f64),
+    SetRestartPolicy(usize),
+    RestartPolicyApplied(Result<RestartPolicy, String>),
+    ApplyCpuLimit,
+    CpuLimitApplied(Result<f64, String>),
+    ImagePulled(Result<String, String>),
+    SetConfirmation(ActionCategory, usize),
+    ShowFiles(String, String),
+    BrowseDirectory(String),
+    DirectoryListed(Result<(String, Vec<FileEntry>), String>),
+    TransferPathInput(String),
+    DownloadPath(String),
+    UploadFile,
+    TransferCompleted(Result<String, String>),
+    EnableElevatedAccess,
+    DockerUnitDetected(Option<DockerUnit>),
+    StartDaemon,
+    DaemonStarted(Result<(), String>),
+    ElevatedAccessReady(Result<PathBuf, String>),
+}
+
+/// An action waiting for the user to confirm it, per the action policy
+struct PendingAction {
+    message: Message,
+    category: ActionCategory,
+    confirmation: Confirmation,
+    /// Container the action targets, so rows can confirm inline
+    target_id: Option<String>,
+    target_name: String,
+    typed: String,
+}
+
+impl PendingAction {
+    fn is_confirmed(&self) -> bool {
+        self.confirmation != Confirmation::TypeName || self.typed.trim() == self.target_name
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ArchiveKind {
+    Image,
+    Volume,
+}
+
+/// A notification kept back during quiet hours
+struct HeldNotification {
+    time: chrono::DateTime<chrono::Local>,
+    summary: String,
+    body: String,
+}
+
+/// An image or volume archive being written to disk
+struct ArchiveExport {
+    kind: ArchiveKind,
+    /// Image reference or volume name
+    name: String,
+    dest: PathBuf,
+    written: u64,
+    /// Image or volume size, used to estimate progress; 0 if unknown
+    total: u64,
+}
+
+/// Action applied to every selected container in selection mode
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BulkAction {
+    Start,
+    Stop,
+    Restart,
+    Delete,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DetailsTab {
+    Overview,
+    RawJson,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 enum PopupView {
     ContainerList,
     ContainerLogs,
+    ProjectLogs,
     ContainerDetails,
+    ContainerFiles,
+    Compare,
+    Images,
+    ImageDetails,
+    Volumes,
+    Networks,
+    DiskUsage,
+    Run,
+    Settings,
+}
+
+/// Overall state of a compose project shown as a dot on its group header
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ProjectStatus {
+    /// Every container running and none unhealthy
+    Up,
+    /// Some containers are not running
+    Partial,
+    /// A container is unhealthy or restarting in a loop
+    Failing,
+    Stopped,
 }
 
 pub struct DockerApplet {
     core: Core,
     popup: Option<Id>,
+    config: Config,
+    config_handler: Option<cosmic_config::Config>,
+    redactor: Redactor,
+    new_redaction_pattern: String,
+    registry_server: String,
+    registry_username: String,
+    registry_password: String,
+    registry_status: Option<String>,
+    pull_status: Option<String>,
+    /// Why a mount folder could not be opened
+    open_folder_hint: Option<String>,
+    /// Memory limit in MiB while the slider is being dragged
+    memory_limit_draft: Option<f64>,
+    /// CPU limit in cores while the slider is being dragged
+    cpu_limit_draft: Option<f64>,
+    limits_error: Option<String>,
+    /// Error-level log lines seen per container since its logs were last opened
+    error_counts: HashMap<String, usize>,
+    /// Container being renamed and the name typed so far
+    renaming: Option<(String, String)>,
+    duplicate_name: String,
+    duplicate_port_offset: String,
+    duplicate_status: Option<String>,
+    exec_input: String,
+    exec_command: String,
+    exec_output: String,
+    exec_running: bool,
+    /// Incremented per run so repeating a command restarts its subscription
+    exec_run: u64,
     docker_available: bool,
+    permission_denied: bool,
+    elevating: bool,
+    /// The `docker compose` run in progress or last finished, and its output
+    compose_task: Option<(ComposeProject, ComposeAction)>,
+    compose_running: bool,
+    compose_run: u64,
+    compose_output: String,
+    /// Unit that can start the daemon; looked up once per outage
+    docker_unit: Option<DockerUnit>,
+    docker_unit_checked: bool,
+    starting_daemon: bool,
+    daemon_start_error: Option<String>,
     containers: Vec<ContainerInfo>,
     stats: HashMap<String, ContainerStats>,
     current_view: PopupView,
     log_container_name: String,
     log_container_id: String,
-    log_content: String,
+    /// Loaded log lines with the stream each came from, capped at `log_buffer_lines`
+    log_lines: VecDeque<(LogStream, String)>,
+    /// Indices into `log_lines` that pass the stream toggles and the filter
+    log_matches: Vec<usize>,
+    /// Scroll position of the log pane, used to build only the lines in view
+    log_scroll_offset: f32,
+    /// Leave variables the redactor would mask out of "copy as .env"
+    env_skip_secrets: bool,
+    new_alert_pattern: String,
+    alert_pattern_error: Option<String>,
+    /// When each (container ID, pattern) alert last notified
+    last_alerts: HashMap<(String, String), std::time::Instant>,
+    /// Standalone window the log view was popped out into
+    logs_window_id: Option<Id>,
+    log_buffer_options: Vec<String>,
+    log_show_stdout: bool,
+    log_show_stderr: bool,
+    /// Wrap long log lines; otherwise the pane scrolls horizontally
+    log_wrap: bool,
     logs_loading: bool,
+    log_window: LogWindow,
+    /// Unix times of a historical range being shown instead of the live stream
+    log_range: Option<(i64, i64)>,
+    log_range_editing: bool,
+    log_range_from: String,
+    log_range_to: String,
+    log_range_error: Option<String>,
+    /// Why the followed log stream stopped, when it failed
+    log_stream_error: Option<String>,
+    log_tail_options: Vec<String>,
+    log_since_options: Vec<String>,
+    /// Show log timestamps in the local time zone instead of UTC
+    log_local_time: bool,
+    log_filter: String,
+    log_filter_regex: bool,
+    /// Compiled from `log_filter`; `None` when the filter is empty or invalid
+    log_filter_matcher: Option<regex::Regex>,
+    log_filter_error: Option<String>,
+    project_logs_name: String,
+    /// Service prefix and text of each line in the project log view
+    project_log_lines: Vec<(String, String)>,
     pending_ops: HashSet<String>,
     health: HashMap<String, HealthStatus>,
+    details_container_id: String,
     details_container_name: String,
+    details_processes: Vec<ProcessInfo>,
     details_data: Option<ContainerDetails>,
     details_loading: bool,
+    details_tab: DetailsTab,
+    json_search: String,
+    files_container_id: String,
+    files_container_name: String,
+    files_path: String,
+    files_entries: Vec<FileEntry>,
+    files_loading: bool,
+    files_error: Option<String>,
+    compare_ids: Vec<String>,
+    compare_names: Vec<String>,
+    compare_left: Option<usize>,
+    compare_right: Option<usize>,
+    compare_result: Option<(ComparableConfig, ComparableConfig)>,
+    compare_loading: bool,
+    transfer_path: String,
+    transfer_status: Option<String>,
     search_query: String,
     collapsed_groups: HashSet<String>,
-    confirm_delete: Option<String>,
+    /// Scaled services shown with their replicas, keyed by `project/service`
+    expanded_services: HashSet<String>,
+    /// Services declared in each project's compose files; `None` while loading or unreadable
+    compose_services: HashMap<String, Option<HashSet<String>>>,
+    /// Recent (CPU %, memory %) samples per container ID, oldest first
+    stats_history: HashMap<String, VecDeque<(f64, f64)>>,
+    /// Isolation-weakening settings per container ID, inspected once per container
+    security_risks: HashMap<String, Vec<SecurityRisk>>,
+    /// Unix start time of each running container; `None` while being looked up
+    started_at: HashMap<String, Option<i64>>,
+    images: Vec<ImageInfo>,
+    /// Image references with a newer digest available upstream
+    outdated_images: HashSet<String>,
+    /// Containers whose image tag has moved on to a newer local image
+    superseded: HashSet<String>,
+    /// Image sizes from the last image listing, keyed by image ID
+    image_sizes: HashMap<String, i64>,
+    /// Image IDs already covered by an image listing request
+    sized_image_ids: HashSet<String>,
+    images_loading: bool,
+    images_status: Option<String>,
+    /// Image being tagged and the reference typed so far
+    tagging: Option<(String, String)>,
+    archive_export: Option<ArchiveExport>,
+    disk_usage: Option<DiskUsage>,
+    disk_usage_loading: bool,
+    disk_usage_status: Option<String>,
+    prune_targets: PruneTargets,
+    pruning: bool,
+    networks: Vec<NetworkInfo>,
+    networks_loading: bool,
+    networks_status: Option<String>,
+    /// Networks the details container could join, for the connect dropdown
+    connectable_networks: Vec<String>,
+    connect_network: Option<usize>,
+    connect_alias: String,
+    network_connect_status: Option<String>,
+    volumes: Vec<VolumeInfo>,
+    volumes_loading: bool,
+    volumes_status: Option<String>,
+    /// Filled in on demand, since the disk usage scan is slow
+    volume_sizes: HashMap<String, i64>,
+    volume_sizes_loading: bool,
+    creating_volume: bool,
+    new_volume_name: String,
+    new_volume_driver: String,
+    /// Comma separated `key=value` labels
+    new_volume_labels: String,
+    run_image: String,
+    run_name: String,
+    /// Comma separated `host:container[/proto]` mappings
+    run_ports: String,
+    run_env_input: String,
+    run_env: Vec<String>,
+    run_status: Option<String>,
+    run_in_progress: bool,
+    /// Local image names offered in the quick-run picker
+    run_image_options: Vec<String>,
+    image_details_ref: String,
+    image_history: Option<(Vec<ImageLayer>, i64)>,
+    image_history_error: Option<String>,
+    /// View to go back to from image details
+    image_details_origin: PopupView,
+    selection_mode: bool,
+    selected: HashSet<String>,
+    pending_action: Option<PendingAction>,
+    confirmation_options: Vec<String>,
+    restart_policy_options: Vec<String>,
     user_initiated_stops: HashSet<String>,
+    user_initiated_starts: HashSet<String>,
+    /// Containers that reported an `oom` event and haven't died yet
+    oom_killed: HashSet<String>,
+    /// Containers the user was told are unhealthy, awaiting a recovery notice
+    notified_unhealthy: HashSet<String>,
+    /// Recent unexpected exits per container, oldest first
+    deaths: HashMap<String, VecDeque<std::time::Instant>>,
+    /// Containers already announced as crash looping
+    crash_looping: HashSet<String>,
+    /// Sampled writable layer sizes per container, oldest first, reaching back just
+    /// past `LAYER_GROWTH_WINDOW`
+    layer_sizes: HashMap<String, VecDeque<(std::time::Instant, i64)>>,
+    /// Notifications kept back during quiet hours, oldest first
+    held_notifications: VecDeque<HeldNotification>,
+    /// Shown notifications still waiting for one of their buttons to be clicked
+    actionable_notifications: usize,
+    /// Launch-time autostart is still running, so its starts aren't announced
+    autostarting: bool,
 }
 
 impl cosmic::Application for DockerApplet {
@@ -84,28 +641,184 @@ impl cosmic::Application for DockerApplet {
     }
 
     fn init(core: Core, _flags: Self::Flags) -> (Self, Task<Action<Self::Message>>) {
+        let (config_handler, config) = Config::load();
         let applet = DockerApplet {
             core,
             popup: None,
+            redactor: build_redactor(&config),
+            new_redaction_pattern: String::new(),
+            registry_server: String::new(),
+            registry_username: String::new(),
+            registry_password: String::new(),
+            registry_status: None,
+            pull_status: None,
+            open_folder_hint: None,
+            memory_limit_draft: None,
+            cpu_limit_draft: None,
+            limits_error: None,
+            error_counts: HashMap::new(),
+            renaming: None,
+            duplicate_name: String::new(),
+            duplicate_port_offset: String::new(),
+            duplicate_status: None,
+            exec_input: String::new(),
+            exec_command: String::new(),
+            exec_output: String::new(),
+            exec_running: false,
+            exec_run: 0,
+            config,
+            config_handler,
             docker_available: true,
+            permission_denied: false,
+            elevating: false,
+            compose_task: None,
+            compose_running: false,
+            compose_run: 0,
+            compose_output: String::new(),
+            docker_unit: None,
+            docker_unit_checked: false,
+            starting_daemon: false,
+            daemon_start_error: None,
             containers: Vec::new(),
             stats: HashMap::new(),
             current_view: PopupView::ContainerList,
             log_container_name: String::new(),
             log_container_id: String::new(),
-            log_content: String::new(),
+            log_lines: VecDeque::new(),
+            log_matches: Vec::new(),
+            log_scroll_offset: 0.0,
+            env_skip_secrets: true,
+            new_alert_pattern: String::new(),
+            alert_pattern_error: None,
+            last_alerts: HashMap::new(),
+            logs_window_id: None,
+            log_buffer_options: LOG_BUFFER_SIZES.iter().map(|size| size.to_string()).collect(),
+            log_show_stdout: true,
+            log_show_stderr: true,
+            log_wrap: true,
             logs_loading: false,
+            log_window: LogWindow::default(),
+            log_range: None,
+            log_range_editing: false,
+            log_range_from: String::new(),
+            log_range_to: String::new(),
+            log_range_error: None,
+            log_stream_error: None,
+            log_tail_options: LOG_TAILS.iter().map(|tail| log_tail_label(*tail)).collect(),
+            log_since_options: LOG_SINCE.iter().map(|since| log_since_label(*since)).collect(),
+            log_local_time: true,
+            log_filter: String::new(),
+            log_filter_regex: false,
+            log_filter_matcher: None,
+            log_filter_error: None,
+            project_logs_name: String::new(),
+            project_log_lines: Vec::new(),
             pending_ops: HashSet::new(),
             health: HashMap::new(),
+            details_container_id: String::new(),
             details_container_name: String::new(),
+            details_processes: Vec::new(),
             details_data: None,
             details_loading: false,
+            details_tab: DetailsTab::Overview,
+            json_search: String::new(),
+            files_container_id: String::new(),
+            files_container_name: String::new(),
+            files_path: String::from("/"),
+            files_entries: Vec::new(),
+            files_loading: false,
+            files_error: None,
+            compare_ids: Vec::new(),
+            compare_names: Vec::new(),
+            compare_left: None,
+            compare_right: None,
+            compare_result: None,
+            compare_loading: false,
+            transfer_path: String::new(),
+            transfer_status: None,
             search_query: String::new(),
             collapsed_groups: HashSet::new(),
-            confirm_delete: None,
+            expanded_services: HashSet::new(),
+            compose_services: HashMap::new(),
+            stats_history: HashMap::new(),
+            security_risks: HashMap::new(),
+            started_at: HashMap::new(),
+            images: Vec::new(),
+            outdated_images: HashSet::new(),
+            superseded: HashSet::new(),
+            image_sizes: HashMap::new(),
+            sized_image_ids: HashSet::new(),
+            images_loading: false,
+            images_status: None,
+            tagging: None,
+            archive_export: None,
+            disk_usage: None,
+            disk_usage_loading: false,
+            disk_usage_status: None,
+            prune_targets: PruneTargets::default(),
+            pruning: false,
+            networks: Vec::new(),
+            networks_loading: false,
+            networks_status: None,
+            connectable_networks: Vec::new(),
+            connect_network: None,
+            connect_alias: String::new(),
+            network_connect_status: None,
+            volumes: Vec::new(),
+            volumes_loading: false,
+            volumes_status: None,
+            volume_sizes: HashMap::new(),
+            volume_sizes_loading: false,
+            creating_volume: false,
+            new_volume_name: String::new(),
+            new_volume_driver: "local".to_string(),
+            new_volume_labels: String::new(),
+            run_image: String::new(),
+            run_name: String::new(),
+            run_ports: String::new(),
+            run_env_input: String::new(),
+            run_env: Vec::new(),
+            run_status: None,
+            run_in_progress: false,
+            run_image_options: Vec::new(),
+            image_details_ref: String::new(),
+            image_history: None,
+            image_history_error: None,
+            image_details_origin: PopupView::Images,
+            selection_mode: false,
+            selected: HashSet::new(),
+            pending_action: None,
+            confirmation_options: Confirmation::ALL
+                .iter()
+                .map(|c| confirmation_label(*c))
+                .collect(),
+            restart_policy_options: RestartPolicy::ALL
+                .iter()
+                .map(|p| restart_policy_label(*p))
+                .collect(),
             user_initiated_stops: HashSet::new(),
+            user_initiated_starts: HashSet::new(),
+            oom_killed: HashSet::new(),
+            notified_unhealthy: HashSet::new(),
+            deaths: HashMap::new(),
+            layer_sizes: HashMap::new(),
+            crash_looping: HashSet::new(),
+            held_notifications: VecDeque::new(),
+            actionable_notifications: 0,
+            autostarting: false,
+        };
+
+        let names = applet.config.autostart_containers.clone();
+        let groups = applet.config.autostart_groups.clone();
+        let task = if names.is_empty() && groups.is_empty() {
+            Task::none()
+        } else {
+            applet.autostarting = true;
+            cosmic::task::future(async move {
+                Message::AutostartFinished(docker::start_autostart(names, groups).await)
+            })
         };
-        (applet, Task::none())
+        (applet, task)
     }
 
     fn update(&mut self, message: Self::Message) -> Task<Action<Self::Message>> {
@@ -113,16 +826,20 @@ impl cosmic::Application for DockerApplet {
             Message::TogglePopup => {
                 return if let Some(popup_id) = self.popup.take() {
                     self.current_view = PopupView::ContainerList;
-                    self.log_content.clear();
-                    self.log_container_id.clear();
+                    if self.logs_window_id.is_none() {
+                        self.clear_log_lines();
+                        self.log_container_id.clear();
+                    }
                     self.search_query.clear();
-                    self.confirm_delete = None;
+                    self.pending_action = None;
                     self.details_data = None;
                     destroy_popup(popup_id)
                 } else {
                     let new_id = Id::unique();
                     self.popup.replace(new_id);
                     self.current_view = PopupView::ContainerList;
+                    // Compose files may have been edited while the popup was closed
+                    self.compose_services.clear();
 
                     let mut popup_settings = self.core.applet.get_popup_settings(
                         self.core.main_window_id().unwrap(),
@@ -146,35 +863,332 @@ impl cosmic::Application for DockerApplet {
                 if self.popup.as_ref() == Some(&id) {
                     self.popup = None;
                     self.current_view = PopupView::ContainerList;
-                    self.log_content.clear();
-                    self.log_container_id.clear();
+                    if self.logs_window_id.is_none() {
+                        self.clear_log_lines();
+                        self.log_container_id.clear();
+                    }
                     self.search_query.clear();
-                    self.confirm_delete = None;
+                    self.pending_action = None;
                     self.details_data = None;
+                } else if self.logs_window_id == Some(id) {
+                    self.logs_window_id = None;
+                    if self.current_view != PopupView::ContainerLogs {
+                        self.clear_log_lines();
+                        self.log_container_id.clear();
+                    }
+                    return cosmic::iced::window::close(id);
+                }
+            }
+
+            Message::PopOutLogs => {
+                if let Some(id) = self.logs_window_id {
+                    return cosmic::iced::window::gain_focus(id);
                 }
+                let (id, open) = cosmic::iced::window::open(cosmic::iced::window::Settings {
+                    size: Size::new(800.0, 600.0),
+                    min_size: Some(Size::new(400.0, 300.0)),
+                    resizable: true,
+                    ..Default::default()
+                });
+                self.logs_window_id = Some(id);
+                // The log stream now lives in the window, so the popup can go
+                self.current_view = PopupView::ContainerList;
+                let close_popup = match self.popup.take() {
+                    Some(popup_id) => destroy_popup(popup_id),
+                    None => Task::none(),
+                };
+                return Task::batch([open.discard(), close_popup]);
             }
 
             Message::DockerEvent(event) => match event {
                 DockerEvent::ContainersUpdated(Ok(containers)) => {
                     self.docker_available = true;
+                    self.permission_denied = false;
+                    self.docker_unit_checked = false;
+                    self.daemon_start_error = None;
                     self.containers = containers;
+                    let mut tasks = Vec::new();
+                    let unsized_ids: Vec<String> = self
+                        .containers
+                        .iter()
+                        .map(|c| c.image_id.clone())
+                        .filter(|id| !id.is_empty() && !self.sized_image_ids.contains(id))
+                        .collect();
+                    if !unsized_ids.is_empty() {
+                        self.sized_image_ids.extend(unsized_ids);
+                        tasks.push(cosmic::task::future(async move {
+                            Message::ImagesLoaded(docker::list_images().await)
+                        }));
+                    }
+
+                    let unloaded_projects: HashSet<String> = self
+                        .containers
+                        .iter()
+                        .filter_map(|c| c.labels.get("com.docker.compose.project"))
+                        .filter(|name| !self.compose_services.contains_key(*name))
+                        .cloned()
+                        .collect();
+                    self.prune_deaths();
+                    // Risks are read once per container, start times again on every start
+                    self.security_risks
+                        .retain(|id, _| self.containers.iter().any(|c| c.id == *id));
+                    self.started_at.retain(|id, _| {
+                        self.containers
+                            .iter()
+                            .any(|c| c.id == *id && c.state == ContainerState::Running)
+                    });
+                    let mut uninspected = Vec::new();
+                    for container in &self.containers {
+                        let running = container.state == ContainerState::Running;
+                        let unstarted = running && !self.started_at.contains_key(&container.id);
+                        if !unstarted && self.security_risks.contains_key(&container.id) {
+                            continue;
+                        }
+                        self.security_risks.entry(container.id.clone()).or_default();
+                        if running {
+                            self.started_at.insert(container.id.clone(), None);
+                        }
+                        uninspected.push(container.id.clone());
+                    }
+                    if !uninspected.is_empty() {
+                        tasks.push(cosmic::task::future(async move {
+                            Message::ContainersInspected(
+                                docker::inspect_containers(uninspected).await,
+                            )
+                        }));
+                    }
+
+                    for name in unloaded_projects {
+                        self.compose_services.insert(name.clone(), None);
+                        let Some(project) =
+                            ComposeProject::from_containers(&name, &self.containers)
+                        else {
+                            continue;
+                        };
+                        tasks.push(cosmic::task::future(async move {
+                            let services = docker::fetch_compose_services(project).await;
+                            Message::ComposeServicesLoaded(name, services)
+                        }));
+                    }
+                    return Task::batch(tasks);
                 }
-                DockerEvent::ContainersUpdated(Err(_)) => {
+                DockerEvent::ContainersUpdated(Err(e)) => {
                     self.docker_available = false;
+                    self.permission_denied = elevate::is_permission_denied(&e);
                     self.containers.clear();
                     self.stats.clear();
+                    if !self.permission_denied && !self.docker_unit_checked {
+                        self.docker_unit_checked = true;
+                        return cosmic::task::future(async move {
+                            Message::DockerUnitDetected(service::detect().await)
+                        });
+                    }
+                }
+                DockerEvent::DaemonDisconnected => {
+                    self.docker_available = false;
+                }
+                DockerEvent::DaemonReconnected => {
+                    return cosmic::task::future(async move {
+                        Message::DockerEvent(DockerEvent::ContainersUpdated(
+                            docker::fetch_containers().await,
+                        ))
+                    });
                 }
                 DockerEvent::StatsUpdated(stats) => {
-                    self.stats = stats;
+                    // Merged rather than replaced, so switching between the background
+                    // and popup samplers keeps showing the last known numbers
+                    let running: HashSet<&String> = self
+                        .containers
+                        .iter()
+                        .filter(|c| c.state == ContainerState::Running)
+                        .map(|c| &c.id)
+                        .collect();
+                    self.stats_history.retain(|id, _| running.contains(id));
+                    self.stats.retain(|id, _| running.contains(id));
+                    for (id, sample) in &stats {
+                        let history = self.stats_history.entry(id.clone()).or_default();
+                        history.push_back((sample.cpu_percent, sample.memory_percent));
+                        if history.len() > STATS_HISTORY {
+                            history.pop_front();
+                        }
+                    }
+                    self.stats.extend(stats);
                 }
                 DockerEvent::HealthUpdated(h) => {
                     self.health = h;
                 }
-                DockerEvent::LogLine(id, line) => {
+                DockerEvent::LogLine(id, stream, line) => {
+                    if id == self.log_container_id {
+                        self.logs_loading = false;
+                        self.push_log_chunk(stream, &line);
+                    }
+                }
+                DockerEvent::LogStreamFailed(id, error) => {
                     if id == self.log_container_id {
+                        tracing::error!("Failed to follow logs: {}", error);
                         self.logs_loading = false;
-                        self.log_content.push_str(&line);
+                        self.log_stream_error = Some(error);
+                    }
+                }
+                DockerEvent::ProjectLogLine(project, prefix, line) => {
+                    if project == self.project_logs_name {
+                        self.project_log_lines.push((prefix, line));
+                        let overflow =
+                            self.project_log_lines.len().saturating_sub(PROJECT_LOG_LINES);
+                        self.project_log_lines.drain(..overflow);
+                    }
+                }
+                DockerEvent::ImageUpdates(outdated) => {
+                    if self.config.notify_image_updates {
+                        let mut newly_outdated: Vec<&String> =
+                            outdated.difference(&self.outdated_images).collect();
+                        newly_outdated.sort();
+                        if !newly_outdated.is_empty() {
+                            let names: Vec<&str> =
+                                newly_outdated.iter().map(|s| s.as_str()).collect();
+                            let body = names.join(", ");
+                            self.notify(
+                                fl!("image-updates"),
+                                body,
+                                "software-update-available-symbolic",
+                            );
+                        }
+                    }
+                    self.outdated_images = outdated;
+                }
+                DockerEvent::SupersededUpdated(superseded) => {
+                    self.superseded = superseded;
+                }
+                DockerEvent::LayerSizesUpdated(sizes) => {
+                    let now = std::time::Instant::now();
+                    self.layer_sizes.retain(|id, _| sizes.contains_key(id));
+                    for (id, size) in sizes {
+                        let samples = self.layer_sizes.entry(id).or_default();
+                        samples.push_back((now, size));
+                        // Keep the newest sample from before the window as the baseline
+                        while samples
+                            .get(1)
+                            .is_some_and(|(at, _)| now - *at >= LAYER_GROWTH_WINDOW)
+                        {
+                            samples.pop_front();
+                        }
+                    }
+                }
+                DockerEvent::ProcessesUpdated(result) => {
+                    return cosmic::Application::update(self, Message::ProcessesReceived(result));
+                }
+                DockerEvent::ArchiveProgress(written) => {
+                    if let Some(export) = &mut self.archive_export {
+                        export.written = written;
+                    }
+                }
+                DockerEvent::ArchiveFinished(result) => {
+                    let Some(export) = self.archive_export.take() else {
+                        return Task::none();
+                    };
+                    let status = Some(match result {
+                        Ok(dest) => fl!("saved-to", path = dest.display().to_string()),
+                        Err(e) => {
+                            tracing::error!("Failed to write {} archive: {}", export.name, e);
+                            e
+                        }
+                    });
+                    match export.kind {
+                        ArchiveKind::Image => self.images_status = status,
+                        ArchiveKind::Volume => self.volumes_status = status,
+                    }
+                }
+                DockerEvent::ComposeOutput(run, line) => {
+                    if run == self.compose_run {
+                        self.compose_output.push_str(&line);
+                        self.compose_output.push('\n');
+                    }
+                }
+                DockerEvent::ComposeFinished(run, result) => {
+                    if run == self.compose_run {
+                        self.compose_running = false;
+                        if let Some((project, _)) = &self.compose_task {
+                            self.compose_services.remove(&project.name);
+                            // Containers compose left as they were never sent a die
+                            for container in &self.containers {
+                                if container.labels.get("com.docker.compose.project")
+                                    == Some(&project.name)
+                                {
+                                    self.user_initiated_stops.remove(&container.id);
+                                }
+                            }
+                        }
+                        if let Some((project, ComposeAction::Update)) = &self.compose_task {
+                            let (body, icon) = match &result {
+                                Ok(()) => (
+                                    fl!("stack-updated", name = project.name.as_str()),
+                                    "software-update-available-symbolic",
+                                ),
+                                Err(_) => (
+                                    fl!("stack-update-failed", name = project.name.as_str()),
+                                    "dialog-warning-symbolic",
+                                ),
+                            };
+                            self.notify("Docker".to_string(), body, icon);
+                        }
+                        match result {
+                            Ok(()) => self.compose_output.push_str(&fl!("compose-done")),
+                            Err(e) => {
+                                tracing::error!("Compose run failed: {}", e);
+                                self.compose_output.push_str(&e);
+                            }
+                        }
+                        return cosmic::task::future(async move {
+                            Message::DockerEvent(DockerEvent::ContainersUpdated(
+                                docker::fetch_containers().await,
+                            ))
+                        });
+                    }
+                }
+                DockerEvent::ExecOutput(run, chunk) => {
+                    if run == self.exec_run {
+                        self.exec_output.push_str(&chunk);
+                    }
+                }
+                DockerEvent::ExecFinished(run, result) => {
+                    if run == self.exec_run {
+                        self.exec_running = false;
+                        match result {
+                            Ok(0) => {}
+                            Ok(code) => {
+                                self.exec_output.push('\n');
+                                self.exec_output
+                                    .push_str(&fl!("exit-code", code = code.to_string()));
+                            }
+                            Err(e) => self.exec_output.push_str(&e),
+                        }
+                    }
+                }
+                DockerEvent::ErrorLogged(id) => {
+                    *self.error_counts.entry(id).or_insert(0) += 1;
+                }
+                DockerEvent::LogAlertMatched(id, pattern, line) => {
+                    let now = std::time::Instant::now();
+                    let key = (id, pattern);
+                    if self
+                        .last_alerts
+                        .get(&key)
+                        .is_some_and(|last| now.duration_since(*last) < LOG_ALERT_COOLDOWN)
+                    {
+                        return Task::none();
                     }
+                    let name = self
+                        .containers
+                        .iter()
+                        .find(|c| c.id == key.0)
+                        .map(|c| c.name.clone())
+                        .unwrap_or_else(|| key.0.clone());
+                    let line: String = ansi::strip(&self.redactor.redact(&line))
+                        .chars()
+                        .take(200)
+                        .collect();
+                    self.last_alerts.insert(key, now);
+                    self.notify(fl!("log-alert", name = name), line, "dialog-warning-symbolic");
                 }
                 DockerEvent::ContainerLifecycleEvent {
                     action,
@@ -182,39 +1196,107 @@ impl cosmic::Application for DockerApplet {
                     container_name,
                     attributes,
                 } => {
+                    // Catches restarts that happen between two list refreshes
+                    if action == "start" {
+                        let now = chrono::Utc::now().timestamp();
+                        self.started_at.insert(container_id.clone(), Some(now));
+                        // Compose creates containers under IDs not known up front
+                        let by_compose = self.compose_running
+                            && self.compose_task.as_ref().is_some_and(|(project, _)| {
+                                attributes.get("com.docker.compose.project") == Some(&project.name)
+                            });
+                        // Restart policy restarts follow an exit that was already reported
+                        let by_policy = self
+                            .deaths
+                            .get(&container_id)
+                            .and_then(|deaths| deaths.back())
+                            .is_some_and(|died| died.elapsed() < CRASH_LOOP_WINDOW);
+                        if !self.user_initiated_starts.remove(&container_id)
+                            && !by_compose
+                            && !by_policy
+                            && self.config.notify_container_starts
+                            && !self.autostarting
+                        {
+                            self.notify(
+                                "Docker".to_string(),
+                                fl!("container-started", name = container_name.as_str()),
+                                "media-playback-start-symbolic",
+                            );
+                        }
+                    }
+                    if action == "oom" {
+                        self.oom_killed.insert(container_id.clone());
+                    }
                     if action == "die" {
+                        // Exit code 137 is any SIGKILL, so only the daemon's `oom` event counts
+                        let oom = self.oom_killed.remove(&container_id);
                         if !self.user_initiated_stops.remove(&container_id) {
-                            let _ = notify_rust::Notification::new()
-                                .summary("Docker")
-                                .body(&fl!(
-                                    "container-stopped",
-                                    name = container_name.as_str()
-                                ))
-                                .icon("dialog-warning-symbolic")
-                                .show();
+                            self.prune_deaths();
+                            let deaths = self.deaths.entry(container_id.clone()).or_default();
+                            deaths.push_back(std::time::Instant::now());
+                            if deaths.len() >= CRASH_LOOP_DEATHS {
+                                // One notification per loop, however long it lasts
+                                if self.crash_looping.insert(container_id) {
+                                    let body = fl!(
+                                        "container-crash-looping",
+                                        name = container_name.as_str(),
+                                        count = deaths.len().to_string(),
+                                        minutes = crash_loop_minutes()
+                                    );
+                                    self.notify("Docker".to_string(), body, "dialog-error-symbolic");
+                                }
+                                return Task::none();
+                            }
+                            let body = if oom {
+                                fl!("container-oom-killed", name = container_name.as_str())
+                            } else {
+                                fl!("container-stopped", name = container_name.as_str())
+                            };
+                            if self.actionable_notifications >= ACTIONABLE_NOTIFICATIONS {
+                                self.notify("Docker".to_string(), body, "dialog-warning-symbolic");
+                                return Task::none();
+                            }
+                            if self.in_quiet_hours() {
+                                self.hold_notification("Docker".to_string(), body);
+                                return Task::none();
+                            }
+                            self.actionable_notifications += 1;
+                            return stopped_notification(container_id, container_name, body);
                         }
                     }
-                    if action == "health_status" {
+                    // The daemon sends the status in the action ("health_status: healthy")
+                    if let Some(status) = action.strip_prefix("health_status") {
                         let health_status = attributes
                             .get("health_status")
                             .map(|s| s.as_str())
-                            .unwrap_or("");
+                            .unwrap_or(status.trim_start_matches(':').trim());
                         if health_status == "unhealthy" {
-                            let _ = notify_rust::Notification::new()
-                                .summary("Docker")
-                                .body(&fl!(
-                                    "container-unhealthy",
-                                    name = container_name.as_str()
-                                ))
-                                .icon("dialog-warning-symbolic")
-                                .show();
+                            self.notified_unhealthy.insert(container_id.clone());
+                            self.notify(
+                                "Docker".to_string(),
+                                fl!("container-unhealthy", name = container_name.as_str()),
+                                "dialog-warning-symbolic",
+                            );
+                        } else if health_status == "healthy"
+                            && self.notified_unhealthy.remove(&container_id)
+                            && self.config.notify_recovery
+                        {
+                            self.notify(
+                                "Docker".to_string(),
+                                fl!("container-recovered", name = container_name.as_str()),
+                                "emblem-ok-symbolic",
+                            );
                         }
                     }
+                    if action == "destroy" {
+                        self.notified_unhealthy.remove(&container_id);
+                    }
                 }
             },
 
             Message::StartContainer(id) => {
                 self.pending_ops.insert(id.clone());
+                self.user_initiated_starts.insert(id.clone());
                 return cosmic::task::future(async move {
                     Message::ActionCompleted(docker::start_container(id).await)
                 });
@@ -231,11 +1313,45 @@ impl cosmic::Application for DockerApplet {
             Message::RestartContainer(id) => {
                 self.pending_ops.insert(id.clone());
                 self.user_initiated_stops.insert(id.clone());
+                self.user_initiated_starts.insert(id.clone());
                 return cosmic::task::future(async move {
                     Message::ActionCompleted(docker::restart_container(id).await)
                 });
             }
 
+            Message::RecreateContainer(id) => {
+                self.pending_ops.insert(id.clone());
+                self.superseded.remove(&id);
+                if self
+                    .containers
+                    .iter()
+                    .any(|c| c.id == id && c.state == ContainerState::Running)
+                {
+                    // Also covers the original being started again if recreating fails
+                    self.user_initiated_stops.insert(id.clone());
+                    self.user_initiated_starts.insert(id.clone());
+                }
+                return cosmic::task::future(async move {
+                    let result = docker::recreate_container(id.clone()).await;
+                    Message::ContainerRecreated(id, result)
+                });
+            }
+
+            Message::ContainerRecreated(id, result) => match result {
+                Ok((new_id, was_running)) => {
+                    self.user_initiated_starts.remove(&id);
+                    if !was_running {
+                        return self.update(Message::ActionCompleted(Ok(id)));
+                    }
+                    self.user_initiated_starts.insert(new_id.clone());
+                    return cosmic::task::future(async move {
+                        let result = docker::start_container(new_id).await.map(|_| id);
+                        Message::ActionCompleted(result)
+                    });
+                }
+                Err(e) => return self.update(Message::ActionCompleted(Err(e))),
+            },
+
             Message::ActionCompleted(result) => match &result {
                 Ok(id) => {
                     self.pending_ops.remove(id);
@@ -248,23 +1364,197 @@ impl cosmic::Application for DockerApplet {
 
             Message::ShowLogs(id, name) => {
                 self.current_view = PopupView::ContainerLogs;
-                self.log_container_name = name;
-                self.log_container_id = id;
-                self.log_content.clear();
+                self.load_logs(id, name);
+            }
+
+            Message::StoppedNotificationAction(id, name, action) => {
+                self.actionable_notifications = self.actionable_notifications.saturating_sub(1);
+                match action.as_str() {
+                    "restart" => {
+                        self.pending_ops.insert(id.clone());
+                        self.user_initiated_starts.insert(id.clone());
+                        return cosmic::task::future(async move {
+                            Message::ActionCompleted(docker::start_container(id).await)
+                        });
+                    }
+                    "logs" => {
+                        self.load_logs(id, name);
+                        return self.update(Message::PopOutLogs);
+                    }
+                    _ => {}
+                }
+            }
+
+            Message::ToggleLogTimestamps(enabled) => {
+                // The stream restarts with the new option, so start over
+                self.log_window.timestamps = enabled;
+                self.clear_log_lines();
+                self.logs_loading = true;
+                if let Some((since, until)) = self.log_range {
+                    return self.fetch_log_range(since, until);
+                }
+            }
+
+            Message::SetLogTail(index) => {
+                if let Some(tail) = LOG_TAILS.get(index) {
+                    self.log_window.tail = *tail;
+                    self.log_range = None;
+                    self.clear_log_lines();
+                    self.logs_loading = true;
+                }
+            }
+
+            Message::SetLogSince(index) => {
+                if let Some(since) = LOG_SINCE.get(index) {
+                    self.log_window.since = *since;
+                    self.log_range = None;
+                    self.clear_log_lines();
+                    self.logs_loading = true;
+                }
+            }
+
+            Message::ToggleLogRangeEditor => {
+                self.log_range_editing = !self.log_range_editing;
+                if self.log_range_editing && self.log_range_from.is_empty() {
+                    let now = chrono::Local::now();
+                    let hour_ago = now - chrono::Duration::hours(1);
+                    self.log_range_from = hour_ago.format(LOG_RANGE_FORMAT).to_string();
+                    self.log_range_to = now.format(LOG_RANGE_FORMAT).to_string();
+                }
+            }
+
+            Message::LogRangeFromInput(input) => {
+                self.log_range_from = input;
+            }
+
+            Message::LogRangeToInput(input) => {
+                self.log_range_to = input;
+            }
+
+            Message::ApplyLogRange => {
+                let since = parse_local_time(&self.log_range_from);
+                // An empty end means up to now
+                let until = if self.log_range_to.trim().is_empty() {
+                    Some(chrono::Local::now().timestamp())
+                } else {
+                    parse_local_time(&self.log_range_to)
+                };
+                match (since, until) {
+                    (Some(since), Some(until)) if since < until => {
+                        self.log_range_error = None;
+                        self.log_range = Some((since, until));
+                        self.clear_log_lines();
+                        self.logs_loading = true;
+                        return self.fetch_log_range(since, until);
+                    }
+                    (Some(_), Some(_)) => {
+                        self.log_range_error = Some(fl!("log-range-order"));
+                    }
+                    _ => {
+                        self.log_range_error = Some(fl!("log-range-invalid"));
+                    }
+                }
+            }
+
+            Message::ClearLogRange => {
+                self.log_range = None;
+                self.log_range_error = None;
+                self.clear_log_lines();
                 self.logs_loading = true;
             }
 
+            Message::LogRangeLoaded(id, result) => {
+                if id == self.log_container_id && self.log_range.is_some() {
+                    self.logs_loading = false;
+                    match result {
+                        Ok(logs) => {
+                            for (stream, chunk) in logs {
+                                self.push_log_chunk(stream, &chunk);
+                            }
+                        }
+                        Err(e) => {
+                            tracing::error!("Failed to load logs: {}", e);
+                            self.log_range_error = Some(e);
+                        }
+                    }
+                }
+            }
+
+            Message::ToggleLogStream(stream, enabled) => {
+                match stream {
+                    LogStream::Stdout => self.log_show_stdout = enabled,
+                    LogStream::Stderr => self.log_show_stderr = enabled,
+                }
+                self.refresh_log_matches();
+            }
+
+            Message::ToggleLogWrap(enabled) => {
+                self.log_wrap = enabled;
+            }
+
+            Message::LogScrolled(offset) => {
+                self.log_scroll_offset = offset;
+            }
+
+            Message::SetLogBufferSize(index) => {
+                if let Some(size) = LOG_BUFFER_SIZES.get(index) {
+                    self.config.log_buffer_lines = *size;
+                    let overflow = self.log_lines.len().saturating_sub(*size);
+                    self.drop_oldest_log_lines(overflow);
+                    self.save_config();
+                }
+            }
+
+            Message::ToggleLogLocalTime(enabled) => {
+                self.log_local_time = enabled;
+            }
+
+            Message::LogFilterChanged(filter) => {
+                self.log_filter = filter;
+                self.update_log_filter();
+            }
+
+            Message::ToggleLogFilterRegex(enabled) => {
+                self.log_filter_regex = enabled;
+                self.update_log_filter();
+            }
+
+            Message::ShowProjectLogs(group_name) => {
+                self.current_view = PopupView::ProjectLogs;
+                self.project_logs_name = group_name;
+                self.project_log_lines.clear();
+            }
+
             Message::BackToList => {
                 self.current_view = PopupView::ContainerList;
-                self.log_content.clear();
-                self.log_container_id.clear();
+                if self.logs_window_id.is_none() {
+                    self.clear_log_lines();
+                    self.log_container_id.clear();
+                }
+                self.project_logs_name.clear();
+                self.project_log_lines.clear();
                 self.details_data = None;
+                self.renaming = None;
             }
 
             Message::OpenInBrowser(port) => {
                 let _ = open::that(format!("http://localhost:{}", port));
             }
 
+            Message::OpenFolder(path) => {
+                // Volume data under /var/lib/docker is only readable by root
+                self.open_folder_hint = match std::fs::read_dir(&path) {
+                    Ok(_) => {
+                        let _ = open::that(&path);
+                        None
+                    }
+                    Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => {
+                        Some(fl!("folder-needs-root", path = path.as_str()))
+                    }
+                    Err(e) => Some(e.to_string()),
+                };
+            }
+
             Message::SearchChanged(q) => {
                 self.search_query = q;
             }
@@ -279,6 +1569,12 @@ impl cosmic::Application for DockerApplet {
                 }
             }
 
+            Message::ToggleService(key) => {
+                if !self.expanded_services.remove(&key) {
+                    self.expanded_services.insert(key);
+                }
+            }
+
             Message::StopAll => {
                 let ids: Vec<String> = self
                     .containers
@@ -311,6 +1607,7 @@ impl cosmic::Application for DockerApplet {
                     .collect();
                 for id in &ids {
                     self.pending_ops.insert(id.clone());
+                    self.user_initiated_starts.insert(id.clone());
                 }
                 return cosmic::task::future(async move {
                     let mut last_result = Ok(String::new());
@@ -324,25 +1621,77 @@ impl cosmic::Application for DockerApplet {
                 });
             }
 
-            Message::StopGroup(group_name) => {
+            Message::RestartUnhealthy => {
                 let ids: Vec<String> = self
-                    .containers
+                    .health
                     .iter()
-                    .filter(|c| {
-                        c.state == ContainerState::Running
-                            && c.labels.get("com.docker.compose.project")
-                                == Some(&group_name)
-                    })
-                    .map(|c| c.id.clone())
+                    .filter(|(_, status)| **status == HealthStatus::Unhealthy)
+                    .map(|(id, _)| id.clone())
                     .collect();
-                for id in &ids {
+                let tasks = ids.into_iter().map(|id| {
                     self.pending_ops.insert(id.clone());
                     self.user_initiated_stops.insert(id.clone());
+                    self.user_initiated_starts.insert(id.clone());
+                    cosmic::task::future(async move {
+                        Message::ActionCompleted(docker::restart_container(id).await)
+                    })
+                });
+                return Task::batch(tasks.collect::<Vec<_>>());
+            }
+
+            Message::ToggleSelectionMode => {
+                self.selection_mode = !self.selection_mode;
+                self.selected.clear();
+            }
+
+            Message::ToggleSelected(id, selected) => {
+                if selected {
+                    self.selected.insert(id);
+                } else {
+                    self.selected.remove(&id);
+                }
+            }
+
+            Message::RunBulkAction(action) => {
+                let targets: Vec<(String, ContainerState)> = self
+                    .containers
+                    .iter()
+                    .filter(|c| self.selected.contains(&c.id))
+                    .map(|c| (c.id.clone(), c.state.clone()))
+                    .collect();
+                self.selected.clear();
+                for (id, state) in &targets {
+                    self.pending_ops.insert(id.clone());
+                    // Only containers that are up will report a `die`
+                    if matches!(
+                        action,
+                        BulkAction::Stop | BulkAction::Restart | BulkAction::Delete
+                    ) && *state != ContainerState::Stopped
+                    {
+                        self.user_initiated_stops.insert(id.clone());
+                    }
+                    // Starting a running container changes nothing and reports no `start`
+                    if action == BulkAction::Restart
+                        || (action == BulkAction::Start && *state != ContainerState::Running)
+                    {
+                        self.user_initiated_starts.insert(id.clone());
+                    }
                 }
                 return cosmic::task::future(async move {
                     let mut last_result = Ok(String::new());
-                    for id in ids {
-                        last_result = docker::stop_container(id).await;
+                    for (id, state) in targets {
+                        last_result = match action {
+                            BulkAction::Start => docker::start_container(id).await,
+                            BulkAction::Stop => docker::stop_container(id).await,
+                            BulkAction::Restart => docker::restart_container(id).await,
+                            BulkAction::Delete => {
+                                let flags = RemoveFlags {
+                                    force: state != ContainerState::Stopped,
+                                    volumes: false,
+                                };
+                                docker::remove_container(id, flags).await
+                            }
+                        };
                         if last_result.is_err() {
                             break;
                         }
@@ -351,24 +1700,78 @@ impl cosmic::Application for DockerApplet {
                 });
             }
 
-            Message::StartGroup(group_name) => {
-                let ids: Vec<String> = self
+            Message::RunCompose(group_name, action) => {
+                if self.compose_running {
+                    return Task::none();
+                }
+                let Some(project) = ComposeProject::from_containers(&group_name, &self.containers)
+                else {
+                    return Task::none();
+                };
+                // Down stops every container; up and update stop the running ones
+                // they recreate
+                self.user_initiated_stops.extend(
+                    self.containers
+                        .iter()
+                        .filter(|c| {
+                            c.labels.get("com.docker.compose.project") == Some(&group_name)
+                                && (action == ComposeAction::Down
+                                    || c.state == ContainerState::Running)
+                        })
+                        .map(|c| c.id.clone()),
+                );
+                self.compose_task = Some((project, action));
+                self.compose_running = true;
+                self.compose_run += 1;
+                self.compose_output.clear();
+            }
+
+            Message::ComposeServicesLoaded(name, result) => match result {
+                Ok(services) => {
+                    self.compose_services.insert(name, Some(services));
+                }
+                Err(e) => tracing::warn!("Failed to read services of {}: {}", name, e),
+            },
+
+            Message::ContainersInspected(inspections) => {
+                for (id, inspection) in inspections {
+                    // Only containers still running wait on a start time
+                    if let Some(started_at) = self.started_at.get_mut(&id) {
+                        *started_at = started_at.or(inspection.started_at);
+                    }
+                    self.security_risks.insert(id, inspection.security_risks);
+                }
+            }
+
+            Message::Tick => {}
+
+            Message::RemoveOrphans(group_name) => {
+                let targets: Vec<(String, bool)> = self
                     .containers
                     .iter()
                     .filter(|c| {
-                        c.state != ContainerState::Running
-                            && c.labels.get("com.docker.compose.project")
-                                == Some(&group_name)
+                        c.labels.get("com.docker.compose.project") == Some(&group_name)
+                            && self.is_orphan(c)
                     })
-                    .map(|c| c.id.clone())
+                    .map(|c| (c.id.clone(), c.state != ContainerState::Stopped))
                     .collect();
-                for id in &ids {
+                for (id, up) in &targets {
                     self.pending_ops.insert(id.clone());
+                    if *up {
+                        self.user_initiated_stops.insert(id.clone());
+                    }
                 }
                 return cosmic::task::future(async move {
+                    // Stopped gracefully first rather than force-removed
                     let mut last_result = Ok(String::new());
-                    for id in ids {
-                        last_result = docker::start_container(id).await;
+                    for (id, up) in targets {
+                        if up {
+                            last_result = docker::stop_container(id.clone()).await;
+                        }
+                        if last_result.is_ok() {
+                            last_result =
+                                docker::remove_container(id, RemoveFlags::default()).await;
+                        }
                         if last_result.is_err() {
                             break;
                         }
@@ -377,738 +1780,6034 @@ impl cosmic::Application for DockerApplet {
                 });
             }
 
-            Message::DeleteContainer(id) => {
-                self.confirm_delete = Some(id);
-            }
-
-            Message::ConfirmDelete(id) => {
-                self.confirm_delete = None;
-                self.pending_ops.insert(id.clone());
-                return cosmic::task::future(async move {
-                    Message::ActionCompleted(docker::remove_container(id).await)
-                });
+            Message::OpenComposeFile(path) => {
+                if let Err(e) = open::that(&path) {
+                    tracing::error!("Failed to open {}: {}", path.display(), e);
+                }
             }
 
-            Message::CancelDelete => {
-                self.confirm_delete = None;
+            Message::CloseComposeOutput => {
+                if !self.compose_running {
+                    self.compose_task = None;
+                    self.compose_output.clear();
+                }
             }
 
-            Message::CopyContainerId(id) => {
-                let short_id = if id.len() > 12 {
-                    id[..12].to_string()
-                } else {
-                    id.clone()
-                };
-                let _ = std::process::Command::new("wl-copy")
-                    .arg(&short_id)
-                    .spawn();
+            Message::StopGroup(group_name) => {
+                let ids: Vec<String> = self
+                    .containers
+                    .iter()
+                    .filter(|c| {
+                        c.state == ContainerState::Running
+                            && c.labels.get("com.docker.compose.project")
+                                == Some(&group_name)
+                    })
+                    .map(|c| c.id.clone())
+                    .collect();
+                for id in &ids {
+                    self.pending_ops.insert(id.clone());
+                    self.user_initiated_stops.insert(id.clone());
+                }
+                return cosmic::task::future(async move {
+                    let mut last_result = Ok(String::new());
+                    for id in ids {
+                        last_result = docker::stop_container(id).await;
+                        if last_result.is_err() {
+                            break;
+                        }
+                    }
+                    Message::ActionCompleted(last_result)
+                });
+            }
+
+            Message::PauseGroup(group_name) => {
+                let ids: Vec<String> = self
+                    .containers
+                    .iter()
+                    .filter(|c| {
+                        c.state == ContainerState::Running
+                            && c.labels.get("com.docker.compose.project")
+                                == Some(&group_name)
+                    })
+                    .map(|c| c.id.clone())
+                    .collect();
+                for id in &ids {
+                    self.pending_ops.insert(id.clone());
+                }
+                return cosmic::task::future(async move {
+                    let mut last_result = Ok(String::new());
+                    for id in ids {
+                        last_result = docker::pause_container(id).await;
+                        if last_result.is_err() {
+                            break;
+                        }
+                    }
+                    Message::ActionCompleted(last_result)
+                });
+            }
+
+            Message::UnpauseGroup(group_name) => {
+                let ids: Vec<String> = self
+                    .containers
+                    .iter()
+                    .filter(|c| {
+                        c.state == ContainerState::Paused
+                            && c.labels.get("com.docker.compose.project")
+                                == Some(&group_name)
+                    })
+                    .map(|c| c.id.clone())
+                    .collect();
+                for id in &ids {
+                    self.pending_ops.insert(id.clone());
+                }
+                return cosmic::task::future(async move {
+                    let mut last_result = Ok(String::new());
+                    for id in ids {
+                        last_result = docker::unpause_container(id).await;
+                        if last_result.is_err() {
+                            break;
+                        }
+                    }
+                    Message::ActionCompleted(last_result)
+                });
+            }
+
+            Message::StartGroup(group_name) => {
+                let ids: Vec<String> = self
+                    .containers
+                    .iter()
+                    .filter(|c| {
+                        c.state != ContainerState::Running
+                            && c.labels.get("com.docker.compose.project")
+                                == Some(&group_name)
+                    })
+                    .map(|c| c.id.clone())
+                    .collect();
+                for id in &ids {
+                    self.pending_ops.insert(id.clone());
+                    self.user_initiated_starts.insert(id.clone());
+                }
+                return cosmic::task::future(async move {
+                    let mut last_result = Ok(String::new());
+                    for id in ids {
+                        last_result = docker::start_container(id).await;
+                        if last_result.is_err() {
+                            break;
+                        }
+                    }
+                    Message::ActionCompleted(last_result)
+                });
+            }
+
+            Message::ExportGroupLogs(group_name) => {
+                let members: Vec<(String, String)> = self
+                    .containers
+                    .iter()
+                    .filter(|c| c.labels.get("com.docker.compose.project") == Some(&group_name))
+                    .map(|c| {
+                        // Replicas of a scaled service each need their own entry
+                        let entry = match (c.compose_service(), c.compose_replica()) {
+                            (Some(service), Some(replica)) => format!("{}-{}", service, replica),
+                            _ => c.name.clone(),
+                        };
+                        (entry, c.id.clone())
+                    })
+                    .collect();
+                let redactor = self.redactor.clone();
+                let title = fl!("export-logs");
+                let file_name = format!("{}-logs.tar", group_name);
+                return cosmic::task::future(async move {
+                    let result = async {
+                        let mut files = Vec::new();
+                        for (entry, id) in members {
+                            let logs = docker::fetch_recent_logs(id, LOG_EXPORT_TAIL).await?;
+                            let logs = redactor.redact(&logs).into_owned();
+                            files.push((format!("{}.log", entry), logs.into_bytes()));
+                        }
+                        let archive = docker::build_tar(&files)?;
+                        let dest = portal::choose_save_path(title, file_name).await?;
+                        std::fs::write(&dest, archive).map_err(|e| e.to_string())?;
+                        Ok::<_, String>(fl!("saved-to", path = dest.display().to_string()))
+                    }
+                    .await;
+                    Message::LogsExported(result)
+                });
+            }
+
+            Message::SaveLogs => {
+                let logs = ansi::strip(&self.redactor.redact(&self.visible_log())).into_owned();
+                let title = fl!("save-logs");
+                let file_name = format!("{}.log", self.log_container_name);
+                return cosmic::task::future(async move {
+                    let result = async {
+                        let dest = portal::choose_save_path(title, file_name).await?;
+                        std::fs::write(&dest, logs).map_err(|e| e.to_string())?;
+                        Ok::<_, String>(fl!("saved-to", path = dest.display().to_string()))
+                    }
+                    .await;
+                    Message::LogsExported(result)
+                });
+            }
+
+            Message::LogsExported(result) => {
+                let body = match result {
+                    Ok(saved) => saved,
+                    Err(e) => {
+                        tracing::error!("Failed to export logs: {}", e);
+                        e
+                    }
+                };
+                self.notify(fl!("export-logs"), body, "document-save-symbolic");
+            }
+
+            Message::ExportStats => {
+                let csv = self.stats_csv();
+                let json = self.stats_json();
+                let title = fl!("export-stats");
+                return cosmic::task::future(async move {
+                    let result = async {
+                        let dest =
+                            portal::choose_save_path(title, "docker-stats.csv".to_string()).await?;
+                        // The extension the user picked decides the format
+                        let is_json = dest.extension().is_some_and(|ext| ext == "json");
+                        let content = if is_json { json } else { csv };
+                        std::fs::write(&dest, content).map_err(|e| e.to_string())?;
+                        Ok::<_, String>(fl!("saved-to", path = dest.display().to_string()))
+                    }
+                    .await;
+                    Message::StatsExported(result)
+                });
+            }
+
+            Message::StatsExported(result) => {
+                let body = match result {
+                    Ok(saved) => saved,
+                    Err(e) => {
+                        tracing::error!("Failed to export stats: {}", e);
+                        e
+                    }
+                };
+                self.notify(fl!("export-stats"), body, "document-save-symbolic");
+            }
+
+            Message::DeleteContainer(id, flags) => {
+                self.pending_ops.insert(id.clone());
+                return cosmic::task::future(async move {
+                    Message::ActionCompleted(docker::remove_container(id, flags).await)
+                });
+            }
+
+            Message::SetRemoveFlags(flags) => {
+                if let Some(pending) = &mut self.pending_action {
+                    if let Message::DeleteContainer(_, pending_flags) = &mut pending.message {
+                        *pending_flags = flags;
+                        // Forcing or dropping volumes may need a stronger confirmation
+                        pending.category = action_category(&pending.message);
+                        pending.confirmation = pending.category.confirmation(&self.config);
+                    }
+                }
+            }
+
+            Message::RequestAction(action) => {
+                let mut category = action_category(&action);
+                // Running members of a bulk delete are force-removed, like a forced delete
+                if matches!(*action, Message::RunBulkAction(BulkAction::Delete))
+                    && self.selected_running() > 0
+                {
+                    category = ActionCategory::Dangerous;
+                }
+                let confirmation = category.confirmation(&self.config);
+                if confirmation == Confirmation::Never {
+                    return cosmic::Application::update(self, *action);
+                }
+                let (target_id, target_name) = self.action_target(&action);
+                self.pending_action = Some(PendingAction {
+                    message: *action,
+                    category,
+                    confirmation,
+                    target_id,
+                    target_name,
+                    typed: String::new(),
+                });
+            }
+
+            Message::ConfirmInputChanged(typed) => {
+                if let Some(pending) = &mut self.pending_action {
+                    pending.typed = typed;
+                }
+            }
+
+            Message::ConfirmAction => {
+                if let Some(pending) = self.pending_action.take() {
+                    if !pending.is_confirmed() {
+                        self.pending_action = Some(pending);
+                        return Task::none();
+                    }
+                    return cosmic::Application::update(self, pending.message);
+                }
+            }
+
+            Message::CancelAction => {
+                self.pending_action = None;
+            }
+
+            Message::CopyContainerId(id) => {
+                let short_id = if id.len() > 12 {
+                    id[..12].to_string()
+                } else {
+                    id.clone()
+                };
+                copy_to_clipboard(&short_id);
+            }
+
+            Message::CopyText(text) => {
+                copy_to_clipboard(&text);
+            }
+
+            Message::SetDetailsTab(tab) => {
+                self.details_tab = tab;
+            }
+
+            Message::JsonSearchChanged(query) => {
+                self.json_search = query;
             }
 
             Message::ShowDetails(id, name) => {
                 self.current_view = PopupView::ContainerDetails;
+                self.details_container_id = id.clone();
                 self.details_container_name = name;
                 self.details_data = None;
+                self.details_processes.clear();
                 self.details_loading = true;
+                self.details_tab = DetailsTab::Overview;
+                self.json_search.clear();
+                self.pull_status = None;
+                self.open_folder_hint = None;
+                self.memory_limit_draft = None;
+                self.cpu_limit_draft = None;
+                self.limits_error = None;
+                self.exec_output.clear();
+                self.exec_running = false;
+                self.duplicate_name = format!("{}-copy", self.details_container_name);
+                self.duplicate_port_offset.clear();
+                self.duplicate_status = None;
+                self.connect_network = None;
+                self.connect_alias.clear();
+                self.network_connect_status = None;
+                // Processes come from a subscription that refreshes while the view is open
+                return Task::batch([
+                    cosmic::task::future(async move {
+                        Message::DetailsReceived(docker::fetch_container_details(id).await)
+                    }),
+                    cosmic::task::future(async move {
+                        Message::NetworksLoaded(docker::fetch_networks().await)
+                    }),
+                ]);
+            }
+
+            Message::ProcessesReceived(result) => match result {
+                Ok((id, processes)) => {
+                    if id == self.details_container_id {
+                        self.details_processes = processes;
+                    }
+                }
+                Err(e) => {
+                    // Stopped containers have no processes to list
+                    tracing::debug!("Failed to fetch processes: {}", e);
+                    self.details_processes.clear();
+                }
+            },
+
+            Message::KillProcess(pid) => {
+                let id = self.details_container_id.clone();
+                return cosmic::task::future(async move {
+                    Message::ProcessKilled(docker::kill_process(id, pid).await)
+                });
+            }
+
+            Message::ProcessKilled(result) => match result {
+                Ok(id) => {
+                    return cosmic::task::future(async move {
+                        Message::ProcessesReceived(docker::fetch_processes(id).await)
+                    });
+                }
+                Err(e) => {
+                    tracing::error!("Failed to kill process: {}", e);
+                }
+            },
+
+            Message::DetailsReceived(result) => {
+                self.details_loading = false;
+                match result {
+                    Ok((_id, details)) => {
+                        self.details_data = Some(details);
+                        self.update_connectable_networks();
+                    }
+                    Err(e) => {
+                        tracing::error!("Failed to fetch container details: {}", e);
+                    }
+                }
+            }
+
+            Message::ShowCompare => {
+                self.current_view = PopupView::Compare;
+                // Snapshot the list so dropdown indexes stay stable while open
+                self.compare_ids = self.containers.iter().map(|c| c.id.clone()).collect();
+                self.compare_names = self.containers.iter().map(|c| c.name.clone()).collect();
+                self.compare_left = None;
+                self.compare_right = None;
+                self.compare_result = None;
+            }
+
+            Message::SelectCompareLeft(index) => {
+                self.compare_left = Some(index);
+                return self.load_comparison();
+            }
+
+            Message::SelectCompareRight(index) => {
+                self.compare_right = Some(index);
+                return self.load_comparison();
+            }
+
+            Message::CompareLoaded(result) => {
+                self.compare_loading = false;
+                match result {
+                    Ok(configs) => self.compare_result = Some(configs),
+                    Err(e) => tracing::error!("Failed to compare containers: {}", e),
+                }
+            }
+
+            Message::ShowImages => {
+                self.current_view = PopupView::Images;
+                self.images_loading = true;
+                self.images_status = None;
+                return cosmic::task::future(async move {
+                    Message::ImagesLoaded(docker::list_images().await)
+                });
+            }
+
+            Message::ImagesLoaded(result) => {
+                self.images_loading = false;
+                match result {
+                    Ok(images) => {
+                        self.run_image_options = images
+                            .iter()
+                            .flat_map(|i| i.tags.iter())
+                            .filter(|t| t.as_str() != "<none>:<none>")
+                            .cloned()
+                            .collect();
+                        self.image_sizes = images.iter().map(|i| (i.id.clone(), i.size)).collect();
+                        self.sized_image_ids.extend(self.image_sizes.keys().cloned());
+                        self.images = images;
+                    }
+                    Err(e) => {
+                        tracing::error!("Failed to list images: {}", e);
+                        self.images_status = Some(e);
+                    }
+                }
+            }
+
+            Message::RemoveImage(id) => {
+                self.pending_ops.insert(id.clone());
+                return cosmic::task::future(async move {
+                    let result = docker::remove_image(id.clone()).await;
+                    Message::ImageRemoved(id, result)
+                });
+            }
+
+            Message::ImageRemoved(id, result) => {
+                self.pending_ops.remove(&id);
+                match result {
+                    Ok(_) => {
+                        self.images.retain(|i| i.id != id);
+                        self.images_status = None;
+                    }
+                    Err(e) => {
+                        // e.g. the image is still used by a container
+                        tracing::error!("Failed to remove image: {}", e);
+                        self.images_status = Some(e);
+                    }
+                }
+            }
+
+            Message::ShowDiskUsage => {
+                self.current_view = PopupView::DiskUsage;
+                self.disk_usage_loading = true;
+                self.disk_usage_status = None;
+                return cosmic::task::future(async move {
+                    Message::DiskUsageLoaded(docker::fetch_disk_usage().await)
+                });
+            }
+
+            Message::DiskUsageLoaded(result) => {
+                self.disk_usage_loading = false;
+                match result {
+                    Ok(usage) => self.disk_usage = Some(usage),
+                    Err(e) => {
+                        tracing::error!("Failed to read disk usage: {}", e);
+                        self.disk_usage_status = Some(e);
+                    }
+                }
+            }
+
+            Message::SetPruneTargets(targets) => {
+                self.prune_targets = targets;
+            }
+
+            Message::FreeUpSpace => {
+                let targets = self.prune_targets;
+                self.pruning = true;
+                self.disk_usage_status = Some(fl!("loading"));
                 return cosmic::task::future(async move {
-                    Message::DetailsReceived(docker::fetch_container_details(id).await)
+                    Message::SpaceFreed(docker::prune_system(targets).await)
                 });
             }
 
-            Message::DetailsReceived(result) => {
-                self.details_loading = false;
-                match result {
-                    Ok((_id, details)) => {
-                        self.details_data = Some(details);
-                    }
-                    Err(e) => {
-                        tracing::error!("Failed to fetch container details: {}", e);
-                    }
-                }
-            }
-        }
-        Task::none()
+            Message::SpaceFreed(result) => {
+                self.pruning = false;
+                match result {
+                    Ok(reclaimed) => {
+                        self.disk_usage_status =
+                            Some(fl!("reclaimed", size = self.format_size(reclaimed as f64)));
+                    }
+                    Err(e) => {
+                        tracing::error!("Failed to free up space: {}", e);
+                        self.disk_usage_status = Some(e);
+                    }
+                }
+                self.disk_usage_loading = true;
+                return cosmic::task::future(async move {
+                    Message::DiskUsageLoaded(docker::fetch_disk_usage().await)
+                });
+            }
+
+            Message::ShowNetworks => {
+                self.current_view = PopupView::Networks;
+                self.networks_loading = true;
+                self.networks_status = None;
+                return cosmic::task::future(async move {
+                    Message::NetworksLoaded(docker::fetch_networks().await)
+                });
+            }
+
+            Message::NetworksLoaded(result) => {
+                self.networks_loading = false;
+                match result {
+                    Ok(networks) => {
+                        self.networks = networks;
+                        self.update_connectable_networks();
+                    }
+                    Err(e) => {
+                        tracing::error!("Failed to list networks: {}", e);
+                        self.networks_status = Some(e);
+                    }
+                }
+            }
+
+            Message::DisconnectNetwork(network) => {
+                let id = self.details_container_id.clone();
+                self.network_connect_status = Some(fl!("loading"));
+                return cosmic::task::future(async move {
+                    Message::NetworkConnectionChanged(docker::disconnect_network(id, network).await)
+                });
+            }
+
+            Message::ConnectNetworkPicked(index) => {
+                self.connect_network = Some(index);
+            }
+
+            Message::ConnectAliasInput(alias) => {
+                self.connect_alias = alias;
+            }
+
+            Message::ConnectNetwork => {
+                let Some(network) = self
+                    .connect_network
+                    .and_then(|i| self.connectable_networks.get(i))
+                    .cloned()
+                else {
+                    return Task::none();
+                };
+                let id = self.details_container_id.clone();
+                let alias = Some(self.connect_alias.trim().to_string()).filter(|a| !a.is_empty());
+                self.network_connect_status = Some(fl!("loading"));
+                return cosmic::task::future(async move {
+                    Message::NetworkConnectionChanged(
+                        docker::connect_network(id, network, alias).await,
+                    )
+                });
+            }
+
+            Message::NetworkConnectionChanged(result) => match result {
+                Ok(_) => {
+                    self.network_connect_status = None;
+                    self.connect_network = None;
+                    self.connect_alias.clear();
+                    let id = self.details_container_id.clone();
+                    return cosmic::task::future(async move {
+                        Message::DetailsReceived(docker::fetch_container_details(id).await)
+                    });
+                }
+                Err(e) => {
+                    tracing::error!("Failed to change network connection: {}", e);
+                    self.network_connect_status = Some(e);
+                }
+            },
+
+            Message::RemoveNetwork(id) => {
+                self.pending_ops.insert(id.clone());
+                return cosmic::task::future(async move {
+                    let result = docker::remove_network(id.clone()).await;
+                    Message::NetworkRemoved(id, result)
+                });
+            }
+
+            Message::NetworkRemoved(id, result) => {
+                self.pending_ops.remove(&id);
+                match result {
+                    Ok(_) => {
+                        self.networks.retain(|n| n.id != id);
+                        self.networks_status = None;
+                    }
+                    Err(e) => {
+                        tracing::error!("Failed to remove network: {}", e);
+                        self.networks_status = Some(e);
+                    }
+                }
+            }
+
+            Message::ShowVolumes => {
+                self.current_view = PopupView::Volumes;
+                self.volumes_loading = true;
+                self.volumes_status = None;
+                return cosmic::task::future(async move {
+                    Message::VolumesLoaded(docker::fetch_volumes().await)
+                });
+            }
+
+            Message::VolumesLoaded(result) => {
+                self.volumes_loading = false;
+                match result {
+                    Ok(volumes) => self.volumes = volumes,
+                    Err(e) => {
+                        tracing::error!("Failed to list volumes: {}", e);
+                        self.volumes_status = Some(e);
+                    }
+                }
+            }
+
+            Message::LoadVolumeSizes => {
+                self.volume_sizes_loading = true;
+                return cosmic::task::future(async move {
+                    Message::VolumeSizesLoaded(docker::fetch_volume_sizes().await)
+                });
+            }
+
+            Message::VolumeSizesLoaded(result) => {
+                self.volume_sizes_loading = false;
+                match result {
+                    Ok(sizes) => self.volume_sizes = sizes,
+                    Err(e) => {
+                        tracing::error!("Failed to read volume sizes: {}", e);
+                        self.volumes_status = Some(e);
+                    }
+                }
+            }
+
+            Message::ToggleCreateVolume => {
+                self.creating_volume = !self.creating_volume;
+            }
+
+            Message::NewVolumeNameInput(name) => {
+                self.new_volume_name = name;
+            }
+
+            Message::NewVolumeDriverInput(driver) => {
+                self.new_volume_driver = driver;
+            }
+
+            Message::NewVolumeLabelsInput(labels) => {
+                self.new_volume_labels = labels;
+            }
+
+            Message::CreateVolume => {
+                let name = self.new_volume_name.trim().to_string();
+                if name.is_empty() {
+                    return Task::none();
+                }
+                let driver = match self.new_volume_driver.trim() {
+                    "" => "local".to_string(),
+                    driver => driver.to_string(),
+                };
+                let labels: Vec<String> = self
+                    .new_volume_labels
+                    .split(',')
+                    .map(|l| l.trim().to_string())
+                    .filter(|l| !l.is_empty())
+                    .collect();
+                self.volumes_status = Some(fl!("loading"));
+                return cosmic::task::future(async move {
+                    Message::VolumeCreated(docker::create_volume(name, driver, labels).await)
+                });
+            }
+
+            Message::VolumeCreated(result) => match result {
+                Ok(name) => {
+                    self.volumes_status = Some(fl!("volume-created", name = name.as_str()));
+                    self.creating_volume = false;
+                    self.new_volume_name.clear();
+                    self.new_volume_labels.clear();
+                    return cosmic::task::future(async move {
+                        Message::VolumesLoaded(docker::fetch_volumes().await)
+                    });
+                }
+                Err(e) => {
+                    tracing::error!("Failed to create volume: {}", e);
+                    self.volumes_status = Some(e);
+                }
+            },
+
+            Message::FindUnusedVolumes => {
+                self.volumes_status = Some(fl!("loading"));
+                return cosmic::task::future(async move {
+                    Message::UnusedVolumesFound(docker::fetch_unused_volumes().await)
+                });
+            }
+
+            Message::UnusedVolumesFound(result) => match result {
+                Ok(names) if names.is_empty() => {
+                    self.volumes_status = Some(fl!("no-unused-volumes"));
+                }
+                Ok(names) => {
+                    self.volumes_status = None;
+                    return cosmic::Application::update(
+                        self,
+                        guarded(Message::PruneVolumes(names)),
+                    );
+                }
+                Err(e) => {
+                    tracing::error!("Failed to list unused volumes: {}", e);
+                    self.volumes_status = Some(e);
+                }
+            },
+
+            Message::PruneVolumes(names) => {
+                // Only the volumes the user confirmed, even if more became unused since
+                self.volumes_status = Some(fl!("loading"));
+                return cosmic::task::future(async move {
+                    let (removed, failed) = docker::remove_volumes(names).await;
+                    Message::VolumesPruned(removed, failed)
+                });
+            }
+
+            Message::VolumesPruned(removed, failed) => {
+                // Sizes are only known once they have been measured
+                let sizes: Vec<i64> = removed
+                    .iter()
+                    .filter_map(|name| self.volume_sizes.remove(name))
+                    .collect();
+                let count = removed.len().to_string();
+                let mut status = if sizes.is_empty() {
+                    fl!("volumes-removed", count = count.as_str())
+                } else {
+                    fl!(
+                        "volumes-pruned",
+                        count = count.as_str(),
+                        size = self.format_size(sizes.iter().sum::<i64>() as f64)
+                    )
+                };
+                for failure in &failed {
+                    tracing::error!("Failed to remove volume {}", failure);
+                    status.push('\n');
+                    status.push_str(failure);
+                }
+                self.volumes_status = Some(status);
+                return cosmic::task::future(async move {
+                    Message::VolumesLoaded(docker::fetch_volumes().await)
+                });
+            }
+
+            Message::RemoveVolume(name) => {
+                self.pending_ops.insert(name.clone());
+                return cosmic::task::future(async move {
+                    let result = docker::remove_volume(name.clone()).await;
+                    Message::VolumeRemoved(name, result)
+                });
+            }
+
+            Message::VolumeRemoved(name, result) => {
+                self.pending_ops.remove(&name);
+                match result {
+                    Ok(_) => {
+                        self.volumes.retain(|v| v.name != name);
+                        self.volume_sizes.remove(&name);
+                        self.volumes_status = None;
+                    }
+                    Err(e) => {
+                        // e.g. the volume is still mounted by a container
+                        tracing::error!("Failed to remove volume: {}", e);
+                        self.volumes_status = Some(e);
+                    }
+                }
+            }
+
+            Message::ShowRun => {
+                self.current_view = PopupView::Run;
+                self.run_status = None;
+                return cosmic::task::future(async move {
+                    Message::ImagesLoaded(docker::list_images().await)
+                });
+            }
+
+            Message::RunImageInput(image) => {
+                self.run_image = image;
+            }
+
+            Message::RunImagePicked(index) => {
+                if let Some(image) = self.run_image_options.get(index) {
+                    self.run_image = image.clone();
+                }
+            }
+
+            Message::RunNameInput(name) => {
+                self.run_name = name;
+            }
+
+            Message::RunPortsInput(ports) => {
+                self.run_ports = ports;
+            }
+
+            Message::RunEnvInput(env) => {
+                self.run_env_input = env;
+            }
+
+            Message::AddRunEnv => {
+                let env = self.run_env_input.trim();
+                if env.contains('=') {
+                    self.run_env.push(env.to_string());
+                    self.run_env_input.clear();
+                }
+            }
+
+            Message::RemoveRunEnv(index) => {
+                if index < self.run_env.len() {
+                    self.run_env.remove(index);
+                }
+            }
+
+            Message::RunContainer => {
+                let image = self.run_image.trim().to_string();
+                if image.is_empty() || self.run_in_progress {
+                    return Task::none();
+                }
+                let name = Some(self.run_name.trim().to_string()).filter(|n| !n.is_empty());
+                let ports: Vec<String> = self
+                    .run_ports
+                    .split(',')
+                    .map(|p| p.trim().to_string())
+                    .filter(|p| !p.is_empty())
+                    .collect();
+                let env = self.run_env.clone();
+                let registries = self.config.registries.clone();
+                self.run_in_progress = true;
+                self.run_status = Some(fl!("starting", image = image.as_str()));
+                return cosmic::task::future(async move {
+                    let credentials = registry::credentials_for_image(&registries, &image).await;
+                    Message::ContainerCreated(
+                        docker::create_container(image, name, ports, env, credentials).await,
+                    )
+                });
+            }
+
+            Message::ContainerCreated(result) => match result {
+                Ok(id) => {
+                    self.user_initiated_starts.insert(id.clone());
+                    return cosmic::task::future(async move {
+                        Message::ContainerRun(docker::start_container(id).await)
+                    });
+                }
+                Err(e) => return self.update(Message::ContainerRun(Err(e))),
+            },
+
+            Message::ContainerRun(result) => {
+                self.run_in_progress = false;
+                match result {
+                    Ok(_) => {
+                        self.run_status = None;
+                        self.run_name.clear();
+                        self.run_ports.clear();
+                        self.run_env.clear();
+                        self.current_view = PopupView::ContainerList;
+                        return cosmic::task::future(async move {
+                            Message::DockerEvent(DockerEvent::ContainersUpdated(
+                                docker::fetch_containers().await,
+                            ))
+                        });
+                    }
+                    Err(e) => {
+                        tracing::error!("Failed to run container: {}", e);
+                        self.run_status = Some(e);
+                    }
+                }
+            }
+
+            Message::ShowImageDetails(image) => {
+                self.image_details_origin = self.current_view.clone();
+                self.current_view = PopupView::ImageDetails;
+                self.image_details_ref = image.clone();
+                self.image_history = None;
+                self.image_history_error = None;
+                return cosmic::task::future(async move {
+                    Message::ImageHistoryLoaded(docker::fetch_image_history(image).await)
+                });
+            }
+
+            Message::ImageHistoryLoaded(result) => match result {
+                Ok(history) => self.image_history = Some(history),
+                Err(e) => {
+                    tracing::error!("Failed to fetch image history: {}", e);
+                    self.image_history_error = Some(e);
+                }
+            },
+
+            Message::BackFromImageDetails => {
+                self.current_view = self.image_details_origin.clone();
+            }
+
+            Message::StartTag(id) => {
+                self.tagging = Some((id, String::new()));
+            }
+
+            Message::TagInput(input) => {
+                if let Some((_, reference)) = &mut self.tagging {
+                    *reference = input;
+                }
+            }
+
+            Message::CancelTag => {
+                self.tagging = None;
+            }
+
+            Message::SubmitTag => {
+                if let Some((id, reference)) = self.tagging.take() {
+                    let reference = reference.trim().to_string();
+                    if reference.is_empty() {
+                        return Task::none();
+                    }
+                    return cosmic::task::future(async move {
+                        Message::ImageTagged(docker::tag_image(id, reference).await)
+                    });
+                }
+            }
+
+            Message::PushImage(id, reference) => {
+                let registries = self.config.registries.clone();
+                self.pending_ops.insert(id.clone());
+                self.images_status = Some(fl!("pushing", image = reference.as_str()));
+                return cosmic::task::future(async move {
+                    let credentials =
+                        registry::credentials_for_image(&registries, &reference).await;
+                    Message::ImagePushed(id, docker::push_image(reference, credentials).await)
+                });
+            }
+
+            Message::ImagePushed(id, result) => {
+                self.pending_ops.remove(&id);
+                self.images_status = Some(match result {
+                    Ok(image) => fl!("pushed", image = image.as_str()),
+                    Err(e) => {
+                        tracing::error!("Failed to push image: {}", e);
+                        e
+                    }
+                });
+            }
+
+            Message::ImageTagged(result) => {
+                match result {
+                    Ok(reference) => {
+                        self.images_status = Some(fl!("tagged", tag = reference.as_str()));
+                    }
+                    Err(e) => {
+                        tracing::error!("Failed to tag image: {}", e);
+                        self.images_status = Some(e);
+                    }
+                }
+                return cosmic::task::future(async move {
+                    Message::ImagesLoaded(docker::list_images().await)
+                });
+            }
+
+            Message::ExportImage(id) => {
+                if self.archive_export.is_some() {
+                    return Task::none();
+                }
+                let Some(image) = self.images.iter().find(|i| i.id == id) else {
+                    return Task::none();
+                };
+                // Save by tag when there is one so the archive restores it on load
+                let reference = image
+                    .tags
+                    .iter()
+                    .find(|t| t.as_str() != "<none>:<none>")
+                    .cloned()
+                    .unwrap_or(id);
+                let size = image.size.max(0) as u64;
+                let file_name = format!("{}.tar", reference.replace(['/', ':'], "_"));
+                let title = fl!("export-image");
+                return cosmic::task::future(async move {
+                    let dest = portal::choose_save_path(title, file_name).await;
+                    Message::ArchiveExportStarted(ArchiveKind::Image, reference, size, dest)
+                });
+            }
+
+            Message::BackupVolume(name) => {
+                if self.archive_export.is_some() {
+                    return Task::none();
+                }
+                let size = self
+                    .volume_sizes
+                    .get(&name)
+                    .copied()
+                    .unwrap_or(0)
+                    .max(0) as u64;
+                let file_name = format!("{}.tar", name);
+                let title = fl!("backup-volume");
+                return cosmic::task::future(async move {
+                    let dest = portal::choose_save_path(title, file_name).await;
+                    Message::ArchiveExportStarted(ArchiveKind::Volume, name, size, dest)
+                });
+            }
+
+            Message::ArchiveExportStarted(kind, name, total, result) => match result {
+                Ok(dest) => {
+                    match kind {
+                        ArchiveKind::Image => self.images_status = None,
+                        ArchiveKind::Volume => self.volumes_status = None,
+                    }
+                    self.archive_export = Some(ArchiveExport {
+                        kind,
+                        name,
+                        dest,
+                        written: 0,
+                        total,
+                    });
+                }
+                Err(e) => {
+                    tracing::warn!("Archive export cancelled: {}", e);
+                }
+            },
+
+            Message::PruneImages => {
+                self.images_status = Some(fl!("loading"));
+                return cosmic::task::future(async move {
+                    Message::ImagesPruned(docker::prune_images().await)
+                });
+            }
+
+            Message::ImagesPruned(result) => {
+                match result {
+                    Ok(reclaimed) => {
+                        self.images_status =
+                            Some(fl!("reclaimed", size = self.format_size(reclaimed as f64)));
+                    }
+                    Err(e) => {
+                        tracing::error!("Failed to prune images: {}", e);
+                        self.images_status = Some(e);
+                    }
+                }
+                return cosmic::task::future(async move {
+                    Message::ImagesLoaded(docker::list_images().await)
+                });
+            }
+
+            Message::ShowSettings => {
+                self.current_view = PopupView::Settings;
+            }
+
+            Message::ConfigChanged(config) => {
+                self.redactor = build_redactor(&config);
+                self.refresh_log_matches();
+                self.config = config;
+            }
+
+            Message::ExecInput(input) => {
+                self.exec_input = input;
+            }
+
+            Message::RunExec => {
+                let command = self.exec_input.trim();
+                if command.is_empty() || self.exec_running {
+                    return Task::none();
+                }
+                self.exec_command = command.to_string();
+                self.exec_output.clear();
+                self.exec_running = true;
+                self.exec_run += 1;
+            }
+
+            Message::OpenShell(id) => {
+                let terminal = self.config.terminal_command.clone();
+                return cosmic::task::future(async move {
+                    let command = docker::shell_command(id).await;
+                    Message::ShellLaunched(launch_in_terminal(&terminal, &command))
+                });
+            }
+
+            Message::ShellLaunched(result) => {
+                if let Err(e) = result {
+                    tracing::error!("Failed to open shell: {}", e);
+                }
+            }
+
+            Message::TerminalCommandInput(command) => {
+                self.config.terminal_command = command;
+                self.save_config();
+            }
+
+            Message::DuplicateNameInput(name) => {
+                self.duplicate_name = name;
+            }
+
+            Message::DuplicatePortOffsetInput(offset) => {
+                // Digits only, the offset is added to each published host port
+                if offset.chars().all(|c| c.is_ascii_digit()) {
+                    self.duplicate_port_offset = offset;
+                }
+            }
+
+            Message::DuplicateContainer => {
+                let name = self.duplicate_name.trim().to_string();
+                if name.is_empty() {
+                    return Task::none();
+                }
+                let offset = if self.duplicate_port_offset.is_empty() {
+                    0
+                } else if let Ok(offset) = self.duplicate_port_offset.parse::<u16>() {
+                    offset
+                } else {
+                    self.duplicate_status = Some(fl!("invalid-port-offset"));
+                    return Task::none();
+                };
+                let id = self.details_container_id.clone();
+                self.duplicate_status = Some(fl!("loading"));
+                return cosmic::task::future(async move {
+                    let result = docker::create_container_from(id, name.clone(), offset)
+                        .await
+                        .map(|_| name);
+                    Message::ContainerDuplicated(result)
+                });
+            }
+
+            Message::ContainerDuplicated(result) => match result {
+                Ok(name) => {
+                    self.duplicate_status = Some(fl!("duplicated", name = name.as_str()));
+                    return cosmic::task::future(async move {
+                        Message::DockerEvent(DockerEvent::ContainersUpdated(
+                            docker::fetch_containers().await,
+                        ))
+                    });
+                }
+                Err(e) => {
+                    tracing::error!("Failed to duplicate container: {}", e);
+                    self.duplicate_status = Some(e);
+                }
+            },
+
+            Message::StartRename(id, name) => {
+                self.renaming = Some((id, name));
+            }
+
+            Message::RenameInput(input) => {
+                if let Some((_, name)) = &mut self.renaming {
+                    *name = input;
+                }
+            }
+
+            Message::CancelRename => {
+                self.renaming = None;
+            }
+
+            Message::SubmitRename => {
+                if let Some((id, name)) = self.renaming.take() {
+                    let name = name.trim().to_string();
+                    if name.is_empty() {
+                        return Task::none();
+                    }
+                    self.pending_ops.insert(id.clone());
+                    return cosmic::task::future(async move {
+                        Message::ContainerRenamed(docker::rename_container(id, name).await)
+                    });
+                }
+            }
+
+            Message::ContainerRenamed(result) => match result {
+                Ok((id, name)) => {
+                    self.pending_ops.remove(&id);
+                    if let Some(container) = self.containers.iter_mut().find(|c| c.id == id) {
+                        // Settings keyed by name follow the container
+                        let old_name = std::mem::replace(&mut container.name, name.clone());
+                        for names in [
+                            &mut self.config.error_watch,
+                            &mut self.config.autostart_containers,
+                        ] {
+                            for entry in names.iter_mut().filter(|n| **n == old_name) {
+                                *entry = name.clone();
+                            }
+                        }
+                        for alert in self
+                            .config
+                            .log_alerts
+                            .iter_mut()
+                            .filter(|a| a.container == old_name)
+                        {
+                            alert.container = name.clone();
+                        }
+                        self.save_config();
+                    }
+                    if self.details_container_id == id {
+                        self.details_container_name = name;
+                    }
+                    return cosmic::task::future(async move {
+                        Message::DockerEvent(DockerEvent::ContainersUpdated(
+                            docker::fetch_containers().await,
+                        ))
+                    });
+                }
+                Err(e) => {
+                    tracing::error!("Failed to rename container: {}", e);
+                    self.pending_ops.clear();
+                }
+            },
+
+            Message::ToggleErrorWatch(name, enabled) => {
+                self.config.error_watch.retain(|n| *n != name);
+                if enabled {
+                    self.config.error_watch.push(name);
+                }
+                self.save_config();
+            }
+
+            Message::CopyEnvFile => {
+                if let Some(details) = &self.details_data {
+                    let env: String = details
+                        .env_vars
+                        .iter()
+                        .filter(|var| !self.env_skip_secrets || !self.redactor.is_sensitive(var))
+                        .map(|var| format!("{}\n", var))
+                        .collect();
+                    copy_to_clipboard(&env);
+                }
+            }
+
+            Message::ToggleEnvSkipSecrets(skip) => {
+                self.env_skip_secrets = skip;
+            }
+
+            Message::AlertPatternInput(pattern) => {
+                self.new_alert_pattern = pattern;
+                self.alert_pattern_error = None;
+            }
+
+            Message::AddLogAlert => {
+                let pattern = self.new_alert_pattern.trim().to_string();
+                if pattern.is_empty() {
+                    return Task::none();
+                }
+                if let Err(e) = regex::Regex::new(&pattern) {
+                    self.alert_pattern_error = Some(e.to_string());
+                    return Task::none();
+                }
+                self.config.log_alerts.push(LogAlert {
+                    container: self.details_container_name.clone(),
+                    pattern,
+                });
+                self.new_alert_pattern.clear();
+                self.save_config();
+            }
+
+            Message::RemoveLogAlert(index) => {
+                if index < self.config.log_alerts.len() {
+                    self.config.log_alerts.remove(index);
+                    self.save_config();
+                }
+            }
+
+            Message::ToggleAutostartContainer(name, enabled) => {
+                self.config.autostart_containers.retain(|n| *n != name);
+                if enabled {
+                    self.config.autostart_containers.push(name);
+                }
+                self.save_config();
+            }
+
+            Message::ToggleAutostartGroup(group_name) => {
+                if self.config.autostart_groups.contains(&group_name) {
+                    self.config.autostart_groups.retain(|g| *g != group_name);
+                } else {
+                    self.config.autostart_groups.push(group_name);
+                }
+                self.save_config();
+            }
+
+            Message::TogglePinGroup(group_name) => {
+                if self.config.pinned_groups.contains(&group_name) {
+                    self.config.pinned_groups.retain(|g| *g != group_name);
+                } else {
+                    self.config.pinned_groups.push(group_name);
+                }
+                self.save_config();
+            }
+
+            Message::MovePinnedGroup(from, to) => {
+                if from < self.config.pinned_groups.len() && to < self.config.pinned_groups.len() {
+                    let group_name = self.config.pinned_groups.remove(from);
+                    self.config.pinned_groups.insert(to, group_name);
+                    self.save_config();
+                }
+            }
+
+            Message::AutostartFinished(result) => {
+                self.autostarting = false;
+                let body = match result {
+                    Ok((started, failed)) => {
+                        if started.is_empty() && failed.is_empty() {
+                            return Task::none();
+                        }
+                        let mut lines = Vec::new();
+                        if !started.is_empty() {
+                            lines.push(fl!("autostart-started", names = started.join(", ")));
+                        }
+                        if !failed.is_empty() {
+                            lines.push(fl!("autostart-failed", names = failed.join(", ")));
+                        }
+                        lines.join("\n")
+                    }
+                    Err(e) => {
+                        tracing::error!("Autostart failed: {}", e);
+                        fl!("autostart-failed", names = e)
+                    }
+                };
+                self.notify(fl!("autostart"), body, "media-playback-start-symbolic");
+            }
+
+            Message::TogglePanelStats(enabled) => {
+                self.config.show_panel_stats = enabled;
+                self.save_config();
+            }
+
+            Message::ToggleDecimalUnits(enabled) => {
+                self.config.decimal_units = enabled;
+                self.save_config();
+            }
+
+            Message::ToggleMemoryAsPercent(enabled) => {
+                self.config.memory_as_percent = enabled;
+                self.save_config();
+            }
+
+            Message::ToggleNormalizeCpu(enabled) => {
+                self.config.normalize_cpu = enabled;
+                self.save_config();
+            }
+
+            Message::ToggleStripLogColors(enabled) => {
+                self.config.strip_log_colors = enabled;
+                self.save_config();
+            }
+
+            Message::ToggleImageUpdateChecks(enabled) => {
+                self.config.check_image_updates = enabled;
+                if !enabled {
+                    self.outdated_images.clear();
+                }
+                self.save_config();
+            }
+
+            Message::ToggleImageUpdateNotifications(enabled) => {
+                self.config.notify_image_updates = enabled;
+                self.save_config();
+            }
+
+            Message::ToggleStartNotifications(enabled) => {
+                self.config.notify_container_starts = enabled;
+                self.save_config();
+            }
+
+            Message::ToggleRecoveryNotifications(enabled) => {
+                self.config.notify_recovery = enabled;
+                self.save_config();
+            }
+
+            Message::ToggleQuietHours(enabled) => {
+                self.config.quiet_hours = enabled;
+                self.save_config();
+            }
+
+            Message::QuietHoursStartInput(time) => {
+                self.config.quiet_hours_start = time;
+                self.save_config();
+            }
+
+            Message::QuietHoursEndInput(time) => {
+                self.config.quiet_hours_end = time;
+                self.save_config();
+            }
+
+            Message::ClearHeldNotifications => {
+                self.held_notifications.clear();
+            }
+
+            Message::ToggleRedaction(enabled) => {
+                self.config.redact_secrets = enabled;
+                self.redactor = build_redactor(&self.config);
+                self.refresh_log_matches();
+                self.save_config();
+            }
+
+            Message::RedactionPatternInput(pattern) => {
+                self.new_redaction_pattern = pattern;
+            }
+
+            Message::AddRedactionPattern => {
+                let pattern = self.new_redaction_pattern.trim().to_string();
+                if pattern.is_empty() {
+                    return Task::none();
+                }
+                if !redact::is_valid_pattern(&pattern) {
+                    tracing::error!("Invalid redaction pattern: {}", pattern);
+                    return Task::none();
+                }
+                self.new_redaction_pattern.clear();
+                self.config.redaction_patterns.push(pattern);
+                self.redactor = build_redactor(&self.config);
+                self.refresh_log_matches();
+                self.save_config();
+            }
+
+            Message::RemoveRedactionPattern(index) => {
+                if index < self.config.redaction_patterns.len() {
+                    self.config.redaction_patterns.remove(index);
+                    self.redactor = build_redactor(&self.config);
+                    self.refresh_log_matches();
+                    self.save_config();
+                }
+            }
+
+            Message::SetConfirmation(category, index) => {
+                let Some(confirmation) = Confirmation::ALL.get(index).copied() else {
+                    return Task::none();
+                };
+                match category {
+                    ActionCategory::Normal => self.config.confirm_normal = confirmation,
+                    ActionCategory::Destructive => self.config.confirm_destructive = confirmation,
+                    ActionCategory::Dangerous => self.config.confirm_dangerous = confirmation,
+                }
+                self.save_config();
+            }
+
+            Message::ShowFiles(id, name) => {
+                self.current_view = PopupView::ContainerFiles;
+                self.files_container_id = id.clone();
+                self.files_container_name = name;
+                return self.browse_directory(id, String::from("/"));
+            }
+
+            Message::BrowseDirectory(path) => {
+                return self.browse_directory(self.files_container_id.clone(), path);
+            }
+
+            Message::DirectoryListed(result) => {
+                self.files_loading = false;
+                match result {
+                    Ok((path, entries)) => {
+                        self.files_path = path;
+                        self.files_entries = entries;
+                        self.files_error = None;
+                    }
+                    Err(e) => {
+                        tracing::error!("Failed to list directory: {}", e);
+                        self.files_error = Some(e);
+                    }
+                }
+            }
+
+            Message::TransferPathInput(path) => {
+                self.transfer_path = path;
+            }
+
+            Message::DownloadPath(path) => {
+                let id = self.files_container_id.clone();
+                let title = fl!("download");
+                self.transfer_status = Some(fl!("loading"));
+                return cosmic::task::future(async move {
+                    let result = docker::download_path(id, path, |file_name| {
+                        portal::choose_save_path(title, file_name)
+                    })
+                    .await
+                    .map(|dest| fl!("saved-to", path = dest.display().to_string()));
+                    Message::TransferCompleted(result)
+                });
+            }
+
+            Message::UploadFile => {
+                let id = self.files_container_id.clone();
+                let dir = self.files_path.clone();
+                let title = fl!("upload");
+                self.transfer_status = Some(fl!("loading"));
+                return cosmic::task::future(async move {
+                    let result = async {
+                        let source = portal::choose_open_path(title).await?;
+                        let file_name = source
+                            .file_name()
+                            .map(|n| n.to_string_lossy().to_string())
+                            .ok_or_else(|| "Invalid file name".to_string())?;
+                        let contents =
+                            tokio::fs::read(&source).await.map_err(|e| e.to_string())?;
+                        let target = join_path(&dir, &file_name);
+                        docker::upload_file(id, dir, file_name, contents).await?;
+                        Ok::<_, String>(fl!("uploaded-to", path = target))
+                    }
+                    .await;
+                    Message::TransferCompleted(result)
+                });
+            }
+
+            Message::TransferCompleted(result) => match result {
+                Ok(status) => {
+                    self.transfer_status = Some(status);
+                    // Show uploaded files right away
+                    if self.current_view == PopupView::ContainerFiles {
+                        return self.browse_directory(
+                            self.files_container_id.clone(),
+                            self.files_path.clone(),
+                        );
+                    }
+                }
+                Err(e) => {
+                    tracing::error!("File transfer failed: {}", e);
+                    self.transfer_status = Some(e);
+                }
+            },
+
+            Message::EnableElevatedAccess => {
+                self.elevating = true;
+                return cosmic::task::future(async move {
+                    Message::ElevatedAccessReady(elevate::start_helper().await)
+                });
+            }
+
+            Message::DockerUnitDetected(unit) => {
+                self.docker_unit = unit;
+            }
+
+            Message::StartDaemon => {
+                let Some(unit) = self.docker_unit.clone() else {
+                    return Task::none();
+                };
+                self.starting_daemon = true;
+                self.daemon_start_error = None;
+                return cosmic::task::future(async move {
+                    Message::DaemonStarted(service::start(unit).await)
+                });
+            }
+
+            Message::DaemonStarted(result) => {
+                self.starting_daemon = false;
+                match result {
+                    Ok(()) => {
+                        return cosmic::task::future(async move {
+                            Message::DockerEvent(DockerEvent::ContainersUpdated(
+                                docker::fetch_containers().await,
+                            ))
+                        });
+                    }
+                    Err(e) => {
+                        tracing::error!("Failed to start Docker: {}", e);
+                        self.daemon_start_error = Some(e);
+                    }
+                }
+            }
+
+            Message::ElevatedAccessReady(result) => {
+                self.elevating = false;
+                match result {
+                    Ok(socket) => docker::set_socket_override(Some(socket)),
+                    Err(e) => tracing::error!("Elevated access failed: {}", e),
+                }
+            }
+
+            Message::RegistryServerInput(server) => {
+                self.registry_server = server;
+            }
+
+            Message::RegistryUsernameInput(username) => {
+                self.registry_username = username;
+            }
+
+            Message::RegistryPasswordInput(password) => {
+                self.registry_password = password;
+            }
+
+            Message::AddRegistry => {
+                let entry = RegistryEntry {
+                    server: self.registry_server.trim().to_string(),
+                    username: self.registry_username.trim().to_string(),
+                };
+                if entry.server.is_empty() || entry.username.is_empty() {
+                    return Task::none();
+                }
+                let password = std::mem::take(&mut self.registry_password);
+                return cosmic::task::future(async move {
+                    let result = registry::store_password(&entry, &password)
+                        .await
+                        .map(|_| entry);
+                    Message::RegistryAdded(result)
+                });
+            }
+
+            Message::RegistryAdded(result) => match result {
+                Ok(entry) => {
+                    self.registry_server.clear();
+                    self.registry_username.clear();
+                    self.registry_status = None;
+                    self.config
+                        .registries
+                        .retain(|r| r.server != entry.server || r.username != entry.username);
+                    self.config.registries.push(entry);
+                    self.save_config();
+                }
+                Err(e) => {
+                    tracing::error!("Failed to store registry credentials: {}", e);
+                    self.registry_status = Some(e);
+                }
+            },
+
+            Message::RemoveRegistry(index) => {
+                if index < self.config.registries.len() {
+                    let entry = self.config.registries.remove(index);
+                    self.save_config();
+                    return Task::future(async move { registry::clear_password(&entry).await })
+                        .discard();
+                }
+            }
+
+            Message::PullImage(image) => {
+                let registries = self.config.registries.clone();
+                self.pull_status = Some(fl!("pulling", image = image.as_str()));
+                return cosmic::task::future(async move {
+                    let credentials = registry::credentials_for_image(&registries, &image).await;
+                    Message::ImagePulled(docker::pull_image(image, credentials).await)
+                });
+            }
+
+            Message::MemoryLimitChanged(mb) => {
+                self.memory_limit_draft = Some(mb);
+            }
+
+            Message::ApplyMemoryLimit => {
+                if let Some(mb) = self.memory_limit_draft {
+                    let id = self.details_container_id.clone();
+                    let bytes = (mb * 1024.0 * 1024.0) as i64;
+                    return cosmic::task::future(async move {
+                        Message::MemoryLimitApplied(docker::update_memory_limit(id, bytes).await)
+                    });
+                }
+            }
+
+            Message::MemoryLimitApplied(result) => {
+                self.memory_limit_draft = None;
+                match result {
+                    Ok(bytes) => {
+                        self.limits_error = None;
+                        if let Some(details) = &mut self.details_data {
+                            details.memory_limit = bytes;
+                        }
+                    }
+                    Err(e) => {
+                        tracing::error!("Failed to update memory limit: {}", e);
+                        self.limits_error = Some(e);
+                    }
+                }
+            }
+
+            Message::SetRestartPolicy(index) => {
+                if let Some(policy) = RestartPolicy::ALL.get(index).copied() {
+                    let id = self.details_container_id.clone();
+                    return cosmic::task::future(async move {
+                        Message::RestartPolicyApplied(
+                            docker::update_restart_policy(id, policy).await,
+                        )
+                    });
+                }
+            }
+
+            Message::RestartPolicyApplied(result) => match result {
+                Ok(policy) => {
+                    self.limits_error = None;
+                    if let Some(details) = &mut self.details_data {
+                        details.restart_policy = policy;
+                    }
+                }
+                Err(e) => {
+                    tracing::error!("Failed to update restart policy: {}", e);
+                    self.limits_error = Some(e);
+                }
+            },
+
+            Message::CpuLimitChanged(cores) => {
+                self.cpu_limit_draft = Some(cores);
+            }
+
+            Message::ApplyCpuLimit => {
+                let host_cpus = self.details_data.as_ref().map_or(1.0, |d| d.host_cpus);
+                if let Some(cores) = self.cpu_limit_draft {
+                    let id = self.details_container_id.clone();
+                    // The top of the slider means no limit
+                    let cores = if cores >= host_cpus { 0.0 } else { cores };
+                    return cosmic::task::future(async move {
+                        Message::CpuLimitApplied(docker::update_cpu_limit(id, cores).await)
+                    });
+                }
+            }
+
+            Message::CpuLimitApplied(result) => {
+                self.cpu_limit_draft = None;
+                match result {
+                    Ok(cores) => {
+                        self.limits_error = None;
+                        if let Some(details) = &mut self.details_data {
+                            details.cpu_limit = cores;
+                        }
+                    }
+                    Err(e) => {
+                        tracing::error!("Failed to update CPU limit: {}", e);
+                        self.limits_error = Some(e);
+                    }
+                }
+            }
+
+            Message::ImagePulled(result) => match result {
+                Ok(image) => {
+                    self.pull_status = Some(fl!("pulled", image = image.as_str()));
+                }
+                Err(e) => {
+                    tracing::error!("Failed to pull image: {}", e);
+                    self.pull_status = Some(e);
+                }
+            },
+
+            Message::ResetRedactionPatterns => {
+                self.config.redaction_patterns = Config::default().redaction_patterns;
+                self.redactor = build_redactor(&self.config);
+                self.refresh_log_matches();
+                self.save_config();
+            }
+        }
+        Task::none()
+    }
+
+    fn view(&self) -> Element<'_, Self::Message> {
+        let running_count = self
+            .containers
+            .iter()
+            .filter(|c| c.state == ContainerState::Running)
+            .count();
+
+        // Dimmed icon while the daemon is unreachable or reconnecting
+        let icon_name = if self.docker_available {
+            "cosmic-applet-docker-symbolic"
+        } else {
+            "cosmic-applet-docker-offline-symbolic"
+        };
+
+        if running_count > 0 {
+            let btn = self
+                .core
+                .applet
+                .icon_button(icon_name)
+                .on_press(Message::TogglePopup);
+            let mut row = widget::row()
+                .push(btn)
+                .push(text::body(format!("{}", running_count)))
+                .align_y(Alignment::Center)
+                .spacing(4);
+
+            if let Some((cpu, mem)) = self.total_usage().filter(|_| self.config.show_panel_stats) {
+                row = row.push(text::caption(format!(
+                    "{:.0}% · {}",
+                    cpu,
+                    self.format_memory(mem)
+                )));
+            }
+
+            row.into()
+        } else {
+            self.core
+                .applet
+                .icon_button(icon_name)
+                .on_press(Message::TogglePopup)
+                .into()
+        }
+    }
+
+    fn view_window(&self, id: Id) -> Element<'_, Self::Message> {
+        if self.logs_window_id == Some(id) {
+            return widget::container(self.view_logs(true))
+                .padding(8)
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .into();
+        }
+        if self.popup != Some(id) {
+            return text::body("").into();
+        }
+
+        let content: Element<Message> = match &self.current_view {
+            PopupView::ContainerList => self.view_container_list(),
+            PopupView::ContainerLogs => self.view_logs(false),
+            PopupView::ProjectLogs => self.view_project_logs(),
+            PopupView::ContainerDetails => self.view_details(),
+            PopupView::ContainerFiles => self.view_files(),
+            PopupView::Compare => self.view_compare(),
+            PopupView::Images => self.view_images(),
+            PopupView::Volumes => self.view_volumes(),
+            PopupView::Networks => self.view_networks(),
+            PopupView::DiskUsage => self.view_disk_usage(),
+            PopupView::ImageDetails => self.view_image_details(),
+            PopupView::Run => self.view_run(),
+            PopupView::Settings => self.view_settings(),
+        };
+
+        self.core
+            .applet
+            .popup_container(content)
+            .max_width(400.0)
+            .max_height(600.0)
+            .into()
+    }
+
+    fn on_close_requested(&self, id: window::Id) -> Option<Message> {
+        Some(Message::PopupClosed(id))
+    }
+
+    fn style(&self) -> Option<cosmic::iced_runtime::Appearance> {
+        Some(cosmic::applet::style())
+    }
+
+    fn subscription(&self) -> Subscription<Self::Message> {
+        let popup_open = self.popup.is_some();
+
+        let mut subs = vec![
+            docker::container_list_subscription(popup_open).map(Message::DockerEvent),
+            docker::docker_events_subscription().map(Message::DockerEvent),
+            self.core
+                .watch_config::<Config>(APP_ID)
+                .map(|update| Message::ConfigChanged(update.config)),
+        ];
+
+        let running_ids: Vec<String> = self
+            .containers
+            .iter()
+            .filter(|c| c.state == ContainerState::Running)
+            .map(|c| c.id.clone())
+            .collect();
+
+        if popup_open && self.current_view == PopupView::ContainerList {
+            subs.push(Subscription::run_with_id(
+                "uptime-tick",
+                cosmic::iced_futures::stream::channel(1, |mut output| async move {
+                    loop {
+                        tokio::time::sleep(std::time::Duration::from_secs(60)).await;
+                        let _ = futures::SinkExt::send(&mut output, Message::Tick).await;
+                    }
+                }),
+            ));
+            subs.push(
+                docker::container_stats_subscription(running_ids.clone(), false)
+                    .map(Message::DockerEvent),
+            );
+            subs.push(docker::health_subscription(running_ids).map(Message::DockerEvent));
+        } else {
+            // Low-frequency pass feeding the panel readout and keeping the
+            // history warm so the popup has numbers as soon as it opens
+            subs.push(
+                docker::container_stats_subscription(running_ids, true).map(Message::DockerEvent),
+            );
+        }
+
+        for container in &self.containers {
+            if container.state == ContainerState::Running
+                && self.config.error_watch.contains(&container.name)
+            {
+                subs.push(
+                    docker::error_count_subscription(container.id.clone())
+                        .map(Message::DockerEvent),
+                );
+            }
+        }
+
+        for container in &self.containers {
+            let patterns: Vec<String> = self
+                .config
+                .log_alerts
+                .iter()
+                .filter(|a| a.container == container.name)
+                .map(|a| a.pattern.clone())
+                .collect();
+            if container.state == ContainerState::Running && !patterns.is_empty() {
+                subs.push(
+                    docker::log_alert_subscription(container.id.clone(), patterns)
+                        .map(Message::DockerEvent),
+                );
+            }
+        }
+
+        if self.config.check_image_updates {
+            let mut images: Vec<String> = self
+                .containers
+                .iter()
+                .filter(|c| c.state == ContainerState::Running)
+                .map(|c| c.image.clone())
+                .collect();
+            images.sort();
+            images.dedup();
+            subs.push(
+                docker::image_update_subscription(images, self.config.registries.clone())
+                    .map(Message::DockerEvent),
+            );
+        }
+
+        if let Some(export) = &self.archive_export {
+            let (name, dest) = (export.name.clone(), export.dest.clone());
+            let sub = match export.kind {
+                ArchiveKind::Image => docker::image_export_subscription(name, dest),
+                ArchiveKind::Volume => docker::volume_backup_subscription(name, dest),
+            };
+            subs.push(sub.map(Message::DockerEvent));
+        }
+
+        let mut created_from: Vec<(String, String)> = self
+            .containers
+            .iter()
+            .filter(|c| !c.image_id.is_empty())
+            .map(|c| (c.id.clone(), c.image_id.clone()))
+            .collect();
+        created_from.sort();
+        subs.push(docker::superseded_subscription(created_from).map(Message::DockerEvent));
+        subs.push(docker::layer_size_subscription().map(Message::DockerEvent));
+
+        if let (Some((project, action)), true) = (&self.compose_task, self.compose_running) {
+            subs.push(
+                docker::compose_subscription(project.clone(), *action, self.compose_run)
+                    .map(Message::DockerEvent),
+            );
+        }
+
+        if popup_open && self.current_view == PopupView::ContainerDetails && self.exec_running {
+            subs.push(
+                docker::exec_subscription(
+                    self.details_container_id.clone(),
+                    self.exec_command.clone(),
+                    self.exec_run,
+                )
+                .map(Message::DockerEvent),
+            );
+        }
+
+        if popup_open && self.current_view == PopupView::ContainerDetails {
+            // Live usage shown next to the resource limits
+            subs.push(
+                docker::container_stats_subscription(
+                    vec![self.details_container_id.clone()],
+                    false,
+                )
+                .map(Message::DockerEvent),
+            );
+            subs.push(
+                docker::processes_subscription(self.details_container_id.clone())
+                    .map(Message::DockerEvent),
+            );
+        }
+
+        let logs_shown = (popup_open && self.current_view == PopupView::ContainerLogs)
+            || self.logs_window_id.is_some();
+        if logs_shown && !self.log_container_id.is_empty() && self.log_range.is_none() {
+            subs.push(
+                docker::log_streaming_subscription(
+                    self.log_container_id.clone(),
+                    self.log_window,
+                )
+                .map(Message::DockerEvent),
+            );
+        }
+
+        if popup_open
+            && self.current_view == PopupView::ProjectLogs
+            && !self.project_logs_name.is_empty()
+        {
+            let containers: Vec<(String, String)> = self
+                .containers
+                .iter()
+                .filter(|c| {
+                    c.labels.get("com.docker.compose.project") == Some(&self.project_logs_name)
+                })
+                .map(|c| {
+                    let prefix = self.service_label(c).unwrap_or_else(|| c.name.clone());
+                    (c.id.clone(), prefix)
+                })
+                .collect();
+            subs.push(
+                docker::project_logs_subscription(self.project_logs_name.clone(), containers)
+                    .map(Message::DockerEvent),
+            );
+        }
+
+        Subscription::batch(subs)
+    }
+}
+
+impl DockerApplet {
+    fn view_bulk_actions(&self) -> Element<'_, Message> {
+        let mut row = widget::row()
+            .push(
+                widget::button::text(fl!("start-all"))
+                    .on_press(guarded(Message::StartAll))
+                    .class(cosmic::theme::Button::Standard),
+            )
+            .push(
+                widget::button::text(fl!("stop-all"))
+                    .on_press(guarded(Message::StopAll))
+                    .class(cosmic::theme::Button::Standard),
+            )
+            .spacing(8);
+
+        if self.health.values().any(|h| *h == HealthStatus::Unhealthy) {
+            row = row.push(
+                widget::button::text(fl!("restart-unhealthy"))
+                    .on_press(guarded(Message::RestartUnhealthy))
+                    .class(cosmic::theme::Button::Destructive),
+            );
+        }
+        row.into()
+    }
+
+    fn view_selection_bar(&self) -> Element<'_, Message> {
+        let has_selection = !self.selected.is_empty();
+        let action = |icon: &'static str, tooltip: String, bulk: BulkAction| {
+            widget::button::icon(widget::icon::from_name(icon))
+                .extra_small()
+                .tooltip(tooltip)
+                .on_press_maybe(has_selection.then(|| guarded(Message::RunBulkAction(bulk))))
+        };
+
+        widget::row()
+            .push(
+                text::body(fl!("selected-count", count = self.selected.len().to_string()))
+                    .width(Length::Fill),
+            )
+            .push(action("media-playback-start-symbolic", fl!("start"), BulkAction::Start))
+            .push(action("media-playback-stop-symbolic", fl!("stop"), BulkAction::Stop))
+            .push(action("view-refresh-symbolic", fl!("restart"), BulkAction::Restart))
+            .push(action("user-trash-symbolic", fl!("delete"), BulkAction::Delete))
+            .align_y(Alignment::Center)
+            .spacing(4)
+            .into()
+    }
+
+    fn selection_checkbox<'a>(&self, container: &ContainerInfo) -> Option<Element<'a, Message>> {
+        if !self.selection_mode {
+            return None;
+        }
+        let id = container.id.clone();
+        Some(
+            widget::checkbox("", self.selected.contains(&container.id))
+                .on_toggle(move |selected| Message::ToggleSelected(id.clone(), selected))
+                .into(),
+        )
+    }
+
+    /// Points the log view at a container and starts its stream from scratch
+    fn load_logs(&mut self, id: String, name: String) {
+        self.error_counts.remove(&id);
+        self.log_container_name = name;
+        self.log_container_id = id;
+        self.clear_log_lines();
+        self.logs_loading = true;
+        self.log_filter.clear();
+        self.update_log_filter();
+        self.log_range = None;
+        self.log_range_editing = false;
+        self.log_scroll_offset = 0.0;
+    }
+
+    /// Running containers with their latest stats, sorted by name
+    fn stats_rows(&self) -> Vec<(&ContainerInfo, &ContainerStats)> {
+        let mut rows: Vec<_> = self
+            .containers
+            .iter()
+            .filter_map(|c| self.stats.get(&c.id).map(|s| (c, s)))
+            .collect();
+        rows.sort_by(|(a, _), (b, _)| a.name.cmp(&b.name));
+        rows
+    }
+
+    /// Current stats of every running container, one CSV row each
+    fn stats_csv(&self) -> String {
+        let mut csv = String::from(
+            "name,id,cpu_percent,memory_mb,memory_limit_mb,memory_percent,pids,\
+             net_rx_bps,net_tx_bps,block_read_bps,block_write_bps\n",
+        );
+        for (container, stats) in self.stats_rows() {
+            let rates = stats.io_rates.unwrap_or_default();
+            csv.push_str(&format!(
+                "{},{},{:.2},{:.1},{:.1},{:.2},{},{:.0},{:.0},{:.0},{:.0}\n",
+                container.name,
+                container.id,
+                self.cpu_share(stats),
+                stats.memory_usage_mb,
+                stats.memory_limit_mb,
+                stats.memory_percent,
+                stats.pids,
+                rates.net_rx,
+                rates.net_tx,
+                rates.block_read,
+                rates.block_write
+            ));
+        }
+        csv
+    }
+
+    /// Current stats plus the recent sample history of every running container
+    fn stats_json(&self) -> String {
+        let containers: Vec<serde_json::Value> = self
+            .stats_rows()
+            .into_iter()
+            .map(|(container, stats)| {
+                let rates = stats.io_rates.unwrap_or_default();
+                let history: Vec<serde_json::Value> = self
+                    .cpu_memory_history(&container.id)
+                    .into_iter()
+                    .map(|(cpu, memory)| {
+                        serde_json::json!({ "cpu_percent": cpu, "memory_percent": memory })
+                    })
+                    .collect();
+                serde_json::json!({
+                    "name": container.name,
+                    "id": container.id,
+                    "cpu_percent": self.cpu_share(stats),
+                    "memory_mb": stats.memory_usage_mb,
+                    "memory_limit_mb": stats.memory_limit_mb,
+                    "memory_percent": stats.memory_percent,
+                    "pids": stats.pids,
+                    "net_rx_bps": rates.net_rx,
+                    "net_tx_bps": rates.net_tx,
+                    "block_read_bps": rates.block_read,
+                    "block_write_bps": rates.block_write,
+                    "history": history,
+                })
+            })
+            .collect();
+        let export = serde_json::json!({
+            "exported_at": chrono::Utc::now().to_rfc3339(),
+            "cpu_normalized": self.config.normalize_cpu,
+            "containers": containers,
+        });
+        serde_json::to_string_pretty(&export).unwrap_or_default()
+    }
+
+    /// Summed CPU % and memory (MB) of all running containers, once stats have arrived
+    fn total_usage(&self) -> Option<(f64, f64)> {
+        if self.stats.is_empty() {
+            return None;
+        }
+        Some(self.stats.values().fold((0.0, 0.0), |(cpu, mem), s| {
+            (cpu + self.cpu_share(s), mem + s.memory_usage_mb)
+        }))
+    }
+
+    /// CPU usage as configured: per core (up to 100% × cores) or as a share of the host
+    fn cpu_share(&self, stats: &ContainerStats) -> f64 {
+        self.normalize_cpu(stats.cpu_percent, stats.online_cpus)
+    }
+
+    fn normalize_cpu(&self, percent: f64, online_cpus: u32) -> f64 {
+        if self.config.normalize_cpu {
+            percent / online_cpus.max(1) as f64
+        } else {
+            percent
+        }
+    }
+
+    /// Recent (CPU, memory) percentages of a container, oldest first, with the CPU
+    /// figures scaled like `cpu_share`
+    fn cpu_memory_history(&self, id: &str) -> Vec<(f64, f64)> {
+        let cpus = self.stats.get(id).map_or(1, |stats| stats.online_cpus);
+        self.stats_history
+            .get(id)
+            .into_iter()
+            .flatten()
+            .map(|(cpu, memory)| (self.normalize_cpu(*cpu, cpus), *memory))
+            .collect()
+    }
+
+    /// A byte count in the units chosen in the settings
+    fn format_size(&self, bytes: f64) -> String {
+        format_size(bytes, self.config.decimal_units)
+    }
+
+    fn format_rate(&self, bytes_per_sec: f64) -> String {
+        format!("{}/s", self.format_size(bytes_per_sec))
+    }
+
+    /// Formats a size given in MiB, as the stats API reports it
+    fn format_memory(&self, mb: f64) -> String {
+        self.format_size(mb * 1024.0 * 1024.0)
+    }
+
+    fn view_container_list(&self) -> Element<'_, Message> {
+        let mut content = widget::column().spacing(8).width(Length::Fill).padding([0, 12]);
+
+        // Header
+        let running_count = self
+            .containers
+            .iter()
+            .filter(|c| c.state == ContainerState::Running)
+            .count();
+
+        let mut title = widget::column().push(text::heading(format!(
+            "{} · {} running",
+            fl!("docker-containers"),
+            running_count
+        )));
+        if let Some((cpu, mem)) = self.total_usage() {
+            title = title.push(text::caption(fl!(
+                "total-usage",
+                cpu = format!("{:.1}", cpu),
+                memory = self.format_memory(mem)
+            )));
+        }
+
+        let header = widget::row()
+            .push(title.width(Length::Fill))
+            .push(
+                widget::button::icon(widget::icon::from_name("document-save-symbolic"))
+                    .extra_small()
+                    .tooltip(fl!("export-stats-tooltip"))
+                    .on_press_maybe((!self.stats.is_empty()).then_some(Message::ExportStats)),
+            )
+            .push(
+                widget::button::icon(widget::icon::from_name("object-select-symbolic"))
+                    .extra_small()
+                    .tooltip(fl!("select"))
+                    .selected(self.selection_mode)
+                    .on_press(Message::ToggleSelectionMode),
+            )
+            .push(
+                widget::button::icon(widget::icon::from_name("list-add-symbolic"))
+                    .extra_small()
+                    .tooltip(fl!("run-container"))
+                    .on_press(Message::ShowRun),
+            )
+            .push(
+                widget::button::icon(widget::icon::from_name("drive-harddisk-symbolic"))
+                    .extra_small()
+                    .tooltip(fl!("images"))
+                    .on_press(Message::ShowImages),
+            )
+            .push(
+                widget::button::icon(widget::icon::from_name("drive-multidisk-symbolic"))
+                    .extra_small()
+                    .tooltip(fl!("volumes"))
+                    .on_press(Message::ShowVolumes),
+            )
+            .push(
+                widget::button::icon(widget::icon::from_name("network-wired-symbolic"))
+                    .extra_small()
+                    .tooltip(fl!("networks"))
+                    .on_press(Message::ShowNetworks),
+            )
+            .push(
+                widget::button::icon(widget::icon::from_name("drive-harddisk-solidstate-symbolic"))
+                    .extra_small()
+                    .tooltip(fl!("disk-usage"))
+                    .on_press(Message::ShowDiskUsage),
+            )
+            .push(
+                widget::button::icon(widget::icon::from_name("view-dual-symbolic"))
+                    .extra_small()
+                    .tooltip(fl!("compare"))
+                    .on_press(Message::ShowCompare),
+            )
+            .push(
+                widget::button::icon(widget::icon::from_name("emblem-system-symbolic"))
+                    .extra_small()
+                    .tooltip(fl!("settings"))
+                    .on_press(Message::ShowSettings),
+            )
+            .align_y(Alignment::Center)
+            .spacing(4);
+
+        content = content.push(widget::container(header).padding(8));
+
+        if let Some(held) = self.view_held_notifications() {
+            content = content.push(held);
+        }
+
+        if !self.docker_available {
+            if self.permission_denied {
+                let elevate_button = widget::button::text(if self.elevating {
+                    fl!("loading")
+                } else {
+                    fl!("use-elevated-access")
+                })
+                .on_press_maybe((!self.elevating).then_some(Message::EnableElevatedAccess))
+                .class(cosmic::theme::Button::Suggested);
+
+                content = content.push(
+                    widget::column()
+                        .push(text::body(fl!("docker-permission-denied")))
+                        .push(elevate_button)
+                        .spacing(8)
+                        .padding(16)
+                        .align_x(Alignment::Center)
+                        .width(Length::Fill),
+                );
+            } else {
+                let mut column = widget::column()
+                    .push(text::body(fl!("docker-unavailable")))
+                    .spacing(8)
+                    .padding(16)
+                    .align_x(Alignment::Center)
+                    .width(Length::Fill);
+                if self.docker_unit.is_some() {
+                    column = column.push(
+                        widget::button::text(if self.starting_daemon {
+                            fl!("loading")
+                        } else {
+                            fl!("start-docker")
+                        })
+                        .on_press_maybe((!self.starting_daemon).then_some(Message::StartDaemon))
+                        .class(cosmic::theme::Button::Suggested),
+                    );
+                }
+                if let Some(error) = &self.daemon_start_error {
+                    column = column.push(text::caption(error));
+                }
+                content = content.push(column);
+            }
+            return scrollable(content).height(Length::Shrink).into();
+        }
+
+        // Search bar
+        let search = widget::text_input::search_input(fl!("search-placeholder"), &self.search_query)
+            .on_input(Message::SearchChanged)
+            .on_clear(Message::ClearSearch);
+        content = content.push(search);
+
+        // Bulk action buttons
+        if self.selection_mode {
+            content = content.push(self.view_selection_bar());
+        } else {
+            content = content.push(self.view_bulk_actions());
+        }
+
+        if let Some(banner) = self.view_pending_banner() {
+            content = content.push(banner);
+        }
+
+        if self.containers.is_empty() {
+            content = content.push(
+                widget::container(text::body(fl!("no-containers")))
+                    .padding(16)
+                    .width(Length::Fill)
+                    .center_x(Length::Fill),
+            );
+            return scrollable(content).height(Length::Shrink).into();
+        }
+
+        // Filter containers by search query
+        let query = self.search_query.to_lowercase();
+        let filtered: Vec<&ContainerInfo> = self
+            .containers
+            .iter()
+            .filter(|c| {
+                if query.is_empty() {
+                    return true;
+                }
+                c.name.to_lowercase().contains(&query)
+                    || c.image.to_lowercase().contains(&query)
+            })
+            .collect();
+
+        if filtered.is_empty() {
+            content = content.push(
+                widget::container(text::body(fl!("no-containers")))
+                    .padding(16)
+                    .width(Length::Fill)
+                    .center_x(Length::Fill),
+            );
+            return scrollable(content).height(Length::Shrink).into();
+        }
+
+        // Group by compose project
+        let mut compose_groups: BTreeMap<String, Vec<&ContainerInfo>> = BTreeMap::new();
+        let mut ungrouped: Vec<&ContainerInfo> = Vec::new();
+
+        for container in &filtered {
+            if let Some(project) = container.labels.get("com.docker.compose.project") {
+                compose_groups
+                    .entry(project.clone())
+                    .or_default()
+                    .push(container);
+            } else {
+                ungrouped.push(container);
+            }
+        }
+
+        let has_groups = !compose_groups.is_empty();
+
+        // Pinned projects first in their configured order, the rest alphabetically
+        let mut ordered_groups: Vec<_> = compose_groups.iter().collect();
+        ordered_groups.sort_by_key(|(name, _)| {
+            self.config
+                .pinned_groups
+                .iter()
+                .position(|pinned| pinned == *name)
+                .unwrap_or(usize::MAX)
+        });
+
+        // Render compose groups
+        for (group_name, group_containers) in ordered_groups {
+            let running_in_group = group_containers
+                .iter()
+                .filter(|c| c.state == ContainerState::Running)
+                .count();
+            let total_in_group = group_containers.len();
+            let paused_in_group = group_containers
+                .iter()
+                .any(|c| c.state == ContainerState::Paused);
+            let is_collapsed = self.collapsed_groups.contains(group_name);
+
+            // Summed over the containers that have reported stats so far
+            let group_stats = group_containers
+                .iter()
+                .filter_map(|c| self.stats.get(&c.id))
+                .fold(None, |total: Option<(f64, f64)>, s| {
+                    let (cpu, mem) = total.unwrap_or_default();
+                    Some((cpu + self.cpu_share(s), mem + s.memory_usage_mb))
+                });
+
+            let arrow_icon = if is_collapsed {
+                "go-next-symbolic"
+            } else {
+                "go-down-symbolic"
+            };
+
+            // Compose files are only known for projects started by compose itself
+            let project =
+                ComposeProject::from_containers(group_name, group_containers.iter().copied());
+            let has_project = project.is_some();
+            let has_orphans = group_containers.iter().any(|c| self.is_orphan(c));
+            let compose_file = project.as_ref().and_then(|p| p.compose_file());
+            let compose_button = |icon: &'static str, tooltip: String, action: ComposeAction| {
+                widget::button::icon(widget::icon::from_name(icon))
+                    .extra_small()
+                    .tooltip(tooltip)
+                    .on_press_maybe((has_project && !self.compose_running).then(|| {
+                        let message = Message::RunCompose(group_name.clone(), action);
+                        if action == ComposeAction::Down {
+                            guarded(message)
+                        } else {
+                            message
+                        }
+                    }))
+            };
+
+            let group_header = widget::row()
+                .push(
+                    widget::button::icon(widget::icon::from_name(arrow_icon))
+                        .extra_small()
+                        .on_press(Message::ToggleGroup(group_name.clone())),
+                )
+                .push(self.view_project_status(group_containers))
+                .push(
+                    widget::column()
+                        .push(text::body(fl!(
+                            "compose-group",
+                            name = group_name.as_str(),
+                            running = running_in_group.to_string(),
+                            total = total_in_group.to_string()
+                        )))
+                        .push_maybe(group_stats.map(|(cpu, mem)| {
+                            text::caption(format!(
+                                "CPU {:.1}%  ·  MEM {}",
+                                cpu,
+                                self.format_memory(mem)
+                            ))
+                        }))
+                        .width(Length::Fill),
+                )
+                .push(
+                    widget::button::icon(widget::icon::from_name(
+                        "media-playback-start-symbolic",
+                    ))
+                    .extra_small()
+                    .tooltip(fl!("start-all"))
+                    .on_press(guarded(Message::StartGroup(group_name.clone()))),
+                )
+                .push(
+                    widget::button::icon(widget::icon::from_name(
+                        "media-playback-stop-symbolic",
+                    ))
+                    .extra_small()
+                    .tooltip(fl!("stop-all"))
+                    .on_press(guarded(Message::StopGroup(group_name.clone()))),
+                )
+                .push(
+                    widget::button::icon(widget::icon::from_name(
+                        "media-playback-pause-symbolic",
+                    ))
+                    .extra_small()
+                    .tooltip(fl!("pause-all"))
+                    .on_press_maybe(
+                        (running_in_group > 0)
+                            .then(|| guarded(Message::PauseGroup(group_name.clone()))),
+                    ),
+                )
+                .push(
+                    widget::button::icon(widget::icon::from_name("media-seek-forward-symbolic"))
+                        .extra_small()
+                        .tooltip(fl!("unpause-all"))
+                        .on_press_maybe(paused_in_group.then(|| {
+                            guarded(Message::UnpauseGroup(group_name.clone()))
+                        })),
+                )
+                .push(compose_button("go-up-symbolic", fl!("compose-up"), ComposeAction::Up))
+                .push(compose_button(
+                    "software-update-available-symbolic",
+                    fl!("update-stack"),
+                    ComposeAction::Update,
+                ))
+                .push(compose_button(
+                    "go-bottom-symbolic",
+                    fl!("compose-down"),
+                    ComposeAction::Down,
+                ))
+                .push(
+                    widget::button::icon(widget::icon::from_name("document-edit-symbolic"))
+                        .extra_small()
+                        .tooltip(fl!("open-compose-file"))
+                        .on_press_maybe(compose_file.map(Message::OpenComposeFile)),
+                )
+                .push_maybe(has_orphans.then(|| {
+                    widget::button::icon(widget::icon::from_name("edit-clear-all-symbolic"))
+                        .extra_small()
+                        .tooltip(fl!("remove-orphans"))
+                        .on_press(guarded(Message::RemoveOrphans(group_name.clone())))
+                }))
+                .push(
+                    widget::button::icon(widget::icon::from_name("text-x-generic-symbolic"))
+                        .extra_small()
+                        .tooltip(fl!("project-logs"))
+                        .on_press(Message::ShowProjectLogs(group_name.clone())),
+                )
+                .push(
+                    widget::button::icon(widget::icon::from_name("document-save-symbolic"))
+                        .extra_small()
+                        .tooltip(fl!("export-logs"))
+                        .on_press(Message::ExportGroupLogs(group_name.clone())),
+                )
+                .push(
+                    widget::button::icon(widget::icon::from_name("view-pin-symbolic"))
+                        .extra_small()
+                        .selected(self.config.pinned_groups.contains(group_name))
+                        .tooltip(fl!("pin-project"))
+                        .on_press(Message::TogglePinGroup(group_name.clone())),
+                )
+                .push(
+                    widget::button::icon(widget::icon::from_name(
+                        if self.config.autostart_groups.contains(group_name) {
+                            "starred-symbolic"
+                        } else {
+                            "non-starred-symbolic"
+                        },
+                    ))
+                    .extra_small()
+                    .tooltip(fl!("start-with-session"))
+                    .on_press(Message::ToggleAutostartGroup(group_name.clone())),
+                )
+                .align_y(Alignment::Center)
+                .spacing(4)
+                .padding([4, 8]);
+
+            content = content.push(group_header);
+            if let Some(output) = self.view_compose_output(group_name) {
+                content = content.push(output);
+            }
+            content = content.push(widget::divider::horizontal::light());
+
+            if !is_collapsed {
+                // Running first, then stopped
+                let mut sorted = group_containers.clone();
+                sorted.sort_by_key(|c| c.state != ContainerState::Running);
+
+                // Scaled services collapse into one line, listed where their first replica sorts
+                let mut shown_services = HashSet::new();
+                for container in &sorted {
+                    let replicas: Vec<&ContainerInfo> = match container.compose_service() {
+                        Some(service) => sorted
+                            .iter()
+                            .filter(|c| c.compose_service() == Some(service))
+                            .copied()
+                            .collect(),
+                        None => Vec::new(),
+                    };
+
+                    let rows = if replicas.len() > 1 {
+                        let service = container.compose_service().unwrap_or_default();
+                        if !shown_services.insert(service) {
+                            continue;
+                        }
+                        let key = format!("{group_name}/{service}");
+                        let expanded = self.expanded_services.contains(&key);
+                        content = content.push(self.view_service_line(
+                            key,
+                            service,
+                            &replicas,
+                            expanded,
+                        ));
+                        content = content.push(widget::divider::horizontal::light());
+                        if !expanded {
+                            continue;
+                        }
+                        replicas
+                    } else {
+                        vec![*container]
+                    };
+
+                    for container in rows {
+                        if container.state == ContainerState::Running {
+                            content = content.push(self.view_running_container(container));
+                        } else {
+                            content = content.push(self.view_stopped_container(container));
+                        }
+                        content = content.push(widget::divider::horizontal::light());
+                    }
+                }
+            }
+        }
+
+        // Render ungrouped containers
+        if has_groups && !ungrouped.is_empty() {
+            let other_header = widget::row()
+                .push(text::caption(fl!("other-containers")))
+                .padding([4, 8]);
+            content = content.push(other_header);
+            content = content.push(widget::divider::horizontal::light());
+        }
+
+        // Running containers (ungrouped)
+        let running: Vec<&ContainerInfo> = ungrouped
+            .iter()
+            .filter(|c| c.state == ContainerState::Running)
+            .copied()
+            .collect();
+
+        for container in &running {
+            content = content.push(self.view_running_container(container));
+            content = content.push(widget::divider::horizontal::light());
+        }
+
+        // Stopped containers (ungrouped)
+        let stopped: Vec<&ContainerInfo> = ungrouped
+            .iter()
+            .filter(|c| c.state != ContainerState::Running)
+            .copied()
+            .collect();
+
+        if !stopped.is_empty() {
+            if !has_groups {
+                let stopped_header = widget::row()
+                    .push(text::caption(format!(
+                        "{} ({})",
+                        fl!("stopped"),
+                        stopped.len()
+                    )))
+                    .padding([4, 8]);
+                content = content.push(stopped_header);
+                content = content.push(widget::divider::horizontal::light());
+            }
+
+            for container in &stopped {
+                content = content.push(self.view_stopped_container(container));
+                content = content.push(widget::divider::horizontal::light());
+            }
+        }
+
+        scrollable(content).height(Length::Shrink).into()
+    }
+
+    fn view_running_container<'a>(&'a self, container: &'a ContainerInfo) -> Element<'a, Message> {
+        let is_pending = self.pending_ops.contains(&container.id);
+
+        let stats_text = if let Some(stats) = self.stats.get(&container.id) {
+            let memory = if self.config.memory_as_percent {
+                format!("{:.1}%", stats.memory_percent)
+            } else {
+                self.format_memory(stats.memory_usage_mb)
+            };
+            format!(
+                "CPU {:.1}%  ·  MEM {}  ·  PIDS {}",
+                self.cpu_share(stats),
+                memory,
+                stats.pids
+            )
+        } else {
+            "CPU --  ·  MEM --  ·  PIDS --".to_string()
+        };
+
+        // Health indicator
+        let health_icon = self.health_icon(container);
+
+        // Port mappings text
+        let ports_text = format_ports(&container.ports);
+
+        // First public port for browser button
+        let first_public_port = container
+            .ports
+            .iter()
+            .find_map(|p| p.public_port);
+
+        // Row 1: health + name + action buttons
+        let actions: Element<Message> = if is_pending {
+            text::caption(fl!("loading")).into()
+        } else if let Some(pending) = self.pending_for(&container.id) {
+            self.view_confirmation(pending)
+        } else {
+            let mut row = widget::row().spacing(4).align_y(Alignment::Center);
+
+            row = row.push(
+                widget::button::icon(widget::icon::from_name(
+                    "media-playback-stop-symbolic",
+                ))
+                .extra_small()
+                .tooltip(fl!("stop"))
+                .on_press(guarded(Message::StopContainer(container.id.clone()))),
+            );
+
+            row = row.push(
+                widget::button::icon(widget::icon::from_name("view-refresh-symbolic"))
+                    .extra_small()
+                    .tooltip(fl!("restart"))
+                    .on_press(guarded(Message::RestartContainer(container.id.clone()))),
+            );
+
+            if let Some(port) = first_public_port {
+                row = row.push(
+                    widget::button::icon(widget::icon::from_name("web-browser-symbolic"))
+                        .extra_small()
+                        .tooltip(fl!("open-browser"))
+                        .on_press(Message::OpenInBrowser(port)),
+                );
+            }
+
+            row = row.push(
+                widget::button::icon(widget::icon::from_name("edit-copy-symbolic"))
+                    .extra_small()
+                    .tooltip(fl!("copy-id"))
+                    .on_press(Message::CopyContainerId(container.id.clone())),
+            );
+
+            row = row.push(
+                widget::button::icon(widget::icon::from_name("system-run-symbolic"))
+                    .extra_small()
+                    .tooltip(fl!("open-shell"))
+                    .on_press(Message::OpenShell(container.id.clone())),
+            );
+
+            row = row.push(
+                widget::button::icon(widget::icon::from_name("folder-symbolic"))
+                    .extra_small()
+                    .tooltip(fl!("files"))
+                    .on_press(Message::ShowFiles(
+                        container.id.clone(),
+                        container.name.clone(),
+                    )),
+            );
+
+            row = row.push(
+                widget::button::icon(widget::icon::from_name("dialog-information-symbolic"))
+                    .extra_small()
+                    .tooltip(fl!("details"))
+                    .on_press(Message::ShowDetails(
+                        container.id.clone(),
+                        container.name.clone(),
+                    )),
+            );
+
+            row = row.push(
+                widget::button::icon(widget::icon::from_name(
+                    "utilities-terminal-symbolic",
+                ))
+                .extra_small()
+                .tooltip(fl!("logs"))
+                .on_press(Message::ShowLogs(
+                    container.id.clone(),
+                    container.name.clone(),
+                )),
+            );
+
+            row.into()
+        };
+
+        let mut name_row = widget::row()
+            .align_y(Alignment::Center)
+            .spacing(4);
+
+        if let Some(checkbox) = self.selection_checkbox(container) {
+            name_row = name_row.push(checkbox);
+        }
+
+        if let Some(icon) = health_icon {
+            name_row = name_row.push(icon);
+        }
+
+        name_row = name_row.push(self.view_container_name(container));
+
+        if let Some(badge) = self.error_badge(container) {
+            name_row = name_row.push(badge);
+        }
+
+        if let Some(badge) = self.crash_loop_badge(container) {
+            name_row = name_row.push(badge);
+        }
+
+        if let Some(badge) = self.security_badge(container) {
+            name_row = name_row.push(badge);
+        }
+
+        name_row = name_row.push(actions);
+
+        let mut col = widget::column()
+            .push(name_row)
+            .push(self.view_image_line(container))
+            .spacing(2)
+            .padding(8)
+            .width(Length::Fill);
+
+        if !ports_text.is_empty() {
+            col = col.push(text::caption(ports_text));
+        }
+
+        let mut stats_row = widget::row()
+            .push(text::caption(stats_text))
+            .align_y(Alignment::Center)
+            .spacing(8);
+        let history = self.cpu_memory_history(&container.id);
+        if history.len() > 1 {
+            stats_row = stats_row
+                .push(sparkline(history.iter().map(|(cpu, _)| *cpu), fl!("cpu-history")))
+                .push(sparkline(history.iter().map(|(_, mem)| *mem), fl!("memory-history")));
+        }
+        col = col.push(stats_row);
+
+        if let Some(stats) = self.stats.get(&container.id) {
+            let percent = stats.memory_percent.clamp(0.0, 100.0) as f32;
+            col = col.push(widget::tooltip(
+                widget::progress_bar(0.0..=100.0, percent)
+                    .height(4)
+                    .class(memory_bar_class(percent)),
+                text::caption(fl!(
+                    "memory-of-limit",
+                    used = self.format_memory(stats.memory_usage_mb),
+                    limit = self.format_memory(stats.memory_limit_mb),
+                    percent = format!("{:.0}", percent)
+                )),
+                widget::tooltip::Position::Bottom,
+            ));
+        }
+
+        if let Some(rates) = self.stats.get(&container.id).and_then(|s| s.io_rates) {
+            col = col.push(text::caption(format!(
+                "NET ↓{} ↑{}  ·  DISK R {} W {}",
+                self.format_rate(rates.net_rx),
+                self.format_rate(rates.net_tx),
+                self.format_rate(rates.block_read),
+                self.format_rate(rates.block_write)
+            )));
+        }
+
+        // Uptime, falling back to Docker's own wording until the start time is known
+        let uptime = match self.started_at.get(&container.id) {
+            Some(Some(started)) => fl!("up-for", duration = format_uptime(*started)),
+            _ => container.status.clone(),
+        };
+        col = col.push(text::caption(uptime));
+
+        col.into()
+    }
+
+    fn view_stopped_container<'a>(
+        &'a self,
+        container: &'a ContainerInfo,
+    ) -> Element<'a, Message> {
+        let is_pending = self.pending_ops.contains(&container.id);
+
+        let health_icon = self.health_icon(container);
+        let ports_text = format_ports(&container.ports);
+
+        // Row 1: name + action buttons
+        let actions: Element<Message> = if is_pending {
+            text::caption(fl!("loading")).into()
+        } else if let Some(pending) = self.pending_for(&container.id) {
+            self.view_confirmation(pending)
+        } else {
+            widget::row()
+                .push(
+                    widget::button::icon(widget::icon::from_name(
+                        "media-playback-start-symbolic",
+                    ))
+                    .extra_small()
+                    .tooltip(fl!("start"))
+                    .on_press(guarded(Message::StartContainer(container.id.clone()))),
+                )
+                .push(
+                    widget::button::icon(widget::icon::from_name("user-trash-symbolic"))
+                        .extra_small()
+                        .tooltip(fl!("delete"))
+                        .on_press(guarded(Message::DeleteContainer(
+                            container.id.clone(),
+                            RemoveFlags {
+                                // Restarting or paused containers need force too
+                                force: container.state != ContainerState::Stopped,
+                                volumes: false,
+                            },
+                        ))),
+                )
+                .push(
+                    widget::button::icon(widget::icon::from_name("edit-copy-symbolic"))
+                        .extra_small()
+                        .tooltip(fl!("copy-id"))
+                        .on_press(Message::CopyContainerId(container.id.clone())),
+                )
+                .push(
+                    widget::button::icon(widget::icon::from_name(
+                        "dialog-information-symbolic",
+                    ))
+                    .extra_small()
+                    .tooltip(fl!("details"))
+                    .on_press(Message::ShowDetails(
+                        container.id.clone(),
+                        container.name.clone(),
+                    )),
+                )
+                .push(
+                    widget::button::icon(widget::icon::from_name(
+                        "utilities-terminal-symbolic",
+                    ))
+                    .extra_small()
+                    .tooltip(fl!("logs"))
+                    .on_press(Message::ShowLogs(
+                        container.id.clone(),
+                        container.name.clone(),
+                    )),
+                )
+                .spacing(4)
+                .align_y(Alignment::Center)
+                .into()
+        };
+
+        let mut name_row = widget::row()
+            .align_y(Alignment::Center)
+            .spacing(4);
+
+        if let Some(checkbox) = self.selection_checkbox(container) {
+            name_row = name_row.push(checkbox);
+        }
+
+        if let Some(icon) = health_icon {
+            name_row = name_row.push(icon);
+        }
+
+        name_row = name_row.push(self.view_container_name(container));
+
+        if let Some(badge) = self.error_badge(container) {
+            name_row = name_row.push(badge);
+        }
+
+        if let Some(badge) = self.crash_loop_badge(container) {
+            name_row = name_row.push(badge);
+        }
+
+        if let Some(badge) = self.security_badge(container) {
+            name_row = name_row.push(badge);
+        }
+
+        name_row = name_row.push(actions);
+
+        let mut col = widget::column()
+            .push(name_row)
+            .push(self.view_image_line(container))
+            .spacing(2)
+            .padding(8)
+            .width(Length::Fill);
+
+        if !ports_text.is_empty() {
+            col = col.push(text::caption(ports_text));
+        }
+
+        // Status
+        col = col.push(text::caption(&container.status));
+
+        col.into()
+    }
+
+    /// `detached` renders it for the popped out window rather than the popup.
+    fn view_logs(&self, detached: bool) -> Element<'_, Message> {
+        let header = widget::row()
+            .push_maybe((!detached).then(|| {
+                widget::button::icon(widget::icon::from_name("go-previous-symbolic"))
+                    .on_press(Message::BackToList)
+            }))
+            .push(text::title4(&self.log_container_name).width(Length::Fill))
+            .push_maybe((!detached).then(|| {
+                widget::button::icon(widget::icon::from_name("window-new-symbolic"))
+                    .extra_small()
+                    .tooltip(fl!("pop-out"))
+                    .on_press(Message::PopOutLogs)
+            }))
+            .push(
+                widget::button::icon(widget::icon::from_name("x-office-calendar-symbolic"))
+                    .extra_small()
+                    .selected(self.log_range_editing || self.log_range.is_some())
+                    .tooltip(fl!("log-range"))
+                    .on_press(Message::ToggleLogRangeEditor),
+            )
+            .push(
+                widget::button::icon(widget::icon::from_name("document-save-symbolic"))
+                    .extra_small()
+                    .tooltip(fl!("save-logs"))
+                    .on_press_maybe((!self.log_lines.is_empty()).then_some(Message::SaveLogs)),
+            )
+            .push(widget::tooltip(
+                widget::dropdown(
+                    self.log_tail_options.as_slice(),
+                    LOG_TAILS.iter().position(|t| *t == self.log_window.tail),
+                    Message::SetLogTail,
+                ),
+                text::caption(fl!("log-tail")),
+                widget::tooltip::Position::Bottom,
+            ))
+            .push(widget::tooltip(
+                widget::dropdown(
+                    self.log_since_options.as_slice(),
+                    LOG_SINCE.iter().position(|s| *s == self.log_window.since),
+                    Message::SetLogSince,
+                ),
+                text::caption(fl!("log-since")),
+                widget::tooltip::Position::Bottom,
+            ))
+            .align_y(Alignment::Center)
+            .spacing(8)
+            .padding(8);
+
+        let shown = &self.log_matches;
+        let log_body: Element<Message> = if self.logs_loading && self.log_lines.is_empty() {
+            widget::container(text::body(fl!("loading")))
+                .padding(16)
+                .center_x(Length::Fill)
+                .into()
+        } else {
+            // Only the lines around the viewport are built; the rest is stand-in space.
+            // Wrapped lines have no fixed height to stand in for, so they are all built.
+            let (first, last) = if self.log_wrap {
+                (0, shown.len())
+            } else {
+                let first = ((self.log_scroll_offset / LOG_LINE_HEIGHT) as usize)
+                    .saturating_sub(LOG_OVERSCAN)
+                    .min(shown.len());
+                let rendered = if detached {
+                    LOG_RENDERED_LINES_DETACHED
+                } else {
+                    LOG_RENDERED_LINES
+                };
+                (first, (first + rendered).min(shown.len()))
+            };
+            let mut lines = widget::column()
+                .push(widget::Space::with_height(first as f32 * LOG_LINE_HEIGHT));
+            if shown.is_empty() {
+                lines = lines.push(text::monotext("(no output)"));
+            }
+            for &index in &shown[first..last] {
+                let line = self.redactor.redact(&self.log_lines[index].1);
+                let line = if self.log_window.timestamps {
+                    format_log_timestamp(&line, self.log_local_time)
+                } else {
+                    line.into_owned()
+                };
+                let line = self.view_log_text(&line, self.log_wrap);
+                lines = if self.log_wrap {
+                    lines.push(line)
+                } else {
+                    lines.push(widget::container(line).height(LOG_LINE_HEIGHT))
+                };
+            }
+            lines = lines.push(widget::Space::with_height(
+                (shown.len() - last) as f32 * LOG_LINE_HEIGHT,
+            ));
+
+            let pane = scrollable(lines)
+                .on_scroll(|viewport| Message::LogScrolled(viewport.absolute_offset().y))
+                .height(if detached {
+                    Length::Fill
+                } else {
+                    Length::Fixed(400.0)
+                });
+            if self.log_wrap {
+                pane.into()
+            } else {
+                pane.direction(Direction::Both {
+                    vertical: Scrollbar::default(),
+                    horizontal: Scrollbar::default(),
+                })
+                .into()
+            }
+        };
+
+        let mut filter_bar = widget::row()
+            .push(
+                widget::text_input::search_input(fl!("filter-logs"), &self.log_filter)
+                    .on_input(Message::LogFilterChanged)
+                    .on_clear(Message::LogFilterChanged(String::new()))
+                    .width(Length::Fill),
+            )
+            .push(
+                widget::checkbox(fl!("regex"), self.log_filter_regex)
+                    .on_toggle(Message::ToggleLogFilterRegex),
+            )
+            .align_y(Alignment::Center)
+            .spacing(8)
+            .padding([0, 8]);
+        if self.log_filter_matcher.is_some() {
+            filter_bar =
+                filter_bar.push(text::caption(fl!("log-matches", count = shown.len().to_string())));
+        }
+
+        let options_bar = widget::row()
+            .push(
+                widget::checkbox("stdout", self.log_show_stdout)
+                    .on_toggle(|enabled| Message::ToggleLogStream(LogStream::Stdout, enabled)),
+            )
+            .push(
+                widget::checkbox("stderr", self.log_show_stderr)
+                    .on_toggle(|enabled| Message::ToggleLogStream(LogStream::Stderr, enabled)),
+            )
+            .push(
+                widget::checkbox(fl!("timestamps"), self.log_window.timestamps)
+                    .on_toggle(Message::ToggleLogTimestamps),
+            )
+            .push(
+                widget::checkbox(fl!("local-time"), self.log_local_time)
+                    .on_toggle_maybe(
+                        self.log_window
+                            .timestamps
+                            .then_some(Message::ToggleLogLocalTime),
+                    ),
+            )
+            .push(
+                widget::checkbox(fl!("wrap-lines"), self.log_wrap)
+                    .on_toggle(Message::ToggleLogWrap),
+            )
+            .spacing(12)
+            .padding([0, 8]);
+
+        let range_bar = self.log_range_editing.then(|| {
+            widget::row()
+                .push(
+                    widget::text_input(LOG_RANGE_PLACEHOLDER, &self.log_range_from)
+                        .on_input(Message::LogRangeFromInput)
+                        .on_submit(|_| Message::ApplyLogRange)
+                        .width(Length::Fill),
+                )
+                .push(text::caption("–"))
+                .push(
+                    widget::text_input(fl!("now"), &self.log_range_to)
+                        .on_input(Message::LogRangeToInput)
+                        .on_submit(|_| Message::ApplyLogRange)
+                        .width(Length::Fill),
+                )
+                .push(
+                    widget::button::icon(widget::icon::from_name("view-refresh-symbolic"))
+                        .extra_small()
+                        .tooltip(fl!("load-range"))
+                        .on_press(Message::ApplyLogRange),
+                )
+                .push(
+                    widget::button::icon(widget::icon::from_name("media-playback-start-symbolic"))
+                        .extra_small()
+                        .tooltip(fl!("follow-live"))
+                        .on_press_maybe(self.log_range.is_some().then_some(Message::ClearLogRange)),
+                )
+                .align_y(Alignment::Center)
+                .spacing(4)
+                .padding([0, 8])
+        });
+
+        widget::column()
+            .push(header)
+            .push_maybe(range_bar)
+            .push_maybe(
+                self.log_range_error
+                    .as_ref()
+                    .or(self.log_stream_error.as_ref())
+                    .map(|e| widget::container(text::caption(e)).padding([0, 8])),
+            )
+            .push(filter_bar)
+            .push(options_bar)
+            .push_maybe(
+                self.log_filter_error
+                    .as_ref()
+                    .map(|e| widget::container(text::caption(e)).padding([0, 8])),
+            )
+            .push(widget::divider::horizontal::light())
+            .push(log_body)
+            .spacing(4)
+            .width(Length::Fill)
+            .into()
+    }
+
+    fn view_project_logs(&self) -> Element<'_, Message> {
+        let header = widget::row()
+            .push(
+                widget::button::icon(widget::icon::from_name("go-previous-symbolic"))
+                    .on_press(Message::BackToList),
+            )
+            .push(text::title4(fl!(
+                "project-logs-title",
+                name = self.project_logs_name.as_str()
+            )))
+            .align_y(Alignment::Center)
+            .spacing(8)
+            .padding(8);
+
+        let log_body: Element<Message> = if self.project_log_lines.is_empty() {
+            widget::container(text::body(fl!("loading")))
+                .padding(16)
+                .center_x(Length::Fill)
+                .into()
+        } else {
+            let width = self
+                .project_log_lines
+                .iter()
+                .map(|(prefix, _)| prefix.chars().count())
+                .max()
+                .unwrap_or(0);
+            let mut lines = widget::column().width(Length::Fill);
+            for (prefix, line) in &self.project_log_lines {
+                let (r, g, b) = service_color(prefix);
+                lines = lines.push(
+                    widget::row()
+                        .push(
+                            text::monotext(format!("{prefix:width$} | "))
+                                .class(cosmic::theme::Text::Color(Color::from_rgb(r, g, b))),
+                        )
+                        .push(self.view_log_text(&self.redactor.redact(line), true)),
+                );
+            }
+            scrollable(lines)
+                .anchor_bottom()
+                .height(400)
+                .into()
+        };
+
+        widget::column()
+            .push(header)
+            .push(widget::divider::horizontal::light())
+            .push(log_body)
+            .spacing(4)
+            .width(Length::Fill)
+            .into()
+    }
+
+    fn view_duplicate(&self) -> Element<'_, Message> {
+        let mut col = widget::column()
+            .push(text::body(fl!("duplicate")))
+            .push(
+                widget::row()
+                    .push(
+                        widget::text_input(fl!("new-name"), &self.duplicate_name)
+                            .on_input(Message::DuplicateNameInput)
+                            .width(Length::Fill),
+                    )
+                    .push(
+                        widget::text_input(fl!("port-offset"), &self.duplicate_port_offset)
+                            .on_input(Message::DuplicatePortOffsetInput)
+                            .width(Length::Fixed(90.0)),
+                    )
+                    .push(
+                        widget::button::icon(widget::icon::from_name("edit-copy-symbolic"))
+                            .extra_small()
+                            .tooltip(fl!("duplicate"))
+                            .on_press(Message::DuplicateContainer),
+                    )
+                    .align_y(Alignment::Center)
+                    .spacing(4),
+            )
+            .spacing(4);
+        if let Some(status) = &self.duplicate_status {
+            col = col.push(text::caption(status));
+        }
+        col.into()
+    }
+
+    fn view_exec(&self) -> Element<'_, Message> {
+        let input = widget::row()
+            .push(
+                widget::text_input(fl!("run-command"), &self.exec_input)
+                    .on_input(Message::ExecInput)
+                    .on_submit(|_| Message::RunExec)
+                    .width(Length::Fill),
+            )
+            .push(
+                widget::button::icon(widget::icon::from_name("media-playback-start-symbolic"))
+                    .extra_small()
+                    .tooltip(fl!("run"))
+                    .on_press_maybe((!self.exec_running).then_some(Message::RunExec)),
+            )
+            .align_y(Alignment::Center)
+            .spacing(4);
+
+        let mut col = widget::column().push(input).spacing(4);
+        if self.exec_running || !self.exec_output.is_empty() {
+            let output = if self.exec_output.is_empty() {
+                fl!("loading")
+            } else {
+                self.redactor.redact(&self.exec_output).into_owned()
+            };
+            col = col.push(
+                widget::container(
+                    scrollable(text::monotext(output).size(11).width(Length::Fill)).height(120),
+                )
+                .class(cosmic::theme::Container::Card)
+                .padding(4),
+            );
+        }
+        col.into()
+    }
+
+    fn view_container_state<'a>(&self, details: &'a ContainerDetails) -> Element<'a, Message> {
+        let time = |time: Option<chrono::DateTime<chrono::Utc>>| {
+            time.map(|t| {
+                t.with_timezone(&chrono::Local)
+                    .format("%Y-%m-%d %H:%M:%S")
+                    .to_string()
+            })
+            .unwrap_or_else(|| "—".to_string())
+        };
+        let command = details
+            .entrypoint
+            .iter()
+            .chain(&details.cmd)
+            .cloned()
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        let mut col = widget::column().spacing(4);
+        if let Some(code) = details.exit_code {
+            col = col.push(widget::settings::item(
+                fl!("last-exit-code"),
+                text::monotext(code.to_string()),
+            ));
+        }
+        col.push(widget::settings::item(
+            fl!("restart-count"),
+            text::body(details.restart_count.to_string()),
+        ))
+        .push(widget::settings::item(fl!("runtime"), text::caption(&details.runtime)))
+        .push(widget::settings::item(fl!("hostname"), text::caption(&details.hostname)))
+        .push(widget::settings::item(
+            fl!("platform"),
+            text::caption(if details.emulated {
+                fl!("platform-emulated", platform = details.platform.as_str())
+            } else {
+                details.platform.clone()
+            }),
+        ))
+        .push(widget::settings::item(fl!("created"), text::caption(time(details.created))))
+        .push(widget::settings::item(fl!("started"), text::caption(time(details.started_at))))
+        .push(widget::settings::item(fl!("finished"), text::caption(time(details.finished_at))))
+        .push(text::body(fl!("command")))
+        .push(text::monotext(if command.is_empty() { fl!("no-data") } else { command }).size(11))
+        .into()
+    }
+
+    fn view_healthcheck<'a>(&self, health: &'a HealthCheck) -> Element<'a, Message> {
+        let mut col = widget::column()
+            .push(text::body(fl!("health-check")))
+            .push(text::monotext(&health.command).size(11))
+            .push(text::caption(fl!(
+                "health-check-timing",
+                interval = format_duration(health.interval),
+                timeout = format_duration(health.timeout),
+                retries = health.retries.to_string(),
+                start = format_duration(health.start_period)
+            )))
+            .spacing(4);
+
+        if health.probes.is_empty() {
+            col = col.push(text::caption(fl!("no-probes")));
+        }
+        for probe in &health.probes {
+            let when = probe
+                .started_at
+                .map(|t| format_age(t.timestamp()))
+                .unwrap_or_default();
+            let status = if probe.exit_code == 0 {
+                fl!("probe-passed", when = when)
+            } else {
+                fl!("probe-failed", when = when, code = probe.exit_code.to_string())
+            };
+            col = col.push(text::caption_heading(status));
+            if !probe.output.is_empty() {
+                col = col.push(
+                    text::monotext(self.redactor.redact(&probe.output).into_owned()).size(11),
+                );
+            }
+        }
+        col.into()
+    }
+
+    fn view_resource_limits(&self, details: &ContainerDetails) -> Element<'_, Message> {
+        const MIN_MB: f64 = 64.0;
+        const STEP_MB: f64 = 64.0;
+
+        let current_mb = details.memory_limit as f64 / (1024.0 * 1024.0);
+        let max_mb = (details.host_memory as f64 / (1024.0 * 1024.0))
+            .max(current_mb)
+            .max(MIN_MB * 2.0);
+        let value = self.memory_limit_draft.unwrap_or(if details.memory_limit > 0 {
+            current_mb
+        } else {
+            max_mb
+        });
+
+        let label = if self.memory_limit_draft.is_none() && details.memory_limit == 0 {
+            fl!("unlimited")
+        } else {
+            self.format_memory(value)
+        };
+
+        const MIN_CORES: f64 = 0.1;
+        let max_cores = details.host_cpus.max(details.cpu_limit).max(MIN_CORES * 2.0);
+        let cores = self.cpu_limit_draft.unwrap_or(if details.cpu_limit > 0.0 {
+            details.cpu_limit
+        } else {
+            max_cores
+        });
+        let cpu_label = if cores >= details.host_cpus {
+            fl!("unlimited")
+        } else {
+            fl!("cores", cores = format!("{:.1}", cores))
+        };
+
+        let stats = self.stats.get(&self.details_container_id);
+        // Usage within 5% of a limit means the kernel is throttling or reclaiming
+        let near_limit = stats.is_some_and(|s| {
+            let memory_capped =
+                details.memory_limit > 0 && s.memory_usage_mb >= 0.95 * current_mb;
+            let cpu_capped =
+                details.cpu_limit > 0.0 && s.cpu_percent >= 95.0 * details.cpu_limit;
+            let pids_capped =
+                details.pids_limit > 0 && s.pids as f64 >= 0.95 * details.pids_limit as f64;
+            memory_capped || cpu_capped || pids_capped
+        });
+
+        let mut col = widget::column()
+            .push(
+                widget::row()
+                    .push(text::body(fl!("memory-limit")).width(Length::Fill))
+                    .push(text::caption(label))
+                    .align_y(Alignment::Center),
+            )
+            .push(
+                widget::slider(MIN_MB..=max_mb, value, Message::MemoryLimitChanged)
+                    .step(STEP_MB)
+                    .on_release(Message::ApplyMemoryLimit),
+            )
+            .push(
+                widget::row()
+                    .push(text::body(fl!("cpu-limit")).width(Length::Fill))
+                    .push(text::caption(cpu_label))
+                    .align_y(Alignment::Center),
+            )
+            .push(
+                widget::slider(MIN_CORES..=max_cores, cores, Message::CpuLimitChanged)
+                    .step(MIN_CORES)
+                    .on_release(Message::ApplyCpuLimit),
+            )
+            .spacing(4);
+        if let Some(s) = stats {
+            col = col.push(text::caption(fl!(
+                "current-usage",
+                cpu = format!("{:.1}", self.cpu_share(s)),
+                memory = self.format_memory(s.memory_usage_mb)
+            )));
+        }
+        let swap = match details.memory_swap {
+            -1 => Some(fl!("unlimited")),
+            bytes if bytes > 0 => Some(self.format_memory(bytes as f64 / (1024.0 * 1024.0))),
+            _ => None,
+        };
+        if let Some(swap) = swap {
+            col = col.push(text::caption(fl!("memory-swap-limit", limit = swap)));
+        }
+        if details.cpu_shares > 0 {
+            col = col.push(text::caption(fl!(
+                "cpu-shares",
+                shares = details.cpu_shares.to_string()
+            )));
+        }
+        let pids = stats.map(|s| s.pids.to_string()).unwrap_or_else(|| "—".to_string());
+        col = col.push(text::caption(if details.pids_limit > 0 {
+            fl!("pids-limited", pids = pids, limit = details.pids_limit.to_string())
+        } else {
+            fl!("pids-unlimited", pids = pids)
+        }));
+        if near_limit {
+            col = col.push(text::caption(fl!("at-limit")));
+        }
+        if let Some(e) = &self.limits_error {
+            col = col.push(text::caption(e));
+        }
+        col.into()
+    }
+
+    fn view_details(&self) -> Element<'_, Message> {
+        let header = widget::row()
+            .push(
+                widget::button::icon(widget::icon::from_name("go-previous-symbolic"))
+                    .on_press(Message::BackToList),
+            )
+            .push(self.view_details_title())
+            .align_y(Alignment::Center)
+            .spacing(8)
+            .padding(8);
+
+        let body: Element<Message> = if self.details_loading {
+            widget::container(text::body(fl!("loading")))
+                .padding(16)
+                .center_x(Length::Fill)
+                .into()
+        } else if let (Some(details), DetailsTab::RawJson) =
+            (&self.details_data, self.details_tab)
+        {
+            self.view_raw_json(details)
+        } else if let Some(details) = &self.details_data {
+            let mut col = widget::column().spacing(8).padding([0, 12]);
+
+            if let Some(container) = self
+                .containers
+                .iter()
+                .find(|c| c.id == self.details_container_id)
+            {
+                col = col.push(text::body(fl!("image")));
+                col = col.push(
+                    widget::row()
+                        .push(
+                            widget::button::link(container.image.clone())
+                                .on_press(Message::ShowImageDetails(container.image.clone()))
+                                .width(Length::Fill),
+                        )
+                        .push(
+                            widget::button::icon(widget::icon::from_name(
+                                "folder-download-symbolic",
+                            ))
+                            .extra_small()
+                            .tooltip(fl!("pull-image"))
+                            .on_press(Message::PullImage(container.image.clone())),
+                        )
+                        .align_y(Alignment::Center)
+                        .spacing(4),
+                );
+                if let Some(digest) = &details.image_digest {
+                    col = col.push(
+                        widget::row()
+                            .push(text::monotext(digest).size(11).width(Length::Fill))
+                            .push(copy_button(digest.clone()))
+                            .align_y(Alignment::Center)
+                            .spacing(4),
+                    );
+                }
+                if let Some(created) = details.image_created {
+                    let built = created
+                        .with_timezone(&chrono::Local)
+                        .format("%Y-%m-%d %H:%M")
+                        .to_string();
+                    col = col.push(text::caption(fl!("image-built", date = built)));
+                }
+                if self.outdated_images.contains(&container.image) {
+                    col = col.push(text::caption(fl!("image-update-available")));
+                }
+                if let Some(status) = &self.pull_status {
+                    col = col.push(text::caption(status));
+                }
+                col = col.push(widget::divider::horizontal::light());
+            }
+
+            col = col.push(self.view_container_state(details));
+            col = col.push(widget::divider::horizontal::light());
+
+            if !details.security_risks.is_empty() {
+                col = col.push(
+                    widget::row()
+                        .push(widget::icon::from_name("security-low-symbolic").size(16))
+                        .push(text::body(fl!("security")))
+                        .align_y(Alignment::Center)
+                        .spacing(4),
+                );
+                for risk in &details.security_risks {
+                    col = col.push(text::caption(security_risk_label(risk)));
+                }
+                col = col.push(widget::divider::horizontal::light());
+            }
+
+            if let Some(health) = &details.healthcheck {
+                col = col.push(self.view_healthcheck(health));
+                col = col.push(widget::divider::horizontal::light());
+            }
+
+            if !details.devices.is_empty() || !details.device_requests.is_empty() {
+                col = col.push(text::body(fl!("devices")));
+                for request in &details.device_requests {
+                    col = col.push(text::caption(device_request_label(request)));
+                }
+                for (host, container) in &details.devices {
+                    col = col.push(text::caption(format!("{} → {}", host, container)));
+                }
+                col = col.push(widget::divider::horizontal::light());
+            }
+
+            col = col.push(self.view_duplicate());
+            col = col.push(widget::divider::horizontal::light());
+
+            col = col.push(self.view_exec());
+            col = col.push(widget::divider::horizontal::light());
+
+            let selected = RestartPolicy::ALL
+                .iter()
+                .position(|p| *p == details.restart_policy);
+            col = col.push(widget::settings::item(
+                fl!("restart-policy"),
+                widget::dropdown(
+                    self.restart_policy_options.as_slice(),
+                    selected,
+                    Message::SetRestartPolicy,
+                ),
+            ));
+            col = col.push(self.view_resource_limits(details));
+            col = col.push(widget::settings::item(
+                fl!("count-errors"),
+                widget::toggler(
+                    self.config
+                        .error_watch
+                        .contains(&self.details_container_name),
+                )
+                .on_toggle(|enabled| {
+                    Message::ToggleErrorWatch(self.details_container_name.clone(), enabled)
+                }),
+            ));
+            col = col.push(widget::settings::item(
+                fl!("start-with-session"),
+                widget::toggler(
+                    self.config
+                        .autostart_containers
+                        .contains(&self.details_container_name),
+                )
+                .on_toggle(|enabled| {
+                    Message::ToggleAutostartContainer(self.details_container_name.clone(), enabled)
+                }),
+            ));
+            col = col.push(widget::divider::horizontal::light());
+
+            col = col.push(self.view_log_alerts());
+            col = col.push(widget::divider::horizontal::light());
+
+            // Ports section - find the container to get its ports
+            let container_ports: Vec<&PortMapping> = self
+                .containers
+                .iter()
+                .find(|c| c.name == self.details_container_name)
+                .map(|c| c.ports.iter().collect())
+                .unwrap_or_default();
+
+            col = col.push(text::body(fl!("ports")));
+            if container_ports.is_empty() {
+                col = col.push(text::caption(fl!("no-data")));
+            } else {
+                for port in &container_ports {
+                    let port_str = if let Some(pub_port) = port.public_port {
+                        format!("{}:{}/{}", pub_port, port.private_port, port.protocol)
+                    } else {
+                        format!("{}/{}", port.private_port, port.protocol)
+                    };
+                    col = col.push(copyable_row(port_str));
+                }
+            }
+
+            col = col.push(widget::divider::horizontal::light());
+
+            // Volumes section
+            col = col.push(text::body(fl!("volumes")));
+            if details.volumes.is_empty() {
+                col = col.push(text::caption(fl!("no-data")));
+            } else {
+                for mount in &details.volumes {
+                    let (icon, kind) = match mount.kind {
+                        MountKind::Bind => ("folder-symbolic", fl!("bind-mount")),
+                        MountKind::Volume => ("drive-harddisk-symbolic", fl!("named-volume")),
+                        MountKind::Other => ("drive-removable-media-symbolic", fl!("other-mount")),
+                    };
+                    // Named volumes read better by name than by their path under /var/lib/docker
+                    let source = if mount.kind == MountKind::Volume && !mount.name.is_empty() {
+                        mount.name.clone()
+                    } else {
+                        mount.source.clone()
+                    };
+                    let source: Element<Message> = if mount.on_host {
+                        widget::tooltip(
+                            widget::button::link(source)
+                                .on_press(Message::OpenFolder(mount.source.clone())),
+                            text::caption(fl!("open-folder")),
+                            widget::tooltip::Position::Top,
+                        )
+                        .into()
+                    } else {
+                        text::caption(source).into()
+                    };
+                    col = col.push(
+                        widget::row()
+                            .push(widget::tooltip(
+                                widget::icon::from_name(icon).size(16),
+                                text::caption(kind),
+                                widget::tooltip::Position::Top,
+                            ))
+                            .push(
+                                widget::column()
+                                    .push(source)
+                                    .push(text::caption(format!("→ {}", mount.destination)))
+                                    .width(Length::Fill),
+                            )
+                            .push(copy_button(mount.source.clone()))
+                            .align_y(Alignment::Center)
+                            .spacing(4),
+                    );
+                }
+                if let Some(hint) = &self.open_folder_hint {
+                    col = col.push(text::caption(hint));
+                }
+            }
+
+            col = col.push(widget::divider::horizontal::light());
+
+            // Networks section
+            col = col.push(text::body(fl!("networks")));
+            if details.networks.is_empty() {
+                col = col.push(text::caption(fl!("no-data")));
+            } else {
+                for network in &details.networks {
+                    let (name, ip) = (&network.name, &network.ip);
+                    let net_text = if ip.is_empty() {
+                        name.clone()
+                    } else {
+                        format!("{} ({})", name, ip)
+                    };
+                    let copy = (!ip.is_empty()).then(|| copy_button(ip.clone()));
+                    col = col.push(
+                        widget::row()
+                            .push(text::caption(net_text).width(Length::Fill))
+                            .push_maybe(copy)
+                            .push(
+                                widget::button::icon(widget::icon::from_name(
+                                    "network-offline-symbolic",
+                                ))
+                                .extra_small()
+                                .tooltip(fl!("disconnect"))
+                                .on_press(guarded(Message::DisconnectNetwork(name.clone()))),
+                            )
+                            .align_y(Alignment::Center)
+                            .spacing(4),
+                    );
+                    let addresses: Vec<String> = [
+                        (!network.mac.is_empty()).then(|| fl!("mac", mac = network.mac.as_str())),
+                        (!network.gateway.is_empty())
+                            .then(|| fl!("gateway", gateway = network.gateway.as_str())),
+                    ]
+                    .into_iter()
+                    .flatten()
+                    .collect();
+                    if !addresses.is_empty() {
+                        col = col.push(text::caption(addresses.join(" · ")));
+                    }
+                    if !network.aliases.is_empty() {
+                        col = col.push(text::caption(fl!(
+                            "aliases",
+                            aliases = network.aliases.join(", ")
+                        )));
+                    }
+                }
+            }
+            if !self.connectable_networks.is_empty() {
+                col = col.push(
+                    widget::row()
+                        .push(widget::dropdown(
+                            self.connectable_networks.as_slice(),
+                            self.connect_network,
+                            Message::ConnectNetworkPicked,
+                        ))
+                        .push(
+                            widget::text_input(fl!("alias-optional"), &self.connect_alias)
+                                .on_input(Message::ConnectAliasInput)
+                                .width(Length::Fill),
+                        )
+                        .push(
+                            widget::button::text(fl!("connect"))
+                                .on_press_maybe(
+                                    self.connect_network.map(|_| Message::ConnectNetwork),
+                                ),
+                        )
+                        .align_y(Alignment::Center)
+                        .spacing(4),
+                );
+            }
+            if let Some(status) = &self.network_connect_status {
+                col = col.push(text::caption(status));
+            }
+
+            col = col.push(widget::divider::horizontal::light());
+
+            // Processes section
+            if !self.details_processes.is_empty() {
+                col = col.push(text::body(fl!("processes")));
+                col = col.push(
+                    widget::row()
+                        .push(text::caption_heading("PID").width(Length::Fixed(56.0)))
+                        .push(text::caption_heading(fl!("user")).width(Length::Fixed(64.0)))
+                        .push(text::caption_heading("%CPU").width(Length::Fixed(40.0)))
+                        .push(text::caption_heading(fl!("command")).width(Length::Fill))
+                        .spacing(4),
+                );
+                for process in &self.details_processes {
+                    col = col.push(
+                        widget::row()
+                            .push(text::monotext(&process.pid).size(11).width(Length::Fixed(56.0)))
+                            .push(text::caption(&process.user).width(Length::Fixed(64.0)))
+                            .push(text::monotext(&process.cpu).size(11).width(Length::Fixed(40.0)))
+                            .push(text::caption(&process.command).width(Length::Fill))
+                            .push(
+                                widget::button::icon(widget::icon::from_name(
+                                    "process-stop-symbolic",
+                                ))
+                                .extra_small()
+                                .tooltip(fl!("kill-process"))
+                                .on_press(guarded(Message::KillProcess(process.pid.clone()))),
+                            )
+                            .align_y(Alignment::Center)
+                            .spacing(4),
+                    );
+                }
+                col = col.push(widget::divider::horizontal::light());
+            }
+
+            // Environment Variables section
+            let mut env_header = widget::row()
+                .push(text::body(fl!("environment")).width(Length::Fill))
+                .align_y(Alignment::Center)
+                .spacing(4);
+            if !details.env_vars.is_empty() {
+                env_header = env_header
+                    .push(
+                        widget::checkbox(fl!("skip-secrets"), self.env_skip_secrets)
+                            .on_toggle(Message::ToggleEnvSkipSecrets),
+                    )
+                    .push(
+                        widget::button::text(fl!("copy-env-file"))
+                            .leading_icon(widget::icon::from_name("edit-copy-symbolic"))
+                            .on_press(Message::CopyEnvFile),
+                    );
+            }
+            col = col.push(env_header);
+            if details.env_vars.is_empty() {
+                col = col.push(text::caption(fl!("no-data")));
+            } else {
+                for var in &details.env_vars {
+                    col = col.push(copyable_row(self.redactor.redact(var).into_owned()));
+                }
+            }
+
+            col = col.push(widget::divider::horizontal::light());
+
+            // Labels section
+            col = col.push(text::body(fl!("labels")));
+            if details.labels.is_empty() {
+                col = col.push(text::caption(fl!("no-data")));
+            } else {
+                for (key, value) in &details.labels {
+                    col = col.push(copyable_row(format!("{}={}", key, value)));
+                }
+            }
+
+            scrollable(col).height(400).into()
+        } else {
+            widget::container(text::body(fl!("no-data")))
+                .padding(16)
+                .center_x(Length::Fill)
+                .into()
+        };
+
+        let tab_button = |label: String, tab: DetailsTab| {
+            widget::button::text(label)
+                .on_press(Message::SetDetailsTab(tab))
+                .class(if self.details_tab == tab {
+                    cosmic::theme::Button::Suggested
+                } else {
+                    cosmic::theme::Button::Standard
+                })
+        };
+        let tabs = widget::row()
+            .push(tab_button(fl!("overview"), DetailsTab::Overview))
+            .push(tab_button(fl!("raw-json"), DetailsTab::RawJson))
+            .spacing(4)
+            .padding([0, 12]);
+
+        let mut column = widget::column()
+            .push(header)
+            .push(tabs)
+            .push(widget::divider::horizontal::light());
+
+        if let Some(banner) = self.view_pending_banner() {
+            column = column.push(widget::container(banner).padding([0, 12]));
+        }
+
+        column
+            .push(body)
+            .spacing(4)
+            .width(Length::Fill)
+            .into()
+    }
+
+    fn view_raw_json<'a>(&'a self, details: &'a ContainerDetails) -> Element<'a, Message> {
+        let json = self.redactor.redact(&details.raw_json);
+        let query = self.json_search.to_lowercase();
+        let shown = if query.is_empty() {
+            json.into_owned()
+        } else {
+            // Keep only lines whose key matches, e.g. "restartcount"
+            json.lines()
+                .filter(|line| {
+                    line.trim_start()
+                        .split_once("\":")
+                        .map(|(key, _)| key.to_lowercase().contains(&query))
+                        .unwrap_or(false)
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+
+        let toolbar = widget::row()
+            .push(
+                widget::text_input::search_input(fl!("search-keys"), &self.json_search)
+                    .on_input(Message::JsonSearchChanged)
+                    .on_clear(Message::JsonSearchChanged(String::new()))
+                    .width(Length::Fill),
+            )
+            .push(
+                widget::button::icon(widget::icon::from_name("edit-copy-symbolic"))
+                    .extra_small()
+                    .tooltip(fl!("copy"))
+                    .on_press(Message::CopyText(shown.clone())),
+            )
+            .align_y(Alignment::Center)
+            .spacing(4);
+
+        widget::column()
+            .push(toolbar)
+            .push(scrollable(text::monotext(shown).size(11).width(Length::Fill)).height(360))
+            .spacing(4)
+            .padding([0, 12])
+            .into()
+    }
+
+    fn view_files(&self) -> Element<'_, Message> {
+        let header = widget::row()
+            .push(
+                widget::button::icon(widget::icon::from_name("go-previous-symbolic"))
+                    .on_press(Message::BackToList),
+            )
+            .push(text::title4(&self.files_container_name))
+            .align_y(Alignment::Center)
+            .spacing(8)
+            .padding(8);
+
+        let path_row = widget::row()
+            .push(
+                widget::button::icon(widget::icon::from_name("go-up-symbolic"))
+                    .extra_small()
+                    .tooltip(fl!("parent-directory"))
+                    .on_press_maybe(
+                        (self.files_path != "/")
+                            .then(|| Message::BrowseDirectory(parent_path(&self.files_path))),
+                    ),
+            )
+            .push(text::monotext(&self.files_path).width(Length::Fill))
+            .push(
+                widget::button::icon(widget::icon::from_name("document-send-symbolic"))
+                    .extra_small()
+                    .tooltip(fl!("upload-here"))
+                    .on_press(Message::UploadFile),
+            )
+            .align_y(Alignment::Center)
+            .spacing(4)
+            .padding([0, 12]);
+
+        let body: Element<Message> = if self.files_loading {
+            widget::container(text::body(fl!("loading")))
+                .padding(16)
+                .center_x(Length::Fill)
+                .into()
+        } else if let Some(error) = &self.files_error {
+            widget::container(text::caption(error))
+                .padding(16)
+                .center_x(Length::Fill)
+                .into()
+        } else if self.files_entries.is_empty() {
+            widget::container(text::body(fl!("empty-directory")))
+                .padding(16)
+                .center_x(Length::Fill)
+                .into()
+        } else {
+            let mut col = widget::column().spacing(2).padding([0, 12]);
+            for entry in &self.files_entries {
+                let path = join_path(&self.files_path, &entry.name);
+                let row = if entry.is_dir {
+                    widget::row()
+                        .push(widget::icon::from_name("folder-symbolic").size(16))
+                        .push(
+                            widget::container(
+                                widget::button::text(&entry.name)
+                                    .on_press(Message::BrowseDirectory(path.clone()))
+                                    .class(cosmic::theme::Button::Text),
+                            )
+                            .width(Length::Fill),
+                        )
+                } else {
+                    widget::row()
+                        .push(widget::icon::from_name("text-x-generic-symbolic").size(16))
+                        .push(text::caption(&entry.name).width(Length::Fill))
+                };
+                let row = row.push(
+                    widget::button::icon(widget::icon::from_name("document-save-symbolic"))
+                        .extra_small()
+                        .tooltip(fl!("download"))
+                        .on_press(Message::DownloadPath(path)),
+                );
+                col = col.push(row.align_y(Alignment::Center).spacing(4));
+            }
+            scrollable(col).height(400).into()
+        };
+
+        let mut transfer = widget::column()
+            .push(
+                widget::row()
+                    .push(
+                        widget::text_input(fl!("container-path"), &self.transfer_path)
+                            .on_input(Message::TransferPathInput)
+                            .width(Length::Fill),
+                    )
+                    .push(
+                        widget::button::icon(widget::icon::from_name("document-save-symbolic"))
+                            .extra_small()
+                            .tooltip(fl!("download"))
+                            .on_press_maybe(
+                                (!self.transfer_path.trim().is_empty()).then(|| {
+                                    Message::DownloadPath(self.transfer_path.trim().to_string())
+                                }),
+                            ),
+                    )
+                    .align_y(Alignment::Center)
+                    .spacing(4),
+            )
+            .spacing(4)
+            .padding([0, 12]);
+
+        if let Some(status) = &self.transfer_status {
+            transfer = transfer.push(text::caption(status));
+        }
+
+        widget::column()
+            .push(header)
+            .push(widget::divider::horizontal::light())
+            .push(path_row)
+            .push(body)
+            .push(widget::divider::horizontal::light())
+            .push(transfer)
+            .spacing(4)
+            .width(Length::Fill)
+            .into()
+    }
+
+    fn browse_directory(&mut self, id: String, path: String) -> Task<Action<Message>> {
+        self.files_loading = true;
+        cosmic::task::future(async move {
+            Message::DirectoryListed(docker::list_directory(id, path).await)
+        })
+    }
+
+    fn load_comparison(&mut self) -> Task<Action<Message>> {
+        let (Some(left), Some(right)) = (self.compare_left, self.compare_right) else {
+            return Task::none();
+        };
+        let (Some(left_id), Some(right_id)) =
+            (self.compare_ids.get(left).cloned(), self.compare_ids.get(right).cloned())
+        else {
+            return Task::none();
+        };
+        self.compare_result = None;
+        self.compare_loading = true;
+        cosmic::task::future(async move {
+            let result = async {
+                let left = docker::fetch_comparable_config(left_id).await?;
+                let right = docker::fetch_comparable_config(right_id).await?;
+                Ok::<_, String>((left, right))
+            }
+            .await;
+            Message::CompareLoaded(result)
+        })
+    }
+
+    fn view_compare(&self) -> Element<'_, Message> {
+        let header = widget::row()
+            .push(
+                widget::button::icon(widget::icon::from_name("go-previous-symbolic"))
+                    .on_press(Message::BackToList),
+            )
+            .push(text::title4(fl!("compare")))
+            .align_y(Alignment::Center)
+            .spacing(8)
+            .padding(8);
+
+        let pickers = widget::row()
+            .push(
+                widget::dropdown(
+                    self.compare_names.as_slice(),
+                    self.compare_left,
+                    Message::SelectCompareLeft,
+                )
+                .width(Length::Fill),
+            )
+            .push(text::body("↔"))
+            .push(
+                widget::dropdown(
+                    self.compare_names.as_slice(),
+                    self.compare_right,
+                    Message::SelectCompareRight,
+                )
+                .width(Length::Fill),
+            )
+            .align_y(Alignment::Center)
+            .spacing(8)
+            .padding([0, 12]);
+
+        let body: Element<Message> = if self.compare_loading {
+            widget::container(text::body(fl!("loading")))
+                .padding(16)
+                .center_x(Length::Fill)
+                .into()
+        } else if let Some((left, right)) = &self.compare_result {
+            let sections = [
+                (fl!("image"), compare::diff_maps(&left.image, &right.image)),
+                (fl!("environment"), compare::diff_maps(&left.env, &right.env)),
+                (fl!("labels"), compare::diff_maps(&left.labels, &right.labels)),
+                (fl!("volumes"), compare::diff_maps(&left.mounts, &right.mounts)),
+                (fl!("ports"), compare::diff_maps(&left.ports, &right.ports)),
+            ];
+
+            let mut col = widget::column().spacing(8).padding([0, 12]);
+            for (title, entries) in sections {
+                col = col.push(text::body(title));
+                if entries.is_empty() {
+                    col = col.push(text::caption(fl!("identical")));
+                }
+                for entry in &entries {
+                    col = col.push(self.view_diff_entry(entry, &left.name, &right.name));
+                }
+                col = col.push(widget::divider::horizontal::light());
+            }
+            scrollable(col).height(400).into()
+        } else {
+            widget::container(text::body(fl!("compare-hint")))
+                .padding(16)
+                .center_x(Length::Fill)
+                .into()
+        };
+
+        widget::column()
+            .push(header)
+            .push(pickers)
+            .push(widget::divider::horizontal::light())
+            .push(body)
+            .spacing(4)
+            .width(Length::Fill)
+            .into()
+    }
+
+    fn view_diff_entry<'a>(
+        &self,
+        entry: &DiffEntry,
+        left_name: &str,
+        right_name: &str,
+    ) -> Element<'a, Message> {
+        let side = |name: &str, value: &Option<String>| {
+            let value = match value {
+                Some(v) => self.redactor.redact(&format!("{}={}", entry.key, v)).into_owned(),
+                None => "—".to_string(),
+            };
+            text::caption(format!("{}: {}", name, value))
+        };
+        widget::column()
+            .push(text::monotext(entry.key.clone()).size(11))
+            .push(side(left_name, &entry.left))
+            .push(side(right_name, &entry.right))
+            .spacing(2)
+            .into()
     }
 
-    fn view(&self) -> Element<'_, Self::Message> {
-        let running_count = self
-            .containers
-            .iter()
-            .filter(|c| c.state == ContainerState::Running)
-            .count();
+    fn view_images(&self) -> Element<'_, Message> {
+        let header = widget::row()
+            .push(
+                widget::button::icon(widget::icon::from_name("go-previous-symbolic"))
+                    .on_press(Message::BackToList),
+            )
+            .push(text::title4(fl!("images")).width(Length::Fill))
+            .push(
+                widget::button::text(fl!("prune-dangling"))
+                    .on_press(guarded(Message::PruneImages))
+                    .class(cosmic::theme::Button::Standard),
+            )
+            .align_y(Alignment::Center)
+            .spacing(8)
+            .padding(8);
 
-        if running_count > 0 {
-            let btn = self
-                .core
-                .applet
-                .icon_button("cosmic-applet-docker-symbolic")
-                .on_press(Message::TogglePopup);
-            widget::row()
-                .push(btn)
-                .push(text::body(format!("{}", running_count)))
-                .align_y(Alignment::Center)
-                .spacing(4)
-                .into()
-        } else {
-            self.core
-                .applet
-                .icon_button("cosmic-applet-docker-symbolic")
-                .on_press(Message::TogglePopup)
-                .into()
+        let mut col = widget::column().spacing(8).padding([0, 12]);
+        if let Some(banner) = self.view_pending_banner() {
+            col = col.push(banner);
+        }
+        if let Some(status) = &self.images_status {
+            col = col.push(text::caption(status));
+        }
+        if let Some(progress) = self.view_archive_progress(ArchiveKind::Image) {
+            col = col.push(progress);
         }
-    }
 
-    fn view_window(&self, id: Id) -> Element<'_, Self::Message> {
-        if self.popup != Some(id) {
-            return text::body("").into();
+        if self.images_loading {
+            col = col.push(text::body(fl!("loading")));
+        } else if self.images.is_empty() {
+            col = col.push(text::caption(fl!("no-data")));
         }
 
-        let content: Element<Message> = match &self.current_view {
-            PopupView::ContainerList => self.view_container_list(),
-            PopupView::ContainerLogs => self.view_logs(),
-            PopupView::ContainerDetails => self.view_details(),
-        };
+        for image in &self.images {
+            let in_use = if image.containers > 0 {
+                fl!("used-by", count = image.containers.to_string())
+            } else {
+                String::new()
+            };
+            let actions: Element<Message> = if self.pending_ops.contains(&image.id) {
+                text::caption(fl!("loading")).into()
+            } else {
+                widget::row()
+                    .push(
+                        widget::button::icon(widget::icon::from_name("bookmark-new-symbolic"))
+                            .extra_small()
+                            .tooltip(fl!("tag"))
+                            .on_press(Message::StartTag(image.id.clone())),
+                    )
+                    .push_maybe(image.tags.iter().any(|t| t != "<none>:<none>").then(|| {
+                        widget::button::icon(widget::icon::from_name("send-to-symbolic"))
+                            .extra_small()
+                            .tooltip(fl!("push-image"))
+                            .on_press(Message::PushImage(
+                                image.id.clone(),
+                                image.display_name(),
+                            ))
+                    }))
+                    .push(
+                        widget::button::icon(widget::icon::from_name("document-save-symbolic"))
+                            .extra_small()
+                            .tooltip(fl!("export-image"))
+                            .on_press_maybe(
+                                self.archive_export
+                                    .is_none()
+                                    .then(|| Message::ExportImage(image.id.clone())),
+                            ),
+                    )
+                    .push(
+                        widget::button::icon(widget::icon::from_name("user-trash-symbolic"))
+                            .extra_small()
+                            .tooltip(fl!("delete"))
+                            .on_press(guarded(Message::RemoveImage(image.id.clone()))),
+                    )
+                    .spacing(4)
+                    .into()
+            };
+            col = col.push(
+                widget::row()
+                    .push(
+                        widget::column()
+                            .push(
+                                widget::button::link(image.display_name())
+                                    .on_press(Message::ShowImageDetails(image.id.clone())),
+                            )
+                            .push(text::caption(format!(
+                                "{}  {}",
+                                self.format_size(image.size as f64),
+                                in_use
+                            )))
+                            .width(Length::Fill),
+                    )
+                    .push(actions)
+                    .align_y(Alignment::Center)
+                    .spacing(4),
+            );
+            if let Some((_, reference)) = self.tagging.as_ref().filter(|(id, _)| *id == image.id)
+            {
+                col = col.push(
+                    widget::row()
+                        .push(
+                            widget::text_input(fl!("new-tag"), reference)
+                                .on_input(Message::TagInput)
+                                .on_submit(|_| Message::SubmitTag)
+                                .width(Length::Fill),
+                        )
+                        .push(
+                            widget::button::icon(widget::icon::from_name(
+                                "object-select-symbolic",
+                            ))
+                            .extra_small()
+                            .tooltip(fl!("tag"))
+                            .on_press(Message::SubmitTag),
+                        )
+                        .push(
+                            widget::button::icon(widget::icon::from_name("window-close-symbolic"))
+                                .extra_small()
+                                .tooltip(fl!("cancel"))
+                                .on_press(Message::CancelTag),
+                        )
+                        .align_y(Alignment::Center)
+                        .spacing(4),
+                );
+            }
+        }
 
-        self.core
-            .applet
-            .popup_container(content)
-            .max_width(400.0)
-            .max_height(600.0)
+        widget::column()
+            .push(header)
+            .push(widget::divider::horizontal::light())
+            .push(scrollable(col).height(400))
+            .spacing(4)
+            .width(Length::Fill)
             .into()
     }
 
-    fn on_close_requested(&self, id: window::Id) -> Option<Message> {
-        Some(Message::PopupClosed(id))
+    /// Refreshes the networks offered for connecting the details container,
+    /// leaving out ones it is already on and the host/none pseudo-networks.
+    fn update_connectable_networks(&mut self) {
+        let attached: Vec<&String> = self
+            .details_data
+            .iter()
+            .flat_map(|d| d.networks.iter().map(|n| &n.name))
+            .collect();
+        let connectable: Vec<String> = self
+            .networks
+            .iter()
+            .filter(|n| !matches!(n.name.as_str(), "host" | "none"))
+            .filter(|n| !attached.contains(&&n.name))
+            .map(|n| n.name.clone())
+            .collect();
+        if connectable != self.connectable_networks {
+            self.connect_network = None;
+            self.connectable_networks = connectable;
+        }
     }
 
-    fn style(&self) -> Option<cosmic::iced_runtime::Appearance> {
-        Some(cosmic::applet::style())
+    fn view_compose_output(&self, group_name: &str) -> Option<Element<'_, Message>> {
+        let (project, _) = self
+            .compose_task
+            .as_ref()
+            .filter(|(project, _)| project.name == group_name)?;
+        let header = widget::row()
+            .push(text::caption_heading(project.working_dir.as_str()).width(Length::Fill))
+            .push(
+                widget::button::icon(widget::icon::from_name("window-close-symbolic"))
+                    .extra_small()
+                    .tooltip(fl!("close"))
+                    .on_press_maybe((!self.compose_running).then_some(Message::CloseComposeOutput)),
+            )
+            .align_y(Alignment::Center)
+            .spacing(4);
+        let output = scrollable(text::monotext(self.compose_output.as_str()).size(11))
+            .anchor_bottom()
+            .height(120)
+            .width(Length::Fill);
+        Some(
+            widget::column()
+                .push(header)
+                .push(output)
+                .spacing(4)
+                .padding([0, 8])
+                .into(),
+        )
     }
 
-    fn subscription(&self) -> Subscription<Self::Message> {
-        let popup_open = self.popup.is_some();
+    fn view_disk_usage(&self) -> Element<'_, Message> {
+        let header = widget::row()
+            .push(
+                widget::button::icon(widget::icon::from_name("go-previous-symbolic"))
+                    .on_press(Message::BackToList),
+            )
+            .push(text::title4(fl!("disk-usage")).width(Length::Fill))
+            .push(
+                widget::button::icon(widget::icon::from_name("view-refresh-symbolic"))
+                    .extra_small()
+                    .tooltip(fl!("refresh"))
+                    .on_press_maybe((!self.disk_usage_loading).then_some(Message::ShowDiskUsage)),
+            )
+            .align_y(Alignment::Center)
+            .spacing(8)
+            .padding(8);
 
-        let mut subs = vec![
-            docker::container_list_subscription(popup_open).map(Message::DockerEvent),
-            docker::docker_events_subscription().map(Message::DockerEvent),
-        ];
+        let mut col = widget::column().spacing(8).padding([0, 12]);
+        if let Some(banner) = self.view_pending_banner() {
+            col = col.push(banner);
+        }
+        if let Some(status) = &self.disk_usage_status {
+            col = col.push(text::caption(status));
+        }
 
-        if popup_open && self.current_view == PopupView::ContainerList {
-            let running_ids: Vec<String> = self
-                .containers
-                .iter()
-                .filter(|c| c.state == ContainerState::Running)
-                .map(|c| c.id.clone())
-                .collect();
+        if self.disk_usage_loading {
+            col = col.push(text::body(fl!("loading")));
+        } else if let Some(usage) = &self.disk_usage {
+            let categories = [
+                (fl!("images"), usage.images),
+                (fl!("containers"), usage.containers),
+                (fl!("volumes"), usage.volumes),
+                (fl!("build-cache"), usage.build_cache),
+            ];
+            for (label, summary) in categories {
+                col = col.push(widget::settings::item(
+                    format!("{} ({})", label, summary.count),
+                    widget::column()
+                        .push(text::body(self.format_size(summary.size as f64)))
+                        .push(text::caption(fl!(
+                            "reclaimable",
+                            size = self.format_size(summary.reclaimable as f64)
+                        )))
+                        .align_x(Alignment::End),
+                ));
+            }
+            if !usage.container_sizes.is_empty() {
+                col = col
+                    .push(widget::divider::horizontal::light())
+                    .push(text::body(fl!("largest-containers")));
+            }
+            for size in usage.container_sizes.iter().take(LARGEST_CONTAINERS) {
+                let mut name = widget::row()
+                    .push(text::body(&size.name))
+                    .align_y(Alignment::Center)
+                    .spacing(4);
+                if size.size_rw > LARGE_WRITABLE_LAYER || self.layer_warning(&size.id).is_some() {
+                    name = name.push(widget::tooltip(
+                        widget::icon::from_name("dialog-warning-symbolic").size(12),
+                        text::caption(fl!("large-writable-layer")),
+                        widget::tooltip::Position::Top,
+                    ));
+                }
+                col = col.push(
+                    widget::row()
+                        .push(name.width(Length::Fill))
+                        .push(
+                            widget::column()
+                                .push(text::body(self.format_size(size.size_rw as f64)))
+                                .push(text::caption(fl!(
+                                    "with-image",
+                                    size = self.format_size(size.size_root_fs as f64)
+                                )))
+                                .align_x(Alignment::End),
+                        )
+                        .align_y(Alignment::Center),
+                );
+            }
 
-            subs.push(
-                docker::container_stats_subscription(running_ids.clone()).map(Message::DockerEvent),
-            );
-            subs.push(docker::health_subscription(running_ids).map(Message::DockerEvent));
+            let total: i64 = categories.iter().map(|(_, s)| s.size).sum();
+            let reclaimable: i64 = categories.iter().map(|(_, s)| s.reclaimable).sum();
+            col = col
+                .push(widget::divider::horizontal::light())
+                .push(widget::settings::item(
+                    fl!("total"),
+                    widget::column()
+                        .push(text::heading(self.format_size(total as f64)))
+                        .push(text::caption(fl!(
+                            "reclaimable",
+                            size = self.format_size(reclaimable as f64)
+                        )))
+                        .align_x(Alignment::End),
+                ));
         }
 
-        if popup_open
-            && self.current_view == PopupView::ContainerLogs
-            && !self.log_container_id.is_empty()
-        {
-            subs.push(
-                docker::log_streaming_subscription(self.log_container_id.clone())
-                    .map(Message::DockerEvent),
-            );
-        }
+        col = col
+            .push(widget::divider::horizontal::light())
+            .push(text::body(fl!("free-up-space")))
+            .push(self.view_prune_targets());
 
-        Subscription::batch(subs)
+        widget::column()
+            .push(header)
+            .push(widget::divider::horizontal::light())
+            .push(scrollable(col).height(400))
+            .spacing(4)
+            .width(Length::Fill)
+            .into()
     }
-}
 
-impl DockerApplet {
-    fn view_container_list(&self) -> Element<'_, Message> {
-        let mut content = widget::column().spacing(8).width(Length::Fill).padding([0, 12]);
+    fn view_prune_targets(&self) -> Element<'_, Message> {
+        let targets = self.prune_targets;
+        let nothing_selected = targets
+            == PruneTargets {
+                stopped_containers: false,
+                dangling_images: false,
+                unused_networks: false,
+                build_cache: false,
+            };
+        widget::column()
+            .push(
+                widget::checkbox(fl!("stopped-containers"), targets.stopped_containers).on_toggle(
+                    move |stopped_containers| {
+                        Message::SetPruneTargets(PruneTargets {
+                            stopped_containers,
+                            ..targets
+                        })
+                    },
+                ),
+            )
+            .push(
+                widget::checkbox(fl!("dangling-images-option"), targets.dangling_images).on_toggle(
+                    move |dangling_images| {
+                        Message::SetPruneTargets(PruneTargets {
+                            dangling_images,
+                            ..targets
+                        })
+                    },
+                ),
+            )
+            .push(
+                widget::checkbox(fl!("unused-networks"), targets.unused_networks).on_toggle(
+                    move |unused_networks| {
+                        Message::SetPruneTargets(PruneTargets {
+                            unused_networks,
+                            ..targets
+                        })
+                    },
+                ),
+            )
+            .push(
+                widget::checkbox(fl!("build-cache"), targets.build_cache).on_toggle(
+                    move |build_cache| {
+                        Message::SetPruneTargets(PruneTargets {
+                            build_cache,
+                            ..targets
+                        })
+                    },
+                ),
+            )
+            .push(
+                widget::button::text(fl!("free-up-space"))
+                    .on_press_maybe(
+                        (!self.pruning && !nothing_selected).then(|| guarded(Message::FreeUpSpace)),
+                    )
+                    .class(cosmic::theme::Button::Destructive),
+            )
+            .spacing(8)
+            .into()
+    }
 
-        // Header
-        let running_count = self
-            .containers
-            .iter()
-            .filter(|c| c.state == ContainerState::Running)
-            .count();
+    fn view_networks(&self) -> Element<'_, Message> {
+        let header = widget::row()
+            .push(
+                widget::button::icon(widget::icon::from_name("go-previous-symbolic"))
+                    .on_press(Message::BackToList),
+            )
+            .push(text::title4(fl!("networks")).width(Length::Fill))
+            .align_y(Alignment::Center)
+            .spacing(8)
+            .padding(8);
 
-        let header = text::heading(format!(
-            "{} · {} running",
-            fl!("docker-containers"),
-            running_count
-        ))
-        .width(Length::Fill);
+        let mut col = widget::column().spacing(8).padding([0, 12]);
+        if let Some(banner) = self.view_pending_banner() {
+            col = col.push(banner);
+        }
+        if let Some(status) = &self.networks_status {
+            col = col.push(text::caption(status));
+        }
 
-        content = content.push(widget::container(header).padding(8));
+        if self.networks_loading {
+            col = col.push(text::body(fl!("loading")));
+        } else if self.networks.is_empty() {
+            col = col.push(text::caption(fl!("no-data")));
+        }
 
-        if !self.docker_available {
-            content = content.push(
-                widget::container(text::body(fl!("docker-unavailable")))
-                    .padding(16)
-                    .width(Length::Fill)
-                    .center_x(Length::Fill),
+        for network in &self.networks {
+            let connected = self
+                .containers
+                .iter()
+                .filter(|c| c.networks.contains(&network.name))
+                .count();
+            let removable = connected == 0 && !network.is_predefined();
+            let actions: Element<Message> = if self.pending_ops.contains(&network.id) {
+                text::caption(fl!("loading")).into()
+            } else {
+                widget::button::icon(widget::icon::from_name("user-trash-symbolic"))
+                    .extra_small()
+                    .tooltip(fl!("delete"))
+                    .on_press_maybe(
+                        removable.then(|| guarded(Message::RemoveNetwork(network.id.clone()))),
+                    )
+                    .into()
+            };
+            col = col.push(
+                widget::row()
+                    .push(
+                        widget::column()
+                            .push(text::body(&network.name))
+                            .push(text::caption(format!(
+                                "{}  {}  {}",
+                                network.driver,
+                                network.scope,
+                                fl!("connected-count", count = connected.to_string())
+                            )))
+                            .width(Length::Fill),
+                    )
+                    .push(actions)
+                    .align_y(Alignment::Center)
+                    .spacing(4),
             );
-            return scrollable(content).height(Length::Shrink).into();
         }
 
-        // Search bar
-        let search = widget::text_input::search_input(fl!("search-placeholder"), &self.search_query)
-            .on_input(Message::SearchChanged)
-            .on_clear(Message::ClearSearch);
-        content = content.push(search);
+        widget::column()
+            .push(header)
+            .push(widget::divider::horizontal::light())
+            .push(scrollable(col).height(400))
+            .spacing(4)
+            .width(Length::Fill)
+            .into()
+    }
 
-        // Bulk action buttons
-        let bulk_actions = widget::row()
+    fn view_archive_progress(&self, kind: ArchiveKind) -> Option<Element<'_, Message>> {
+        let export = self.archive_export.as_ref().filter(|e| e.kind == kind)?;
+        // Sizes are only estimates of the archive size, so clamp the progress
+        let progress = if export.total > 0 {
+            (export.written as f32 / export.total as f32).min(1.0) * 100.0
+        } else {
+            0.0
+        };
+        Some(
+            widget::column()
+                .push(text::caption(fl!(
+                    "writing-archive",
+                    name = export.name.as_str(),
+                    written = self.format_size(export.written as f64)
+                )))
+                .push(widget::progress_bar(0.0..=100.0, progress).height(4))
+                .spacing(4)
+                .into(),
+        )
+    }
+
+    fn view_volumes(&self) -> Element<'_, Message> {
+        let header = widget::row()
             .push(
-                widget::button::text(fl!("start-all"))
-                    .on_press(Message::StartAll)
+                widget::button::icon(widget::icon::from_name("go-previous-symbolic"))
+                    .on_press(Message::BackToList),
+            )
+            .push(text::title4(fl!("volumes")).width(Length::Fill))
+            .push(
+                widget::button::icon(widget::icon::from_name("list-add-symbolic"))
+                    .extra_small()
+                    .tooltip(fl!("create-volume"))
+                    .selected(self.creating_volume)
+                    .on_press(Message::ToggleCreateVolume),
+            )
+            .push(
+                widget::button::text(fl!("prune-unused"))
+                    .on_press(Message::FindUnusedVolumes)
                     .class(cosmic::theme::Button::Standard),
             )
             .push(
-                widget::button::text(fl!("stop-all"))
-                    .on_press(Message::StopAll)
+                widget::button::text(fl!("show-sizes"))
+                    .on_press_maybe(
+                        (!self.volume_sizes_loading).then_some(Message::LoadVolumeSizes),
+                    )
                     .class(cosmic::theme::Button::Standard),
             )
-            .spacing(8);
-        content = content.push(bulk_actions);
+            .align_y(Alignment::Center)
+            .spacing(8)
+            .padding(8);
 
-        if self.containers.is_empty() {
-            content = content.push(
-                widget::container(text::body(fl!("no-containers")))
-                    .padding(16)
-                    .width(Length::Fill)
-                    .center_x(Length::Fill),
-            );
-            return scrollable(content).height(Length::Shrink).into();
+        let mut col = widget::column().spacing(8).padding([0, 12]);
+        if let Some(banner) = self.view_pending_banner() {
+            col = col.push(banner);
+        }
+        if let Some(status) = &self.volumes_status {
+            col = col.push(text::caption(status));
+        }
+        if let Some(progress) = self.view_archive_progress(ArchiveKind::Volume) {
+            col = col.push(progress);
+        }
+        if self.creating_volume {
+            col = col
+                .push(
+                    widget::text_input(fl!("name"), &self.new_volume_name)
+                        .on_input(Message::NewVolumeNameInput),
+                )
+                .push(widget::settings::item(
+                    fl!("driver"),
+                    widget::text_input("local", &self.new_volume_driver)
+                        .on_input(Message::NewVolumeDriverInput)
+                        .width(Length::Fixed(140.0)),
+                ))
+                .push(
+                    widget::text_input(fl!("labels-hint"), &self.new_volume_labels)
+                        .on_input(Message::NewVolumeLabelsInput)
+                        .on_submit(|_| Message::CreateVolume),
+                )
+                .push(
+                    widget::button::text(fl!("create-volume"))
+                        .on_press_maybe(
+                            (!self.new_volume_name.trim().is_empty())
+                                .then_some(Message::CreateVolume),
+                        )
+                        .class(cosmic::theme::Button::Suggested),
+                )
+                .push(widget::divider::horizontal::light());
         }
 
-        // Filter containers by search query
-        let query = self.search_query.to_lowercase();
-        let filtered: Vec<&ContainerInfo> = self
-            .containers
-            .iter()
-            .filter(|c| {
-                if query.is_empty() {
-                    return true;
-                }
-                c.name.to_lowercase().contains(&query)
-                    || c.image.to_lowercase().contains(&query)
-            })
-            .collect();
-
-        if filtered.is_empty() {
-            content = content.push(
-                widget::container(text::body(fl!("no-containers")))
-                    .padding(16)
-                    .width(Length::Fill)
-                    .center_x(Length::Fill),
-            );
-            return scrollable(content).height(Length::Shrink).into();
+        if self.volumes_loading {
+            col = col.push(text::body(fl!("loading")));
+        } else if self.volumes.is_empty() {
+            col = col.push(text::caption(fl!("no-data")));
         }
 
-        // Group by compose project
-        let mut compose_groups: BTreeMap<String, Vec<&ContainerInfo>> = BTreeMap::new();
-        let mut ungrouped: Vec<&ContainerInfo> = Vec::new();
+        for volume in &self.volumes {
+            let consumers: Vec<&str> = self
+                .containers
+                .iter()
+                .filter(|c| c.volumes.contains(&volume.name))
+                .map(|c| c.name.as_str())
+                .collect();
+            let mut details = volume.driver.clone();
+            if let Some(created) = &volume.created {
+                // Only the date part of the RFC 3339 timestamp
+                details.push_str(&format!("  {}", &created[..created.len().min(10)]));
+            }
+            if self.volume_sizes_loading {
+                details.push_str(&format!("  {}", fl!("loading")));
+            } else if let Some(size) = self.volume_sizes.get(&volume.name).filter(|s| **s >= 0) {
+                details.push_str(&format!("  {}", self.format_size(*size as f64)));
+            }
 
-        for container in &filtered {
-            if let Some(project) = container.labels.get("com.docker.compose.project") {
-                compose_groups
-                    .entry(project.clone())
-                    .or_default()
-                    .push(container);
+            let actions: Element<Message> = if self.pending_ops.contains(&volume.name) {
+                text::caption(fl!("loading")).into()
             } else {
-                ungrouped.push(container);
-            }
+                widget::row()
+                    .push(
+                        widget::button::icon(widget::icon::from_name("document-save-symbolic"))
+                            .extra_small()
+                            .tooltip(fl!("backup-volume"))
+                            .on_press_maybe(
+                                self.archive_export
+                                    .is_none()
+                                    .then(|| Message::BackupVolume(volume.name.clone())),
+                            ),
+                    )
+                    .push(
+                        widget::button::icon(widget::icon::from_name("user-trash-symbolic"))
+                            .extra_small()
+                            .tooltip(fl!("delete"))
+                            .on_press(guarded(Message::RemoveVolume(volume.name.clone()))),
+                    )
+                    .spacing(4)
+                    .into()
+            };
+            let mut name_row = widget::row()
+                .push(text::body(&volume.name))
+                .align_y(Alignment::Center)
+                .spacing(4);
+            let used_by = if consumers.is_empty() {
+                name_row = name_row.push(widget::tooltip(
+                    widget::icon::from_name("emblem-ok-symbolic").size(12),
+                    text::caption(fl!("unused-volume")),
+                    widget::tooltip::Position::Top,
+                ));
+                fl!("unused")
+            } else {
+                consumers.join(", ")
+            };
+            col = col.push(
+                widget::row()
+                    .push(
+                        widget::column()
+                            .push(name_row)
+                            .push(text::caption(details))
+                            .push(text::caption(used_by))
+                            .width(Length::Fill),
+                    )
+                    .push(actions)
+                    .align_y(Alignment::Center)
+                    .spacing(4),
+            );
         }
 
-        let has_groups = !compose_groups.is_empty();
+        widget::column()
+            .push(header)
+            .push(widget::divider::horizontal::light())
+            .push(scrollable(col).height(400))
+            .spacing(4)
+            .width(Length::Fill)
+            .into()
+    }
 
-        // Render compose groups
-        for (group_name, group_containers) in &compose_groups {
-            let running_in_group = group_containers
-                .iter()
-                .filter(|c| c.state == ContainerState::Running)
-                .count();
-            let total_in_group = group_containers.len();
-            let is_collapsed = self.collapsed_groups.contains(group_name);
+    fn view_run(&self) -> Element<'_, Message> {
+        let header = widget::row()
+            .push(
+                widget::button::icon(widget::icon::from_name("go-previous-symbolic"))
+                    .on_press(Message::BackToList),
+            )
+            .push(text::title4(fl!("run-container")))
+            .align_y(Alignment::Center)
+            .spacing(8)
+            .padding(8);
 
-            let arrow_icon = if is_collapsed {
-                "go-next-symbolic"
-            } else {
-                "go-down-symbolic"
-            };
+        let picked = self
+            .run_image_options
+            .iter()
+            .position(|i| *i == self.run_image);
 
-            let group_header = widget::row()
-                .push(
-                    widget::button::icon(widget::icon::from_name(arrow_icon))
-                        .extra_small()
-                        .on_press(Message::ToggleGroup(group_name.clone())),
-                )
-                .push(
-                    text::body(fl!(
-                        "compose-group",
-                        name = group_name.as_str(),
-                        running = running_in_group.to_string(),
-                        total = total_in_group.to_string()
-                    ))
-                    .width(Length::Fill),
-                )
+        let mut col = widget::column()
+            .push(text::body(fl!("image")))
+            .push(
+                widget::text_input("redis:7", &self.run_image)
+                    .on_input(Message::RunImageInput),
+            )
+            .spacing(8)
+            .padding([0, 12]);
+        if !self.run_image_options.is_empty() {
+            col = col.push(widget::settings::item(
+                fl!("local-images"),
+                widget::dropdown(
+                    self.run_image_options.as_slice(),
+                    picked,
+                    Message::RunImagePicked,
+                ),
+            ));
+        }
+
+        col = col
+            .push(text::body(fl!("name")))
+            .push(
+                widget::text_input(fl!("optional"), &self.run_name)
+                    .on_input(Message::RunNameInput),
+            )
+            .push(text::body(fl!("ports")))
+            .push(
+                widget::text_input("8080:80, 5432:5432/tcp", &self.run_ports)
+                    .on_input(Message::RunPortsInput),
+            )
+            .push(text::body(fl!("environment")));
+        for (index, env) in self.run_env.iter().enumerate() {
+            col = col.push(
+                widget::row()
+                    .push(text::caption(self.redactor.redact(env).into_owned()).width(Length::Fill))
+                    .push(
+                        widget::button::icon(widget::icon::from_name("edit-delete-symbolic"))
+                            .extra_small()
+                            .tooltip(fl!("delete"))
+                            .on_press(Message::RemoveRunEnv(index)),
+                    )
+                    .align_y(Alignment::Center)
+                    .spacing(4),
+            );
+        }
+        col = col.push(
+            widget::row()
                 .push(
-                    widget::button::icon(widget::icon::from_name(
-                        "media-playback-start-symbolic",
-                    ))
-                    .extra_small()
-                    .tooltip(fl!("start-all"))
-                    .on_press(Message::StartGroup(group_name.clone())),
+                    widget::text_input("KEY=value", &self.run_env_input)
+                        .on_input(Message::RunEnvInput)
+                        .on_submit(|_| Message::AddRunEnv)
+                        .width(Length::Fill),
                 )
                 .push(
-                    widget::button::icon(widget::icon::from_name(
-                        "media-playback-stop-symbolic",
-                    ))
-                    .extra_small()
-                    .tooltip(fl!("stop-all"))
-                    .on_press(Message::StopGroup(group_name.clone())),
+                    widget::button::icon(widget::icon::from_name("list-add-symbolic"))
+                        .extra_small()
+                        .tooltip(fl!("add"))
+                        .on_press(Message::AddRunEnv),
                 )
                 .align_y(Alignment::Center)
-                .spacing(4)
-                .padding([4, 8]);
+                .spacing(4),
+        );
 
-            content = content.push(group_header);
-            content = content.push(widget::divider::horizontal::light());
+        col = col.push(
+            widget::button::text(fl!("run"))
+                .on_press_maybe(
+                    (!self.run_in_progress && !self.run_image.trim().is_empty())
+                        .then_some(Message::RunContainer),
+                )
+                .class(cosmic::theme::Button::Suggested),
+        );
+        if let Some(status) = &self.run_status {
+            col = col.push(text::caption(status));
+        }
 
-            if !is_collapsed {
-                // Running first, then stopped
-                let mut sorted = group_containers.clone();
-                sorted.sort_by_key(|c| c.state != ContainerState::Running);
+        widget::column()
+            .push(header)
+            .push(widget::divider::horizontal::light())
+            .push(scrollable(col).height(400))
+            .spacing(4)
+            .width(Length::Fill)
+            .into()
+    }
 
-                for container in sorted {
-                    if container.state == ContainerState::Running {
-                        content = content.push(self.view_running_container(container));
-                    } else {
-                        content = content.push(self.view_stopped_container(container));
-                    }
-                    content = content.push(widget::divider::horizontal::light());
-                }
+    fn view_image_details(&self) -> Element<'_, Message> {
+        let header = widget::row()
+            .push(
+                widget::button::icon(widget::icon::from_name("go-previous-symbolic"))
+                    .on_press(Message::BackFromImageDetails),
+            )
+            .push(text::title4(self.image_display_name(&self.image_details_ref)))
+            .align_y(Alignment::Center)
+            .spacing(8)
+            .padding(8);
+
+        let mut col = widget::column().spacing(8).padding([0, 12]);
+        if let Some(e) = &self.image_history_error {
+            col = col.push(text::caption(e));
+        } else if let Some((layers, total_size)) = &self.image_history {
+            let layer_bytes: i64 = layers.iter().map(|l| l.size).sum();
+            let sized_layers = layers.iter().filter(|l| l.size > 0).count();
+            col = col.push(text::body(fl!("size-breakdown")));
+            col = col.push(text::caption(fl!(
+                "image-size-summary",
+                total = self.format_size(*total_size as f64),
+                layers = sized_layers.to_string(),
+                steps = layers.len().to_string()
+            )));
+            col = col.push(widget::divider::horizontal::light());
+            col = col.push(text::body(fl!("layers")));
+            for layer in layers {
+                let share = if layer_bytes > 0 {
+                    layer.size as f64 / layer_bytes as f64 * 100.0
+                } else {
+                    0.0
+                };
+                col = col.push(
+                    widget::column()
+                        .push(text::monotext(&layer.created_by).size(11))
+                        .push(text::caption(format!(
+                            "{} ({:.0}%)  ·  {}",
+                            self.format_size(layer.size as f64),
+                            share,
+                            format_age(layer.created)
+                        )))
+                        .spacing(2),
+                );
             }
+        } else {
+            col = col.push(text::body(fl!("loading")));
         }
 
-        // Render ungrouped containers
-        if has_groups && !ungrouped.is_empty() {
-            let other_header = widget::row()
-                .push(text::caption(fl!("other-containers")))
-                .padding([4, 8]);
-            content = content.push(other_header);
-            content = content.push(widget::divider::horizontal::light());
-        }
+        widget::column()
+            .push(header)
+            .push(widget::divider::horizontal::light())
+            .push(scrollable(col).height(400))
+            .spacing(4)
+            .width(Length::Fill)
+            .into()
+    }
 
-        // Running containers (ungrouped)
-        let running: Vec<&ContainerInfo> = ungrouped
+    /// Tagged name for an image ID, or the reference itself.
+    fn image_display_name(&self, reference: &str) -> String {
+        self.images
             .iter()
-            .filter(|c| c.state == ContainerState::Running)
-            .copied()
-            .collect();
+            .find(|i| i.id == reference)
+            .map(|i| i.display_name())
+            .unwrap_or_else(|| reference.to_string())
+    }
 
-        for container in &running {
-            content = content.push(self.view_running_container(container));
-            content = content.push(widget::divider::horizontal::light());
+    fn view_settings(&self) -> Element<'_, Message> {
+        let header = widget::row()
+            .push(
+                widget::button::icon(widget::icon::from_name("go-previous-symbolic"))
+                    .on_press(Message::BackToList),
+            )
+            .push(text::title4(fl!("settings")))
+            .align_y(Alignment::Center)
+            .spacing(8)
+            .padding(8);
+
+        let confirmation_settings = [
+            (
+                ActionCategory::Normal,
+                self.config.confirm_normal,
+                fl!("confirm-normal"),
+            ),
+            (
+                ActionCategory::Destructive,
+                self.config.confirm_destructive,
+                fl!("confirm-destructive"),
+            ),
+            (
+                ActionCategory::Dangerous,
+                self.config.confirm_dangerous,
+                fl!("confirm-dangerous"),
+            ),
+        ];
+
+        let mut col = widget::column()
+            .push(widget::settings::item(
+                fl!("show-panel-stats"),
+                widget::toggler(self.config.show_panel_stats)
+                    .on_toggle(Message::TogglePanelStats),
+            ))
+            .push(widget::divider::horizontal::light())
+            .push(widget::settings::item(
+                fl!("decimal-units"),
+                widget::toggler(self.config.decimal_units)
+                    .on_toggle(Message::ToggleDecimalUnits),
+            ))
+            .push(widget::divider::horizontal::light())
+            .push(widget::settings::item(
+                fl!("memory-as-percent"),
+                widget::toggler(self.config.memory_as_percent)
+                    .on_toggle(Message::ToggleMemoryAsPercent),
+            ))
+            .push(widget::divider::horizontal::light())
+            .push(widget::settings::item(
+                fl!("normalize-cpu"),
+                widget::toggler(self.config.normalize_cpu)
+                    .on_toggle(Message::ToggleNormalizeCpu),
+            ))
+            .push(widget::divider::horizontal::light())
+            .push(widget::settings::item(
+                fl!("log-buffer-lines"),
+                widget::dropdown(
+                    self.log_buffer_options.as_slice(),
+                    LOG_BUFFER_SIZES
+                        .iter()
+                        .position(|size| *size == self.config.log_buffer_lines),
+                    Message::SetLogBufferSize,
+                ),
+            ))
+            .push(widget::divider::horizontal::light())
+            .push(widget::settings::item(
+                fl!("strip-log-colors"),
+                widget::toggler(self.config.strip_log_colors)
+                    .on_toggle(Message::ToggleStripLogColors),
+            ))
+            .push(widget::divider::horizontal::light())
+            .push(widget::settings::item(
+                fl!("terminal-command"),
+                widget::text_input("cosmic-term -e", &self.config.terminal_command)
+                    .on_input(Message::TerminalCommandInput)
+                    .width(Length::Fixed(160.0)),
+            ))
+            .push(widget::divider::horizontal::light())
+            .push(widget::settings::item(
+                fl!("check-image-updates"),
+                widget::toggler(self.config.check_image_updates)
+                    .on_toggle(Message::ToggleImageUpdateChecks),
+            ))
+            .push(widget::settings::item(
+                fl!("notify-image-updates"),
+                widget::toggler(self.config.notify_image_updates)
+                    .on_toggle_maybe(
+                        self.config
+                            .check_image_updates
+                            .then_some(Message::ToggleImageUpdateNotifications),
+                    ),
+            ))
+            .push(widget::divider::horizontal::light())
+            .push(widget::settings::item(
+                fl!("notify-container-starts"),
+                widget::toggler(self.config.notify_container_starts)
+                    .on_toggle(Message::ToggleStartNotifications),
+            ))
+            .push(widget::settings::item(
+                fl!("notify-recovery"),
+                widget::toggler(self.config.notify_recovery)
+                    .on_toggle(Message::ToggleRecoveryNotifications),
+            ))
+            .push(widget::divider::horizontal::light())
+            .push(widget::settings::item(
+                fl!("quiet-hours"),
+                widget::toggler(self.config.quiet_hours).on_toggle(Message::ToggleQuietHours),
+            ))
+            .push(widget::settings::item(
+                fl!("quiet-hours-window"),
+                widget::row()
+                    .push(
+                        widget::text_input("22:00", &self.config.quiet_hours_start)
+                            .on_input(Message::QuietHoursStartInput)
+                            .width(Length::Fixed(64.0)),
+                    )
+                    .push(text::body("–"))
+                    .push(
+                        widget::text_input("07:00", &self.config.quiet_hours_end)
+                            .on_input(Message::QuietHoursEndInput)
+                            .width(Length::Fixed(64.0)),
+                    )
+                    .align_y(Alignment::Center)
+                    .spacing(4),
+            ))
+            .push_maybe(
+                (self.config.quiet_hours && quiet_window(&self.config).is_none())
+                    .then(|| text::caption(fl!("quiet-hours-invalid"))),
+            )
+            .push(widget::divider::horizontal::light())
+            .push(widget::settings::item(
+                fl!("redact-secrets"),
+                widget::toggler(self.config.redact_secrets).on_toggle(Message::ToggleRedaction),
+            ))
+            .spacing(8)
+            .padding([0, 12]);
+
+        col = col.push(widget::divider::horizontal::light());
+        col = col.push(text::body(fl!("confirmations")));
+        for (category, current, label) in confirmation_settings {
+            let selected = Confirmation::ALL.iter().position(|c| *c == current);
+            col = col.push(widget::settings::item(
+                label,
+                widget::dropdown(self.confirmation_options.as_slice(), selected, move |index| {
+                    Message::SetConfirmation(category, index)
+                }),
+            ));
         }
+        col = col.push(widget::divider::horizontal::light());
 
-        // Stopped containers (ungrouped)
-        let stopped: Vec<&ContainerInfo> = ungrouped
-            .iter()
-            .filter(|c| c.state != ContainerState::Running)
-            .copied()
-            .collect();
+        col = col.push(text::body(fl!("pinned-projects")));
+        if self.config.pinned_groups.is_empty() {
+            col = col.push(text::caption(fl!("pinned-projects-empty")));
+        }
+        let last_pinned = self.config.pinned_groups.len().saturating_sub(1);
+        for (index, group_name) in self.config.pinned_groups.iter().enumerate() {
+            col = col.push(
+                widget::row()
+                    .push(text::caption(group_name).width(Length::Fill))
+                    .push(
+                        widget::button::icon(widget::icon::from_name("go-up-symbolic"))
+                            .extra_small()
+                            .tooltip(fl!("move-up"))
+                            .on_press_maybe(
+                                (index > 0).then(|| Message::MovePinnedGroup(index, index - 1)),
+                            ),
+                    )
+                    .push(
+                        widget::button::icon(widget::icon::from_name("go-down-symbolic"))
+                            .extra_small()
+                            .tooltip(fl!("move-down"))
+                            .on_press_maybe(
+                                (index < last_pinned)
+                                    .then(|| Message::MovePinnedGroup(index, index + 1)),
+                            ),
+                    )
+                    .push(
+                        widget::button::icon(widget::icon::from_name("edit-delete-symbolic"))
+                            .extra_small()
+                            .tooltip(fl!("unpin"))
+                            .on_press(Message::TogglePinGroup(group_name.clone())),
+                    )
+                    .align_y(Alignment::Center)
+                    .spacing(4),
+            );
+        }
+        col = col.push(widget::divider::horizontal::light());
 
-        if !stopped.is_empty() {
-            if !has_groups {
-                let stopped_header = widget::row()
-                    .push(text::caption(format!(
-                        "{} ({})",
-                        fl!("stopped"),
-                        stopped.len()
-                    )))
-                    .padding([4, 8]);
-                content = content.push(stopped_header);
-                content = content.push(widget::divider::horizontal::light());
-            }
+        col = col.push(text::body(fl!("autostart")));
+        if self.config.autostart_containers.is_empty() && self.config.autostart_groups.is_empty() {
+            col = col.push(text::caption(fl!("autostart-empty")));
+        }
+        for name in &self.config.autostart_containers {
+            col = col.push(
+                widget::row()
+                    .push(text::caption(name).width(Length::Fill))
+                    .push(
+                        widget::button::icon(widget::icon::from_name("edit-delete-symbolic"))
+                            .extra_small()
+                            .tooltip(fl!("delete"))
+                            .on_press(Message::ToggleAutostartContainer(name.clone(), false)),
+                    )
+                    .align_y(Alignment::Center)
+                    .spacing(4),
+            );
+        }
+        for group_name in &self.config.autostart_groups {
+            col = col.push(
+                widget::row()
+                    .push(
+                        text::caption(fl!("autostart-group", name = group_name.as_str()))
+                            .width(Length::Fill),
+                    )
+                    .push(
+                        widget::button::icon(widget::icon::from_name("edit-delete-symbolic"))
+                            .extra_small()
+                            .tooltip(fl!("delete"))
+                            .on_press(Message::ToggleAutostartGroup(group_name.clone())),
+                    )
+                    .align_y(Alignment::Center)
+                    .spacing(4),
+            );
+        }
+        col = col.push(widget::divider::horizontal::light());
 
-            for container in &stopped {
-                content = content.push(self.view_stopped_container(container));
-                content = content.push(widget::divider::horizontal::light());
+        col = col.push(text::body(fl!("registries")));
+        for (index, entry) in self.config.registries.iter().enumerate() {
+            col = col.push(
+                widget::row()
+                    .push(
+                        text::caption(format!("{} · {}", entry.server, entry.username))
+                            .width(Length::Fill),
+                    )
+                    .push(
+                        widget::button::icon(widget::icon::from_name("edit-delete-symbolic"))
+                            .extra_small()
+                            .tooltip(fl!("delete"))
+                            .on_press(Message::RemoveRegistry(index)),
+                    )
+                    .align_y(Alignment::Center)
+                    .spacing(4),
+            );
+        }
+        col = col
+            .push(
+                widget::text_input(fl!("registry-server"), &self.registry_server)
+                    .on_input(Message::RegistryServerInput),
+            )
+            .push(
+                widget::text_input(fl!("registry-username"), &self.registry_username)
+                    .on_input(Message::RegistryUsernameInput),
+            )
+            .push(
+                widget::secure_input(fl!("registry-password"), &self.registry_password, None, true)
+                    .on_input(Message::RegistryPasswordInput),
+            )
+            .push(
+                widget::button::text(fl!("add-registry"))
+                    .on_press(Message::AddRegistry)
+                    .class(cosmic::theme::Button::Standard),
+            );
+        if let Some(status) = &self.registry_status {
+            col = col.push(text::caption(status));
+        }
+        col = col.push(widget::divider::horizontal::light());
+
+        if self.config.redact_secrets {
+            col = col.push(text::caption(fl!("redaction-patterns")));
+            for (index, pattern) in self.config.redaction_patterns.iter().enumerate() {
+                col = col.push(
+                    widget::row()
+                        .push(text::monotext(pattern).size(11).width(Length::Fill))
+                        .push(
+                            widget::button::icon(widget::icon::from_name("edit-delete-symbolic"))
+                                .extra_small()
+                                .tooltip(fl!("delete"))
+                                .on_press(Message::RemoveRedactionPattern(index)),
+                        )
+                        .align_y(Alignment::Center)
+                        .spacing(4),
+                );
             }
+            col = col.push(
+                widget::row()
+                    .push(
+                        widget::text_input(
+                            fl!("redaction-pattern-placeholder"),
+                            &self.new_redaction_pattern,
+                        )
+                        .on_input(Message::RedactionPatternInput)
+                        .width(Length::Fill),
+                    )
+                    .push(
+                        widget::button::icon(widget::icon::from_name("list-add-symbolic"))
+                            .extra_small()
+                            .tooltip(fl!("add"))
+                            .on_press(Message::AddRedactionPattern),
+                    )
+                    .align_y(Alignment::Center)
+                    .spacing(4),
+            );
+            col = col.push(
+                widget::button::text(fl!("reset-defaults"))
+                    .on_press(Message::ResetRedactionPatterns)
+                    .class(cosmic::theme::Button::Standard),
+            );
         }
 
-        scrollable(content).height(Length::Shrink).into()
+        widget::column()
+            .push(header)
+            .push(widget::divider::horizontal::light())
+            .push(scrollable(col).height(Length::Shrink))
+            .spacing(4)
+            .width(Length::Fill)
+            .into()
     }
 
-    fn view_running_container<'a>(&'a self, container: &'a ContainerInfo) -> Element<'a, Message> {
-        let is_pending = self.pending_ops.contains(&container.id);
+    fn pending_for(&self, container_id: &str) -> Option<&PendingAction> {
+        self.pending_action
+            .as_ref()
+            .filter(|p| p.target_id.as_deref() == Some(container_id))
+    }
 
-        let stats_text = if let Some(stats) = self.stats.get(&container.id) {
-            format!(
-                "CPU {:.1}%  ·  MEM {}",
-                stats.cpu_percent,
-                format_memory(stats.memory_usage_mb)
-            )
-        } else {
-            "CPU --  ·  MEM --".to_string()
-        };
+    /// Container id (if any) and display name an action applies to
+    /// Selected containers that are not stopped, which a bulk delete has to force
+    fn selected_running(&self) -> usize {
+        self.containers
+            .iter()
+            .filter(|c| self.selected.contains(&c.id) && c.state != ContainerState::Stopped)
+            .count()
+    }
 
-        // Health indicator
-        let health_icon = self.health_icon(container);
+    fn action_target(&self, message: &Message) -> (Option<String>, String) {
+        let container_name = |id: &String| {
+            self.containers
+                .iter()
+                .find(|c| &c.id == id)
+                .map(|c| c.name.clone())
+                .unwrap_or_else(|| id.clone())
+        };
+        match message {
+            Message::StartContainer(id)
+            | Message::StopContainer(id)
+            | Message::RestartContainer(id)
+            | Message::DeleteContainer(id, _)
+            | Message::RecreateContainer(id) => (Some(id.clone()), container_name(id)),
+            Message::StartGroup(name)
+            | Message::PauseGroup(name)
+            | Message::UnpauseGroup(name)
+            | Message::StopGroup(name)
+            | Message::RunCompose(name, _)
+            | Message::RemoveOrphans(name) => (None, name.clone()),
+            Message::KillProcess(pid) => (None, format!("PID {}", pid)),
+            Message::RestartUnhealthy => (None, fl!("unhealthy-containers")),
+            Message::RemoveImage(id) => (
+                None,
+                self.images
+                    .iter()
+                    .find(|i| &i.id == id)
+                    .map(|i| i.display_name())
+                    .unwrap_or_else(|| id.clone()),
+            ),
+            Message::PruneImages => (None, fl!("dangling-images")),
+            Message::RemoveVolume(name) => (None, name.clone()),
+            Message::DisconnectNetwork(network) => (None, network.clone()),
+            Message::FreeUpSpace => (None, fl!("unused-data")),
+            Message::RemoveNetwork(id) => (
+                None,
+                self.networks
+                    .iter()
+                    .find(|n| &n.id == id)
+                    .map(|n| n.name.clone())
+                    .unwrap_or_else(|| id.clone()),
+            ),
+            Message::PruneVolumes(names) => (
+                None,
+                fl!("unused-volumes", count = names.len().to_string()),
+            ),
+            Message::RunBulkAction(_) => (
+                None,
+                fl!("selected-count", count = self.selected.len().to_string()),
+            ),
+            _ => (None, fl!("all-containers")),
+        }
+    }
 
-        // Port mappings text
-        let ports_text = format_ports(&container.ports);
+    /// Confirmation for actions that have no container row to confirm in,
+    /// such as group, bulk and process actions
+    fn view_pending_banner(&self) -> Option<Element<'_, Message>> {
+        self.pending_action
+            .as_ref()
+            .filter(|p| p.target_id.is_none())
+            .map(|pending| self.view_confirmation(pending))
+    }
 
-        // First public port for browser button
-        let first_public_port = container
-            .ports
-            .iter()
-            .find_map(|p| p.public_port);
+    fn view_confirmation<'a>(&'a self, pending: &'a PendingAction) -> Element<'a, Message> {
+        let mut row = widget::row()
+            .push(text::caption(fl!(
+                "confirm-action",
+                action = action_label(&pending.message),
+                name = pending.target_name.as_str()
+            )))
+            .spacing(4)
+            .align_y(Alignment::Center);
 
-        // Row 1: health + name + action buttons
-        let actions: Element<Message> = if is_pending {
-            text::caption(fl!("loading")).into()
-        } else {
-            let mut row = widget::row().spacing(4).align_y(Alignment::Center);
+        if matches!(pending.message, Message::RunBulkAction(BulkAction::Delete))
+            && pending.category == ActionCategory::Dangerous
+        {
+            row = row.push(text::caption(fl!(
+                "bulk-delete-running",
+                count = self.selected_running().to_string()
+            )));
+        }
 
+        if pending.confirmation == Confirmation::TypeName {
             row = row.push(
-                widget::button::icon(widget::icon::from_name(
-                    "media-playback-stop-symbolic",
-                ))
-                .extra_small()
-                .tooltip(fl!("stop"))
-                .on_press(Message::StopContainer(container.id.clone())),
+                widget::text_input(
+                    fl!("type-to-confirm", name = pending.target_name.as_str()),
+                    &pending.typed,
+                )
+                .on_input(Message::ConfirmInputChanged)
+                .width(Length::Fixed(120.0)),
             );
+        }
 
-            row = row.push(
-                widget::button::icon(widget::icon::from_name("view-refresh-symbolic"))
-                    .extra_small()
-                    .tooltip(fl!("restart"))
-                    .on_press(Message::RestartContainer(container.id.clone())),
+        let confirm_class = if pending.category == ActionCategory::Normal {
+            cosmic::theme::Button::Suggested
+        } else {
+            cosmic::theme::Button::Destructive
+        };
+
+        let row = row
+            .push(
+                widget::button::text(fl!("confirm-yes"))
+                    .on_press_maybe(pending.is_confirmed().then_some(Message::ConfirmAction))
+                    .class(confirm_class),
+            )
+            .push(
+                widget::button::text(fl!("confirm-no"))
+                    .on_press(Message::CancelAction)
+                    .class(cosmic::theme::Button::Standard),
             );
 
-            if let Some(port) = first_public_port {
-                row = row.push(
-                    widget::button::icon(widget::icon::from_name("web-browser-symbolic"))
-                        .extra_small()
-                        .tooltip(fl!("open-browser"))
-                        .on_press(Message::OpenInBrowser(port)),
-                );
+        if let Message::PruneVolumes(names) = &pending.message {
+            return widget::column()
+                .push(row)
+                .push(text::caption(names.join(", ")))
+                .spacing(4)
+                .into();
+        }
+
+        let Message::DeleteContainer(_, flags) = pending.message else {
+            return row.into();
+        };
+        let options = widget::row()
+            .push(
+                widget::checkbox(fl!("force-remove"), flags.force).on_toggle(move |force| {
+                    Message::SetRemoveFlags(RemoveFlags { force, ..flags })
+                }),
+            )
+            .push(
+                widget::checkbox(fl!("remove-volumes"), flags.volumes).on_toggle(move |volumes| {
+                    Message::SetRemoveFlags(RemoveFlags { volumes, ..flags })
+                }),
+            )
+            .spacing(8);
+        widget::column().push(row).push(options).spacing(4).into()
+    }
+
+    fn save_config(&self) {
+        if let Some(handler) = &self.config_handler {
+            if let Err(e) = self.config.write_entry(handler) {
+                tracing::error!("Failed to save config: {}", e);
             }
+        }
+    }
 
-            row = row.push(
-                widget::button::icon(widget::icon::from_name("edit-copy-symbolic"))
+    fn view_rename_input(&self, draft: &str) -> Element<'_, Message> {
+        widget::row()
+            .push(
+                widget::text_input(fl!("new-name"), draft)
+                    .on_input(Message::RenameInput)
+                    .on_submit(|_| Message::SubmitRename)
+                    .width(Length::Fill),
+            )
+            .push(
+                widget::button::icon(widget::icon::from_name("object-select-symbolic"))
                     .extra_small()
-                    .tooltip(fl!("copy-id"))
-                    .on_press(Message::CopyContainerId(container.id.clone())),
-            );
-
-            row = row.push(
-                widget::button::icon(widget::icon::from_name("dialog-information-symbolic"))
+                    .tooltip(fl!("rename"))
+                    .on_press(Message::SubmitRename),
+            )
+            .push(
+                widget::button::icon(widget::icon::from_name("window-close-symbolic"))
                     .extra_small()
-                    .tooltip(fl!("details"))
-                    .on_press(Message::ShowDetails(
-                        container.id.clone(),
-                        container.name.clone(),
-                    )),
-            );
+                    .tooltip(fl!("cancel"))
+                    .on_press(Message::CancelRename),
+            )
+            .align_y(Alignment::Center)
+            .spacing(4)
+            .width(Length::Fill)
+            .into()
+    }
 
-            row = row.push(
-                widget::button::icon(widget::icon::from_name(
-                    "utilities-terminal-symbolic",
-                ))
-                .extra_small()
-                .tooltip(fl!("logs"))
-                .on_press(Message::ShowLogs(
-                    container.id.clone(),
-                    container.name.clone(),
-                )),
-            );
+    fn rename_button<'a>(&self, id: &str, name: &str) -> Element<'a, Message> {
+        widget::button::icon(widget::icon::from_name("document-edit-symbolic"))
+            .extra_small()
+            .tooltip(fl!("rename"))
+            .on_press(Message::StartRename(id.to_string(), name.to_string()))
+            .into()
+    }
 
-            row.into()
-        };
+    fn view_container_name<'a>(&'a self, container: &'a ContainerInfo) -> Element<'a, Message> {
+        match &self.renaming {
+            Some((id, draft)) if *id == container.id => self.view_rename_input(draft),
+            _ => widget::row()
+                .push(match self.service_label(container) {
+                    Some(label) => Element::from(widget::tooltip(
+                        text::body(label),
+                        text::caption(&container.name),
+                        widget::tooltip::Position::Bottom,
+                    )),
+                    None => text::body(&container.name).into(),
+                })
+                .push_maybe(self.is_orphan(container).then(|| {
+                    widget::tooltip(
+                        widget::icon::from_name("dialog-warning-symbolic").size(14),
+                        text::caption(fl!("orphan-tooltip")),
+                        widget::tooltip::Position::Bottom,
+                    )
+                }))
+                .push(self.rename_button(&container.id, &container.name))
+                .align_y(Alignment::Center)
+                .spacing(2)
+                .width(Length::Fill)
+                .into(),
+        }
+    }
 
-        let mut name_row = widget::row()
-            .align_y(Alignment::Center)
-            .spacing(4);
+    /// Whether the container's service is no longer declared in its compose files
+    fn is_orphan(&self, container: &ContainerInfo) -> bool {
+        let Some(service) = container.compose_service() else {
+            return false;
+        };
+        container
+            .labels
+            .get("com.docker.compose.project")
+            .and_then(|project| self.compose_services.get(project))
+            .and_then(Option::as_ref)
+            .is_some_and(|services| !services.contains(service))
+    }
 
-        if let Some(icon) = health_icon {
-            name_row = name_row.push(icon);
+    /// "service" or "service #2" for compose containers, numbered only when scaled
+    fn service_label(&self, container: &ContainerInfo) -> Option<String> {
+        let project = container.labels.get("com.docker.compose.project")?;
+        let service = container.compose_service()?;
+        let scaled = self
+            .containers
+            .iter()
+            .filter(|c| c.labels.get("com.docker.compose.project") == Some(project))
+            .filter(|c| c.compose_service() == Some(service))
+            .nth(1)
+            .is_some();
+        match container.compose_replica() {
+            Some(replica) if scaled => Some(format!("{service} #{replica}")),
+            _ => Some(service.to_string()),
         }
+    }
 
-        name_row = name_row
-            .push(text::body(&container.name).width(Length::Fill))
-            .push(actions);
+    fn view_service_line<'a>(
+        &self,
+        key: String,
+        service: &str,
+        replicas: &[&ContainerInfo],
+        expanded: bool,
+    ) -> Element<'a, Message> {
+        let running = replicas
+            .iter()
+            .filter(|c| c.state == ContainerState::Running)
+            .count();
+        let arrow_icon = if expanded {
+            "go-down-symbolic"
+        } else {
+            "go-next-symbolic"
+        };
 
-        let mut col = widget::column()
-            .push(name_row)
-            .push(text::caption(&container.image))
-            .spacing(2)
-            .padding(8)
-            .width(Length::Fill);
+        widget::row()
+            .push(
+                widget::button::icon(widget::icon::from_name(arrow_icon))
+                    .extra_small()
+                    .on_press(Message::ToggleService(key)),
+            )
+            .push(text::body(fl!(
+                "service-replicas",
+                name = service,
+                total = replicas.len().to_string(),
+                running = running.to_string()
+            )))
+            .align_y(Alignment::Center)
+            .spacing(4)
+            .padding([4, 8])
+            .into()
+    }
 
-        if !ports_text.is_empty() {
-            col = col.push(text::caption(ports_text));
+    fn view_details_title(&self) -> Element<'_, Message> {
+        match &self.renaming {
+            Some((id, draft)) if *id == self.details_container_id => {
+                self.view_rename_input(draft)
+            }
+            _ => widget::row()
+                .push(text::title4(&self.details_container_name))
+                .push(self.rename_button(&self.details_container_id, &self.details_container_name))
+                .align_y(Alignment::Center)
+                .spacing(4)
+                .into(),
         }
+    }
 
-        col = col.push(text::caption(stats_text));
-
-        // Uptime / status
-        col = col.push(text::caption(&container.status));
+    fn view_image_line<'a>(&self, container: &'a ContainerInfo) -> Element<'a, Message> {
+        let caption = match self.image_sizes.get(&container.image_id) {
+            Some(size) => format!("{} ({})", container.image, self.format_size(*size as f64)),
+            None => container.image.clone(),
+        };
+        let mut row = widget::row()
+            .push(text::caption(caption))
+            .align_y(Alignment::Center)
+            .spacing(4);
+        if self.superseded.contains(&container.id) {
+            row = row.push(widget::tooltip(
+                widget::button::text(fl!("outdated"))
+                    .leading_icon(widget::icon::from_name("view-refresh-symbolic"))
+                    .class(cosmic::theme::Button::Link)
+                    .on_press_maybe(
+                        (!self.pending_ops.contains(&container.id))
+                            .then(|| guarded(Message::RecreateContainer(container.id.clone()))),
+                    ),
+                text::caption(fl!("recreate-tooltip")),
+                widget::tooltip::Position::Top,
+            ));
+        }
+        if self.outdated_images.contains(&container.image) {
+            row = row.push(widget::tooltip(
+                widget::icon::from_name("software-update-available-symbolic").size(12),
+                text::caption(fl!("image-update-available")),
+                widget::tooltip::Position::Top,
+            ));
+        }
+        if let Some((size, growth)) = self.layer_warning(&container.id) {
+            let tooltip = if growth > LAYER_GROWTH {
+                fl!(
+                    "writable-layer-growth",
+                    growth = self.format_size(growth as f64),
+                    size = self.format_size(size as f64)
+                )
+            } else {
+                fl!("writable-layer-size", size = self.format_size(size as f64))
+            };
+            row = row.push(widget::tooltip(
+                widget::icon::from_name("drive-harddisk-symbolic").size(12),
+                text::caption(tooltip),
+                widget::tooltip::Position::Top,
+            ));
+        }
+        row.into()
+    }
 
-        col.into()
+    /// Latest writable layer size and its growth over the last `LAYER_GROWTH_WINDOW`,
+    /// when either is large enough to flag
+    fn layer_warning(&self, id: &str) -> Option<(i64, i64)> {
+        let samples = self.layer_sizes.get(id)?;
+        let (_, size) = *samples.back()?;
+        let (_, baseline) = *samples.front()?;
+        let growth = size - baseline;
+        (size > LARGE_WRITABLE_LAYER || growth > LAYER_GROWTH).then_some((size, growth))
     }
 
-    fn view_stopped_container<'a>(
-        &'a self,
-        container: &'a ContainerInfo,
-    ) -> Element<'a, Message> {
-        let is_pending = self.pending_ops.contains(&container.id);
+    fn error_badge<'a>(&self, container: &ContainerInfo) -> Option<Element<'a, Message>> {
+        let count = self.error_counts.get(&container.id)?;
+        Some(
+            widget::button::custom(
+                widget::row()
+                    .push(widget::icon::from_name("dialog-error-symbolic").size(12))
+                    .push(text::caption(count.to_string()))
+                    .align_y(Alignment::Center)
+                    .spacing(2),
+            )
+            .class(cosmic::theme::Button::Destructive)
+            .padding([0, 4])
+            .on_press(Message::ShowLogs(container.id.clone(), container.name.clone()))
+            .into(),
+        )
+    }
 
-        let health_icon = self.health_icon(container);
-        let ports_text = format_ports(&container.ports);
+    /// Shows a desktop notification, or keeps it for the popup during quiet hours
+    fn notify(&mut self, summary: String, body: String, icon: &str) {
+        if self.in_quiet_hours() {
+            self.hold_notification(summary, body);
+            return;
+        }
+        let _ = notify_rust::Notification::new()
+            .summary(&summary)
+            .body(&body)
+            .icon(icon)
+            .show();
+    }
 
-        // Check if this container has a pending delete confirmation
-        let confirming_delete = self
-            .confirm_delete
-            .as_ref()
-            .map(|id| id == &container.id)
-            .unwrap_or(false);
+    fn hold_notification(&mut self, summary: String, body: String) {
+        if self.held_notifications.len() == HELD_NOTIFICATIONS {
+            self.held_notifications.pop_front();
+        }
+        self.held_notifications.push_back(HeldNotification {
+            time: chrono::Local::now(),
+            summary,
+            body,
+        });
+    }
 
-        // Row 1: name + action buttons
-        let actions: Element<Message> = if is_pending {
-            text::caption(fl!("loading")).into()
-        } else if confirming_delete {
-            widget::row()
-                .push(text::caption(fl!(
-                    "confirm-delete",
-                    name = container.name.as_str()
-                )))
-                .push(
-                    widget::button::text(fl!("confirm-yes"))
-                        .on_press(Message::ConfirmDelete(container.id.clone()))
-                        .class(cosmic::theme::Button::Destructive),
-                )
-                .push(
-                    widget::button::text(fl!("confirm-no"))
-                        .on_press(Message::CancelDelete)
-                        .class(cosmic::theme::Button::Standard),
-                )
-                .spacing(4)
-                .align_y(Alignment::Center)
-                .into()
-        } else {
-            widget::row()
-                .push(
-                    widget::button::icon(widget::icon::from_name(
-                        "media-playback-start-symbolic",
-                    ))
-                    .extra_small()
-                    .tooltip(fl!("start"))
-                    .on_press(Message::StartContainer(container.id.clone())),
-                )
-                .push(
-                    widget::button::icon(widget::icon::from_name("user-trash-symbolic"))
-                        .extra_small()
-                        .tooltip(fl!("delete"))
-                        .on_press(Message::DeleteContainer(container.id.clone())),
-                )
-                .push(
-                    widget::button::icon(widget::icon::from_name("edit-copy-symbolic"))
-                        .extra_small()
-                        .tooltip(fl!("copy-id"))
-                        .on_press(Message::CopyContainerId(container.id.clone())),
-                )
-                .push(
-                    widget::button::icon(widget::icon::from_name(
-                        "dialog-information-symbolic",
-                    ))
-                    .extra_small()
-                    .tooltip(fl!("details"))
-                    .on_press(Message::ShowDetails(
-                        container.id.clone(),
-                        container.name.clone(),
-                    )),
-                )
-                .push(
-                    widget::button::icon(widget::icon::from_name(
-                        "utilities-terminal-symbolic",
-                    ))
-                    .extra_small()
-                    .tooltip(fl!("logs"))
-                    .on_press(Message::ShowLogs(
-                        container.id.clone(),
-                        container.name.clone(),
-                    )),
-                )
-                .spacing(4)
-                .align_y(Alignment::Center)
-                .into()
+    fn in_quiet_hours(&self) -> bool {
+        if !self.config.quiet_hours {
+            return false;
+        }
+        let Some((start, end)) = quiet_window(&self.config) else {
+            return false;
         };
+        let now = chrono::Local::now().time();
+        if start <= end {
+            start <= now && now < end
+        } else {
+            // The window spans midnight
+            now >= start || now < end
+        }
+    }
 
-        let mut name_row = widget::row()
+    fn view_held_notifications(&self) -> Option<Element<'_, Message>> {
+        if self.held_notifications.is_empty() {
+            return None;
+        }
+        let header = widget::row()
+            .push(
+                text::body(fl!(
+                    "held-notifications",
+                    count = self.held_notifications.len().to_string()
+                ))
+                .width(Length::Fill),
+            )
+            .push(
+                widget::button::icon(widget::icon::from_name("edit-clear-all-symbolic"))
+                    .extra_small()
+                    .tooltip(fl!("clear"))
+                    .on_press(Message::ClearHeldNotifications),
+            )
             .align_y(Alignment::Center)
             .spacing(4);
+        let mut col = widget::column().push(header).spacing(4);
+        for held in self.held_notifications.iter().rev() {
+            col = col.push(text::caption(format!(
+                "{} · {}: {}",
+                held.time.format("%H:%M"),
+                held.summary,
+                held.body
+            )));
+        }
+        Some(
+            widget::container(col)
+                .padding(8)
+                .class(cosmic::theme::Container::Card)
+                .into(),
+        )
+    }
 
-        if let Some(icon) = health_icon {
-            name_row = name_row.push(icon);
+    /// Forgets exits older than the crash loop window and ends loops that went quiet
+    fn prune_deaths(&mut self) {
+        let now = std::time::Instant::now();
+        for deaths in self.deaths.values_mut() {
+            while deaths
+                .front()
+                .is_some_and(|died| now.duration_since(*died) > CRASH_LOOP_WINDOW)
+            {
+                deaths.pop_front();
+            }
         }
+        self.deaths.retain(|_, deaths| !deaths.is_empty());
+        self.crash_looping.retain(|id| self.deaths.contains_key(id));
+    }
+
+    fn crash_loop_badge<'a>(&self, container: &ContainerInfo) -> Option<Element<'a, Message>> {
+        let deaths = self
+            .deaths
+            .get(&container.id)
+            .filter(|_| self.crash_looping.contains(&container.id))?;
+        Some(
+            widget::tooltip(
+                widget::icon::from_name("view-refresh-symbolic").size(12),
+                text::caption(fl!(
+                    "crash-looping",
+                    count = deaths.len().to_string(),
+                    minutes = crash_loop_minutes()
+                )),
+                widget::tooltip::Position::Top,
+            )
+            .into(),
+        )
+    }
 
-        name_row = name_row
-            .push(text::body(&container.name).width(Length::Fill))
-            .push(actions);
+    fn security_badge<'a>(&self, container: &ContainerInfo) -> Option<Element<'a, Message>> {
+        let risks = self
+            .security_risks
+            .get(&container.id)
+            .filter(|risks| !risks.is_empty())?;
+        let summary: Vec<String> = risks.iter().map(security_risk_label).collect();
+        Some(
+            widget::tooltip(
+                widget::icon::from_name("security-low-symbolic").size(12),
+                text::caption(summary.join("\n")),
+                widget::tooltip::Position::Top,
+            )
+            .into(),
+        )
+    }
 
-        let mut col = widget::column()
-            .push(name_row)
-            .push(text::caption(&container.image))
-            .spacing(2)
-            .padding(8)
-            .width(Length::Fill);
+    fn fetch_log_range(&self, since: i64, until: i64) -> Task<Action<Message>> {
+        let id = self.log_container_id.clone();
+        let timestamps = self.log_window.timestamps;
+        cosmic::task::future(async move {
+            let logs = docker::fetch_logs_between(id.clone(), since, until, timestamps).await;
+            Message::LogRangeLoaded(id, logs)
+        })
+    }
 
-        if !ports_text.is_empty() {
-            col = col.push(text::caption(ports_text));
+    fn view_log_alerts(&self) -> Element<'_, Message> {
+        let mut col = widget::column()
+            .push(text::body(fl!("log-alerts")))
+            .spacing(4);
+        for (index, alert) in self.config.log_alerts.iter().enumerate() {
+            if alert.container != self.details_container_name {
+                continue;
+            }
+            col = col.push(
+                widget::row()
+                    .push(text::monotext(&alert.pattern).width(Length::Fill))
+                    .push(
+                        widget::button::icon(widget::icon::from_name("edit-delete-symbolic"))
+                            .extra_small()
+                            .tooltip(fl!("delete"))
+                            .on_press(Message::RemoveLogAlert(index)),
+                    )
+                    .align_y(Alignment::Center)
+                    .spacing(4),
+            );
+        }
+        col = col.push(
+            widget::row()
+                .push(
+                    widget::text_input(fl!("alert-pattern"), &self.new_alert_pattern)
+                        .on_input(Message::AlertPatternInput)
+                        .on_submit(|_| Message::AddLogAlert)
+                        .width(Length::Fill),
+                )
+                .push(
+                    widget::button::icon(widget::icon::from_name("list-add-symbolic"))
+                        .extra_small()
+                        .tooltip(fl!("add-alert"))
+                        .on_press(Message::AddLogAlert),
+                )
+                .align_y(Alignment::Center)
+                .spacing(4),
+        );
+        if let Some(error) = &self.alert_pattern_error {
+            col = col.push(text::caption(error));
         }
-
-        // Status
-        col = col.push(text::caption(&container.status));
-
         col.into()
     }
 
-    fn view_logs(&self) -> Element<'_, Message> {
-        let header = widget::row()
-            .push(
-                widget::button::icon(widget::icon::from_name("go-previous-symbolic"))
-                    .on_press(Message::BackToList),
-            )
-            .push(text::title4(&self.log_container_name))
-            .align_y(Alignment::Center)
-            .spacing(8)
-            .padding(8);
-
-        let log_body: Element<Message> = if self.logs_loading && self.log_content.is_empty() {
-            widget::container(text::body(fl!("loading")))
-                .padding(16)
-                .center_x(Length::Fill)
-                .into()
-        } else {
-            let log_text = if self.log_content.is_empty() {
-                "(no output)".to_string()
-            } else {
-                self.log_content.clone()
-            };
-            scrollable(text::monotext(log_text).width(Length::Fill))
-                .height(400)
-                .into()
-        };
+    fn log_stream_shown(&self, stream: LogStream) -> bool {
+        match stream {
+            LogStream::Stdout => self.log_show_stdout,
+            LogStream::Stderr => self.log_show_stderr,
+        }
+    }
 
-        widget::column()
-            .push(header)
-            .push(widget::divider::horizontal::light())
-            .push(log_body)
-            .spacing(4)
-            .width(Length::Fill)
-            .into()
+    /// Loaded log output from the streams currently shown
+    fn visible_log(&self) -> String {
+        let mut log = String::new();
+        for (_, line) in self
+            .log_lines
+            .iter()
+            .filter(|(stream, _)| self.log_stream_shown(*stream))
+        {
+            log.push_str(line);
+            log.push('\n');
+        }
+        log
     }
 
-    fn view_details(&self) -> Element<'_, Message> {
-        let header = widget::row()
-            .push(
-                widget::button::icon(widget::icon::from_name("go-previous-symbolic"))
-                    .on_press(Message::BackToList),
-            )
-            .push(text::title4(&self.details_container_name))
-            .align_y(Alignment::Center)
-            .spacing(8)
-            .padding(8);
+    /// Splits a log frame into lines and drops the oldest beyond the buffer size
+    fn push_log_chunk(&mut self, stream: LogStream, chunk: &str) {
+        for line in chunk.lines() {
+            if self.log_line_matches(stream, line) {
+                self.log_matches.push(self.log_lines.len());
+            }
+            self.log_lines.push_back((stream, line.to_string()));
+        }
+        let overflow = self
+            .log_lines
+            .len()
+            .saturating_sub(self.config.log_buffer_lines.max(1));
+        self.drop_oldest_log_lines(overflow);
+    }
 
-        let body: Element<Message> = if self.details_loading {
-            widget::container(text::body(fl!("loading")))
-                .padding(16)
-                .center_x(Length::Fill)
-                .into()
-        } else if let Some(details) = &self.details_data {
-            let mut col = widget::column().spacing(8).padding([0, 12]);
+    /// Empties the log pane ahead of a fresh stream
+    fn clear_log_lines(&mut self) {
+        self.log_lines.clear();
+        self.log_matches.clear();
+        self.log_stream_error = None;
+    }
 
-            // Ports section - find the container to get its ports
-            let container_ports: Vec<&PortMapping> = self
-                .containers
-                .iter()
-                .find(|c| c.name == self.details_container_name)
-                .map(|c| c.ports.iter().collect())
-                .unwrap_or_default();
+    /// Drops `count` lines from the front and shifts the cached matches to suit
+    fn drop_oldest_log_lines(&mut self, count: usize) {
+        if count == 0 {
+            return;
+        }
+        self.log_lines.drain(..count);
+        self.log_matches.retain(|index| *index >= count);
+        for index in &mut self.log_matches {
+            *index -= count;
+        }
+    }
 
-            col = col.push(text::body(fl!("ports")));
-            if container_ports.is_empty() {
-                col = col.push(text::caption(fl!("no-data")));
-            } else {
-                for port in &container_ports {
-                    let port_str = if let Some(pub_port) = port.public_port {
-                        format!("{}:{}/{}", pub_port, port.private_port, port.protocol)
-                    } else {
-                        format!("{}/{}", port.private_port, port.protocol)
-                    };
-                    col = col.push(text::caption(port_str));
-                }
+    /// Whether a log line passes the stream toggles and the filter, as the view shows it
+    fn log_line_matches(&self, stream: LogStream, line: &str) -> bool {
+        self.log_stream_shown(stream)
+            && match &self.log_filter_matcher {
+                Some(matcher) => matcher.is_match(&ansi::strip(&self.redactor.redact(line))),
+                None => true,
             }
+    }
 
-            col = col.push(widget::divider::horizontal::light());
+    /// Rebuilds the cached matches after the filter, stream toggles or redactor change
+    fn refresh_log_matches(&mut self) {
+        self.log_matches = self
+            .log_lines
+            .iter()
+            .enumerate()
+            .filter(|(_, (stream, line))| self.log_line_matches(*stream, line))
+            .map(|(index, _)| index)
+            .collect();
+    }
 
-            // Volumes section
-            col = col.push(text::body(fl!("volumes")));
-            if details.volumes.is_empty() {
-                col = col.push(text::caption(fl!("no-data")));
-            } else {
-                for (src, dst) in &details.volumes {
-                    col = col.push(text::caption(format!("{} → {}", src, dst)));
-                }
-            }
+    /// Log text with its ANSI colors rendered, or stripped when the user prefers plain text
+    /// Error and warning lines are tinted wherever the log itself sets no color.
+    /// Without `wrap` long lines stay whole and need a horizontally scrolling parent.
+    fn view_log_text<'a>(&self, log: &str, wrap: bool) -> Element<'a, Message> {
+        let (wrapping, width) = if wrap {
+            (Wrapping::Word, Length::Fill)
+        } else {
+            (Wrapping::None, Length::Shrink)
+        };
+        let plain = ansi::strip(log);
+        let highlights: Vec<Option<Color>> = plain
+            .split('\n')
+            .map(|line| docker::log_severity(line).map(severity_color))
+            .collect();
+        if highlights.iter().all(Option::is_none)
+            && (self.config.strip_log_colors || !log.contains('\x1b'))
+        {
+            return text::monotext(plain.into_owned())
+                .wrapping(wrapping)
+                .width(width)
+                .into();
+        }
 
-            col = col.push(widget::divider::horizontal::light());
+        let runs = if self.config.strip_log_colors {
+            vec![ansi::StyledRun {
+                text: plain.into_owned(),
+                color: None,
+                bold: false,
+            }]
+        } else {
+            ansi::parse(log)
+        };
 
-            // Networks section
-            col = col.push(text::body(fl!("networks")));
-            if details.networks.is_empty() {
-                col = col.push(text::caption(fl!("no-data")));
+        // Runs can span lines, so split them where the highlight changes
+        let mut spans: Vec<Span<'a>> = Vec::new();
+        let mut line = 0;
+        for run in runs {
+            let font = if run.bold {
+                Font {
+                    weight: Weight::Bold,
+                    ..cosmic::font::mono()
+                }
             } else {
-                for (name, ip) in &details.networks {
-                    let net_text = if ip.is_empty() {
-                        name.clone()
-                    } else {
-                        format!("{} ({})", name, ip)
-                    };
-                    col = col.push(text::caption(net_text));
+                cosmic::font::mono()
+            };
+            for piece in run.text.split_inclusive('\n') {
+                let highlight = highlights.get(line).copied().flatten();
+                spans.push(
+                    span(piece.to_string())
+                        .color_maybe(run.color.or(highlight))
+                        .font(font),
+                );
+                if piece.ends_with('\n') {
+                    line += 1;
                 }
             }
+        }
+        rich_text(spans).wrapping(wrapping).width(width).into()
+    }
 
-            col = col.push(widget::divider::horizontal::light());
-
-            // Environment Variables section
-            col = col.push(text::body(fl!("environment")));
-            if details.env_vars.is_empty() {
-                col = col.push(text::caption(fl!("no-data")));
+    fn update_log_filter(&mut self) {
+        self.log_filter_matcher = None;
+        self.log_filter_error = None;
+        if !self.log_filter.is_empty() {
+            let pattern = if self.log_filter_regex {
+                self.log_filter.clone()
             } else {
-                for var in &details.env_vars {
-                    col = col.push(text::caption(var));
-                }
+                regex::escape(&self.log_filter)
+            };
+            match regex::RegexBuilder::new(&pattern)
+                .case_insensitive(!self.log_filter_regex)
+                .build()
+            {
+                Ok(matcher) => self.log_filter_matcher = Some(matcher),
+                Err(e) => self.log_filter_error = Some(e.to_string()),
             }
+        }
+        self.refresh_log_matches();
+    }
 
-            scrollable(col).height(400).into()
+    fn project_status(&self, containers: &[&ContainerInfo]) -> ProjectStatus {
+        let failing = containers.iter().any(|c| {
+            c.state == ContainerState::Restarting
+                || self.crash_looping.contains(&c.id)
+                || self.health.get(&c.id) == Some(&HealthStatus::Unhealthy)
+        });
+        let running = containers
+            .iter()
+            .filter(|c| c.state == ContainerState::Running)
+            .count();
+        if failing {
+            ProjectStatus::Failing
+        } else if running == containers.len() {
+            ProjectStatus::Up
+        } else if running == 0 {
+            ProjectStatus::Stopped
         } else {
-            widget::container(text::body(fl!("no-data")))
-                .padding(16)
-                .center_x(Length::Fill)
-                .into()
-        };
+            ProjectStatus::Partial
+        }
+    }
 
-        widget::column()
-            .push(header)
-            .push(widget::divider::horizontal::light())
-            .push(body)
-            .spacing(4)
-            .width(Length::Fill)
-            .into()
+    fn view_project_status<'a>(&self, containers: &[&ContainerInfo]) -> Element<'a, Message> {
+        let (color, label) = match self.project_status(containers) {
+            ProjectStatus::Up => (Color::from_rgb(0.30, 0.75, 0.40), fl!("project-up")),
+            ProjectStatus::Partial => (Color::from_rgb(0.95, 0.75, 0.20), fl!("project-partial")),
+            ProjectStatus::Failing => (Color::from_rgb(0.90, 0.30, 0.30), fl!("project-failing")),
+            ProjectStatus::Stopped => (Color::from_rgb(0.55, 0.55, 0.55), fl!("project-stopped")),
+        };
+        widget::tooltip(
+            text::body("●").class(cosmic::theme::Text::Color(color)),
+            text::caption(label),
+            widget::tooltip::Position::Bottom,
+        )
+        .into()
     }
 
     fn health_icon<'a>(&self, container: &ContainerInfo) -> Option<Element<'a, Message>> {
@@ -1127,6 +7826,286 @@ impl DockerApplet {
     }
 }
 
+/// A one-line bar chart of `values`, scaled to their peak so small changes stay visible
+fn sparkline<'a>(values: impl Iterator<Item = f64> + Clone, label: String) -> Element<'a, Message> {
+    // An empty history has no peak to report
+    let peak = values.clone().fold(f64::MIN, f64::max).max(0.0);
+    // Near-idle histories are scaled against 1 so noise doesn't fill the bars
+    let scale = peak.max(1.0);
+    let bars: String = values
+        .map(|value| {
+            let level = (value / scale * (SPARK_BARS.len() - 1) as f64).round() as usize;
+            SPARK_BARS[level.min(SPARK_BARS.len() - 1)]
+        })
+        .collect();
+    widget::tooltip(
+        text::monotext(bars).size(10),
+        text::caption(fl!("sparkline-peak", label = label, peak = format!("{:.1}", peak))),
+        widget::tooltip::Position::Bottom,
+    )
+    .into()
+}
+
+/// Green while there is headroom, red once the container nears its memory limit
+fn memory_bar_class(percent: f32) -> cosmic::theme::ProgressBar {
+    match percent {
+        p if p >= 90.0 => cosmic::theme::ProgressBar::Danger,
+        p if p >= 70.0 => cosmic::theme::ProgressBar::Primary,
+        _ => cosmic::theme::ProgressBar::Success,
+    }
+}
+
+fn severity_color(severity: LogSeverity) -> Color {
+    match severity {
+        LogSeverity::Error => Color::from_rgb(0.90, 0.35, 0.35),
+        LogSeverity::Warning => Color::from_rgb(0.95, 0.70, 0.25),
+    }
+}
+
+/// Stable color for a service so its lines are recognizable across refreshes
+fn service_color(service: &str) -> (f32, f32, f32) {
+    let hash = service
+        .bytes()
+        .fold(0usize, |hash, byte| hash.wrapping_mul(31).wrapping_add(byte as usize));
+    SERVICE_COLORS[hash % SERVICE_COLORS.len()]
+}
+
+fn security_risk_label(risk: &SecurityRisk) -> String {
+    match risk {
+        SecurityRisk::Privileged => fl!("risk-privileged"),
+        SecurityRisk::Capabilities(caps) => fl!("risk-capabilities", caps = caps.join(", ")),
+        SecurityRisk::HostNetwork => fl!("risk-host-network"),
+        SecurityRisk::HostPid => fl!("risk-host-pid"),
+        SecurityRisk::RootUser => fl!("risk-root-user"),
+    }
+}
+
+/// Describes a device request the way `--gpus` spells it, e.g. "nvidia: all (gpu)"
+fn device_request_label(request: &DeviceRequestInfo) -> String {
+    let devices = if !request.device_ids.is_empty() {
+        request.device_ids.join(", ")
+    } else if request.count < 0 {
+        fl!("all-devices")
+    } else {
+        request.count.to_string()
+    };
+    let driver = if request.driver.is_empty() {
+        fl!("default-driver")
+    } else {
+        request.driver.clone()
+    };
+    if request.capabilities.is_empty() {
+        format!("{}: {}", driver, devices)
+    } else {
+        format!("{}: {} ({})", driver, devices, request.capabilities.join(", "))
+    }
+}
+
+fn copy_button<'a>(value: String) -> Element<'a, Message> {
+    widget::button::icon(widget::icon::from_name("edit-copy-symbolic"))
+        .extra_small()
+        .tooltip(fl!("copy"))
+        .on_press(Message::CopyText(value))
+        .into()
+}
+
+/// A caption line with a button that copies its text
+fn copyable_row<'a>(value: String) -> Element<'a, Message> {
+    widget::row()
+        .push(text::caption(value.clone()).width(Length::Fill))
+        .push(copy_button(value))
+        .align_y(Alignment::Center)
+        .spacing(4)
+        .into()
+}
+
+/// Parses the configured quiet hours, `None` if either end isn't a valid HH:MM time
+fn quiet_window(config: &Config) -> Option<(chrono::NaiveTime, chrono::NaiveTime)> {
+    let parse = |time: &str| chrono::NaiveTime::parse_from_str(time.trim(), "%H:%M").ok();
+    Some((
+        parse(&config.quiet_hours_start)?,
+        parse(&config.quiet_hours_end)?,
+    ))
+}
+
+/// Tells the user a container stopped on its own, offering to start it again or
+/// open its logs. The notification server is waited on from a separate thread until
+/// a button is clicked or the notification expires or is closed; the returned task
+/// always finishes with `StoppedNotificationAction`.
+fn stopped_notification(id: String, name: String, body: String) -> Task<Action<Message>> {
+    let (sender, receiver) = futures::channel::oneshot::channel();
+    std::thread::spawn(move || {
+        let handle = notify_rust::Notification::new()
+            .summary("Docker")
+            .body(&body)
+            .icon("dialog-warning-symbolic")
+            .action("restart", &fl!("restart"))
+            .action("logs", &fl!("show-logs"))
+            .timeout(notify_rust::Timeout::Milliseconds(
+                NOTIFICATION_ACTION_TIMEOUT.as_millis() as u32,
+            ))
+            .show();
+        if let Ok(handle) = handle {
+            handle.wait_for_action(|action| {
+                let _ = sender.send(action.to_string());
+            });
+        }
+    });
+    cosmic::task::future(async move {
+        let action = receiver.await.unwrap_or_default();
+        Message::StoppedNotificationAction(id, name, action)
+    })
+}
+
+/// `CRASH_LOOP_WINDOW` in whole minutes, for the crash loop messages
+fn crash_loop_minutes() -> String {
+    (CRASH_LOOP_WINDOW.as_secs() / 60).to_string()
+}
+
+/// Wraps an action so it goes through the confirmation policy first
+fn guarded(message: Message) -> Message {
+    Message::RequestAction(Box::new(message))
+}
+
+fn action_category(message: &Message) -> ActionCategory {
+    match message {
+        Message::DeleteContainer(_, flags) if flags.force || flags.volumes => {
+            ActionCategory::Dangerous
+        }
+        Message::DeleteContainer(..)
+        | Message::RecreateContainer(_)
+        | Message::KillProcess(_)
+        | Message::RemoveImage(_)
+        | Message::PruneImages
+        | Message::RemoveNetwork(_)
+        | Message::FreeUpSpace
+        | Message::RunCompose(_, ComposeAction::Down)
+        | Message::RemoveOrphans(_)
+        | Message::RunBulkAction(BulkAction::Delete) => ActionCategory::Destructive,
+        Message::RemoveVolume(_) | Message::PruneVolumes(_) => ActionCategory::Dangerous,
+        _ => ActionCategory::Normal,
+    }
+}
+
+fn action_label(message: &Message) -> String {
+    match message {
+        Message::StartContainer(_) => fl!("start"),
+        Message::StopContainer(_) => fl!("stop"),
+        Message::RestartContainer(_) => fl!("restart"),
+        Message::DeleteContainer(..) => fl!("delete"),
+        Message::RecreateContainer(_) => fl!("recreate"),
+        Message::KillProcess(_) => fl!("kill-process"),
+        Message::StartAll | Message::StartGroup(_) => fl!("start-all"),
+        Message::PauseGroup(_) => fl!("pause-all"),
+        Message::UnpauseGroup(_) => fl!("unpause-all"),
+        Message::StopAll | Message::StopGroup(_) => fl!("stop-all"),
+        Message::RestartUnhealthy => fl!("restart-unhealthy"),
+        Message::RemoveImage(_) => fl!("delete"),
+        Message::PruneImages => fl!("prune"),
+        Message::RemoveVolume(_) | Message::RemoveNetwork(_) => fl!("delete"),
+        Message::DisconnectNetwork(_) => fl!("disconnect"),
+        Message::RunCompose(_, ComposeAction::Up) => fl!("compose-up"),
+        Message::RunCompose(_, ComposeAction::Down) => fl!("compose-down"),
+        Message::RunCompose(_, ComposeAction::Update) => fl!("update-stack"),
+        Message::RemoveOrphans(_) => fl!("remove-orphans"),
+        Message::FreeUpSpace => fl!("prune"),
+        Message::PruneVolumes(_) => fl!("prune"),
+        Message::RunBulkAction(action) => match action {
+            BulkAction::Start => fl!("start"),
+            BulkAction::Stop => fl!("stop"),
+            BulkAction::Restart => fl!("restart"),
+            BulkAction::Delete => fl!("delete"),
+        },
+        _ => String::new(),
+    }
+}
+
+fn confirmation_label(confirmation: Confirmation) -> String {
+    match confirmation {
+        Confirmation::Never => fl!("confirmation-never"),
+        Confirmation::Ask => fl!("confirmation-ask"),
+        Confirmation::TypeName => fl!("confirmation-type-name"),
+    }
+}
+
+fn log_tail_label(tail: Option<usize>) -> String {
+    match tail {
+        Some(lines) => fl!("log-tail-lines", lines = lines.to_string()),
+        None => fl!("log-tail-all"),
+    }
+}
+
+fn log_since_label(since: LogSince) -> String {
+    match since {
+        LogSince::Last(secs) if secs < 3600 => {
+            fl!("log-since-minutes", minutes = (secs / 60).to_string())
+        }
+        LogSince::Last(secs) => fl!("log-since-hours", hours = (secs / 3600).to_string()),
+        LogSince::LastStart => fl!("log-since-start"),
+        LogSince::All => fl!("log-since-all"),
+    }
+}
+
+fn restart_policy_label(policy: RestartPolicy) -> String {
+    match policy {
+        RestartPolicy::No => fl!("restart-no"),
+        RestartPolicy::OnFailure => fl!("restart-on-failure"),
+        RestartPolicy::UnlessStopped => fl!("restart-unless-stopped"),
+        RestartPolicy::Always => fl!("restart-always"),
+    }
+}
+
+fn copy_to_clipboard(text: &str) {
+    use std::io::Write;
+
+    let child = std::process::Command::new("wl-copy")
+        .stdin(std::process::Stdio::piped())
+        .spawn();
+    if let Ok(mut child) = child {
+        if let Some(mut stdin) = child.stdin.take() {
+            let _ = stdin.write_all(text.as_bytes());
+        }
+    }
+}
+
+/// Spawns the configured terminal emulator with `command` appended.
+fn launch_in_terminal(terminal: &str, command: &[String]) -> Result<(), String> {
+    let mut parts = terminal.split_whitespace();
+    let program = parts
+        .next()
+        .ok_or_else(|| "No terminal command configured".to_string())?;
+    std::process::Command::new(program)
+        .args(parts)
+        .args(command)
+        .spawn()
+        .map(|_| ())
+        .map_err(|e| format!("Failed to run {}: {}", program, e))
+}
+
+fn build_redactor(config: &Config) -> Redactor {
+    if config.redact_secrets {
+        Redactor::new(&config.redaction_patterns)
+    } else {
+        Redactor::default()
+    }
+}
+
+/// Joins a directory inside a container with one of its entries
+fn join_path(dir: &str, name: &str) -> String {
+    if dir.ends_with('/') {
+        format!("{}{}", dir, name)
+    } else {
+        format!("{}/{}", dir, name)
+    }
+}
+
+fn parent_path(path: &str) -> String {
+    match path.trim_end_matches('/').rsplit_once('/') {
+        Some(("", _)) | None => String::from("/"),
+        Some((parent, _)) => parent.to_string(),
+    }
+}
+
 fn format_ports(ports: &[PortMapping]) -> String {
     let mappings: Vec<String> = ports
         .iter()
@@ -1144,10 +8123,88 @@ fn format_ports(ports: &[PortMapping]) -> String {
     }
 }
 
-fn format_memory(mb: f64) -> String {
-    if mb >= 1024.0 {
-        format!("{:.1}G", mb / 1024.0)
+/// Formats a byte count in decimal (kB, MB, …) or binary (KiB, MiB, …) units
+fn format_size(bytes: f64, decimal: bool) -> String {
+    let (base, units) = if decimal {
+        (1000.0, ["B", "kB", "MB", "GB", "TB"])
+    } else {
+        (1024.0, ["B", "KiB", "MiB", "GiB", "TiB"])
+    };
+    let mut value = bytes;
+    let mut unit = 0;
+    while value >= base && unit < units.len() - 1 {
+        value /= base;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{:.0} {}", value, units[unit])
+    } else {
+        format!("{:.1} {}", value, units[unit])
+    }
+}
+
+/// Reads a local date and time as typed in the log range inputs
+fn parse_local_time(input: &str) -> Option<i64> {
+    let time = chrono::NaiveDateTime::parse_from_str(input.trim(), LOG_RANGE_FORMAT).ok()?;
+    let time = time.and_local_timezone(chrono::Local).earliest()?;
+    Some(time.timestamp())
+}
+
+/// Rewrites the RFC 3339 time the daemon puts at the start of a log line
+/// into a fixed-width column, in local time or UTC.
+fn format_log_timestamp(line: &str, local: bool) -> String {
+    let parsed = line.split_once(' ').and_then(|(stamp, rest)| {
+        let time = chrono::DateTime::parse_from_rfc3339(stamp).ok()?;
+        Some((time, rest))
+    });
+    match parsed {
+        Some((time, rest)) => {
+            let time = if local {
+                time.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M:%S%.3f")
+            } else {
+                time.with_timezone(&chrono::Utc).format("%Y-%m-%d %H:%M:%S%.3fZ")
+            };
+            format!("{}  {}", time, rest)
+        }
+        None => line.to_string(),
+    }
+}
+
+/// How long ago a unix timestamp was, e.g. "3d ago".
+fn format_age(timestamp: i64) -> String {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0);
+    let secs = (now - timestamp).max(0);
+    let age = match secs {
+        s if s < 3600 => format!("{}m", s / 60),
+        s if s < 86_400 => format!("{}h", s / 3600),
+        s if s < 86_400 * 365 => format!("{}d", s / 86_400),
+        s => format!("{}y", s / (86_400 * 365)),
+    };
+    fl!("ago", age = age)
+}
+
+/// Time since `started` (Unix seconds) in the largest two units that apply
+fn format_uptime(started: i64) -> String {
+    let secs = (chrono::Utc::now().timestamp() - started).max(0);
+    let (days, hours, minutes) = (secs / 86_400, secs / 3600 % 24, secs / 60 % 60);
+    if days > 0 {
+        fl!("uptime-days", days = days.to_string(), hours = hours.to_string())
+    } else if hours > 0 {
+        fl!("uptime-hours", hours = hours.to_string(), minutes = minutes.to_string())
+    } else if minutes > 0 {
+        fl!("uptime-minutes", minutes = minutes.to_string())
     } else {
-        format!("{:.0}M", mb)
+        fl!("uptime-seconds")
+    }
+}
+
+fn format_duration(duration: std::time::Duration) -> String {
+    match duration.as_secs() {
+        0 => format!("{}ms", duration.as_millis()),
+        s if s < 60 || s % 60 != 0 => format!("{}s", s),
+        s => format!("{}m", s / 60),
     }
 }
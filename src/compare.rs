@@ -0,0 +1,104 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+/// The parts of a container's configuration worth comparing side by side.
+#[derive(Debug, Clone, Default)]
+pub struct ComparableConfig {
+    pub name: String,
+    pub image: BTreeMap<String, String>,
+    pub env: BTreeMap<String, String>,
+    pub labels: BTreeMap<String, String>,
+    /// Mount destination → source
+    pub mounts: BTreeMap<String, String>,
+    /// Container port → host binding
+    pub ports: BTreeMap<String, String>,
+}
+
+/// A key whose value differs between the two containers. `None` means the
+/// key is missing on that side.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiffEntry {
+    pub key: String,
+    pub left: Option<String>,
+    pub right: Option<String>,
+}
+
+pub fn diff_maps(
+    left: &BTreeMap<String, String>,
+    right: &BTreeMap<String, String>,
+) -> Vec<DiffEntry> {
+    let keys: BTreeSet<&String> = left.keys().chain(right.keys()).collect();
+    keys.into_iter()
+        .filter_map(|key| {
+            let l = left.get(key);
+            let r = right.get(key);
+            (l != r).then(|| DiffEntry {
+                key: key.clone(),
+                left: l.cloned(),
+                right: r.cloned(),
+            })
+        })
+        .collect()
+}
+
+/// Splits `KEY=VALUE` environment entries into a map.
+pub fn env_map(env: &[String]) -> BTreeMap<String, String> {
+    env.iter()
+        .map(|var| match var.split_once('=') {
+            Some((key, value)) => (key.to_string(), value.to_string()),
+            None => (var.clone(), String::new()),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn map(entries: &[(&str, &str)]) -> BTreeMap<String, String> {
+        entries
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn diff_lists_changed_and_missing_keys_in_order() {
+        let left = map(&[("A", "1"), ("B", "2"), ("C", "3")]);
+        let right = map(&[("A", "1"), ("B", "20"), ("D", "4")]);
+        assert_eq!(
+            diff_maps(&left, &right),
+            vec![
+                DiffEntry {
+                    key: "B".into(),
+                    left: Some("2".into()),
+                    right: Some("20".into()),
+                },
+                DiffEntry {
+                    key: "C".into(),
+                    left: Some("3".into()),
+                    right: None,
+                },
+                DiffEntry {
+                    key: "D".into(),
+                    left: None,
+                    right: Some("4".into()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn identical_maps_have_no_diff() {
+        let both = map(&[("A", "1")]);
+        assert!(diff_maps(&both, &both).is_empty());
+    }
+
+    #[test]
+    fn env_splits_on_the_first_equals() {
+        let env = vec!["URL=a=b".to_string(), "EMPTY=".to_string(), "FLAG".to_string()];
+        assert_eq!(
+            env_map(&env),
+            map(&[("URL", "a=b"), ("EMPTY", ""), ("FLAG", "")])
+        );
+    }
+}
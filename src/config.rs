@@ -1 +1,118 @@
+use crate::policy::Confirmation;
+use crate::redact;
+use crate::registry::RegistryEntry;
+use cosmic::cosmic_config::{self, cosmic_config_derive::CosmicConfigEntry, CosmicConfigEntry};
+use serde::{Deserialize, Serialize};
+
 pub const APP_ID: &str = "com.example.CosmicAppletDocker";
+
+/// A regex that raises a notification when a container logs a matching line
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LogAlert {
+    /// Container name, so the alert survives the container being recreated
+    pub container: String,
+    pub pattern: String,
+}
+
+#[derive(Debug, Clone, PartialEq, CosmicConfigEntry)]
+#[version = 1]
+pub struct Config {
+    pub show_panel_stats: bool,
+    /// Show memory in MB/GB (powers of 1000) instead of MiB/GiB
+    pub decimal_units: bool,
+    /// Show container memory as a percentage of its limit
+    pub memory_as_percent: bool,
+    /// Scale CPU so 100% is the whole host rather than one core
+    pub normalize_cpu: bool,
+    pub redact_secrets: bool,
+    /// Show log output as plain text instead of rendering ANSI colors
+    pub strip_log_colors: bool,
+    /// Log lines kept in memory for the log view; older lines are dropped
+    pub log_buffer_lines: usize,
+    pub redaction_patterns: Vec<String>,
+    pub confirm_normal: Confirmation,
+    pub confirm_destructive: Confirmation,
+    pub confirm_dangerous: Confirmation,
+    pub registries: Vec<RegistryEntry>,
+    /// Container names whose logs are tailed in the background to count errors
+    pub error_watch: Vec<String>,
+    pub log_alerts: Vec<LogAlert>,
+    /// Container names started when the applet launches with the session
+    pub autostart_containers: Vec<String>,
+    /// Compose projects started when the applet launches with the session
+    pub autostart_groups: Vec<String>,
+    /// Compose projects listed above the others, in this order
+    pub pinned_groups: Vec<String>,
+    /// Terminal emulator prefix the shell command is appended to, e.g. `cosmic-term -e`
+    pub terminal_command: String,
+    /// Periodically compare local image digests with their registry
+    pub check_image_updates: bool,
+    pub notify_image_updates: bool,
+    /// Notify when a container is started by something other than the applet
+    pub notify_container_starts: bool,
+    /// Notify when a container reported unhealthy passes its health check again
+    pub notify_recovery: bool,
+    /// Keep notifications for the popup instead of showing them between these times
+    pub quiet_hours: bool,
+    /// Quiet hours as HH:MM; the window may span midnight
+    pub quiet_hours_start: String,
+    pub quiet_hours_end: String,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            show_panel_stats: false,
+            decimal_units: false,
+            memory_as_percent: false,
+            normalize_cpu: false,
+            redact_secrets: true,
+            strip_log_colors: false,
+            log_buffer_lines: 5000,
+            redaction_patterns: redact::DEFAULT_PATTERNS
+                .iter()
+                .map(|p| p.to_string())
+                .collect(),
+            confirm_normal: Confirmation::Never,
+            confirm_destructive: Confirmation::Ask,
+            confirm_dangerous: Confirmation::TypeName,
+            registries: Vec::new(),
+            error_watch: Vec::new(),
+            log_alerts: Vec::new(),
+            autostart_containers: Vec::new(),
+            autostart_groups: Vec::new(),
+            pinned_groups: Vec::new(),
+            terminal_command: "cosmic-term -e".to_string(),
+            check_image_updates: false,
+            notify_image_updates: true,
+            notify_container_starts: false,
+            notify_recovery: false,
+            quiet_hours: false,
+            quiet_hours_start: "22:00".to_string(),
+            quiet_hours_end: "07:00".to_string(),
+        }
+    }
+}
+
+impl Config {
+    pub fn load() -> (Option<cosmic_config::Config>, Self) {
+        match cosmic_config::Config::new(APP_ID, Self::VERSION) {
+            Ok(handler) => {
+                let config = match Self::get_entry(&handler) {
+                    Ok(config) => config,
+                    Err((errors, config)) => {
+                        for error in errors {
+                            tracing::error!("Failed to load config: {}", error);
+                        }
+                        config
+                    }
+                };
+                (Some(handler), config)
+            }
+            Err(e) => {
+                tracing::error!("Failed to open config: {}", e);
+                (None, Self::default())
+            }
+        }
+    }
+}
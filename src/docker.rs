@@ -1,15 +1,40 @@
+use crate::compare::{self, ComparableConfig};
+use crate::registry::{self, RegistryEntry};
+use bollard::auth::DockerCredentials;
 use bollard::container::{
-    InspectContainerOptions, ListContainersOptions, LogsOptions, RemoveContainerOptions,
-    RestartContainerOptions, StartContainerOptions, Stats, StatsOptions, StopContainerOptions,
+    CPUStats, Config, CreateContainerOptions, DownloadFromContainerOptions, InspectContainerOptions,
+    ListContainersOptions, LogOutput, LogsOptions, NetworkingConfig, PruneContainersOptions,
+    RemoveContainerOptions, RenameContainerOptions, RestartContainerOptions, StartContainerOptions,
+    Stats, StatsOptions, StopContainerOptions, TopOptions, UpdateContainerOptions,
+    UploadToContainerOptions,
+};
+use bollard::exec::{CreateExecOptions, StartExecResults};
+use bollard::image::{
+    CreateImageOptions, ListImagesOptions, PruneImagesOptions, PushImageOptions,
+    RemoveImageOptions, TagImageOptions,
+};
+use bollard::models::{
+    BuildPruneResponse, ContainerInspectResponse, EndpointSettings, EventMessageTypeEnum,
+    HealthStatusEnum, MountPointTypeEnum, PortTypeEnum, RestartPolicyNameEnum,
+};
+use bollard::network::{
+    ConnectNetworkOptions, DisconnectNetworkOptions, ListNetworksOptions, PruneNetworksOptions,
 };
-use bollard::models::{EventMessageTypeEnum, HealthStatusEnum, PortTypeEnum};
 use bollard::system::EventsOptions;
+use bollard::volume::{CreateVolumeOptions, ListVolumesOptions};
 use bollard::Docker;
 use cosmic::iced::Subscription;
 use cosmic::iced_futures::stream;
-use futures::{SinkExt, StreamExt};
-use std::collections::HashMap;
-use std::time::Duration;
+use futures::channel::mpsc;
+use futures::{SinkExt, Stream, StreamExt};
+use std::collections::{HashMap, HashSet};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
+
+/// Socket of the elevated relay, when the user opted into it
+static SOCKET_OVERRIDE: RwLock<Option<PathBuf>> = RwLock::new(None);
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum ContainerState {
@@ -38,8 +63,359 @@ pub enum HealthStatus {
 #[derive(Debug, Clone)]
 pub struct ContainerDetails {
     pub env_vars: Vec<String>,
-    pub volumes: Vec<(String, String)>,
-    pub networks: Vec<(String, String)>,
+    pub volumes: Vec<MountInfo>,
+    pub networks: Vec<NetworkAttachment>,
+    /// Full inspect document, pretty-printed
+    pub raw_json: String,
+    /// Memory limit in bytes, 0 when unlimited
+    pub memory_limit: i64,
+    /// Total memory available to the daemon, in bytes
+    pub host_memory: i64,
+    /// Memory plus swap in bytes; 0 when unset, -1 when unlimited
+    pub memory_swap: i64,
+    /// CPU limit in cores, 0 when unlimited
+    pub cpu_limit: f64,
+    /// Relative CPU weight, 0 when left at the default of 1024
+    pub cpu_shares: i64,
+    /// Maximum processes and threads, 0 when unlimited
+    pub pids_limit: i64,
+    /// CPUs available to the daemon
+    pub host_cpus: f64,
+    pub restart_policy: RestartPolicy,
+    /// Exit code of the last run; `None` while the container is running
+    pub exit_code: Option<i64>,
+    pub restart_count: i64,
+    pub entrypoint: Vec<String>,
+    pub cmd: Vec<String>,
+    pub created: Option<chrono::DateTime<chrono::Utc>>,
+    pub started_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub finished_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// All labels, sorted by key
+    pub labels: Vec<(String, String)>,
+    /// `None` when the image defines no health check or it is disabled
+    pub healthcheck: Option<HealthCheck>,
+    /// Registry digest of the image the container runs, `repo@sha256:…`; `None` for
+    /// images that were built locally and never pushed or pulled
+    pub image_digest: Option<String>,
+    pub image_created: Option<chrono::DateTime<chrono::Utc>>,
+    /// OCI runtime, `runc` unless configured otherwise
+    pub runtime: String,
+    /// Host device paths mapped into the container, with their path inside it
+    pub devices: Vec<(String, String)>,
+    /// Devices requested from a driver, such as GPUs via `--gpus`
+    pub device_requests: Vec<DeviceRequestInfo>,
+    pub security_risks: Vec<SecurityRisk>,
+    pub hostname: String,
+    /// `os/arch[/variant]` of the image, empty if the image is gone
+    pub platform: String,
+    /// The image targets another CPU architecture than the daemon's, so it runs emulated
+    pub emulated: bool,
+}
+
+/// Settings that weaken the isolation between a container and the host
+#[derive(Debug, Clone, PartialEq)]
+pub enum SecurityRisk {
+    Privileged,
+    Capabilities(Vec<String>),
+    HostNetwork,
+    HostPid,
+    /// `--user` explicitly set to root; an unset user is left to the image
+    RootUser,
+}
+
+/// What the container list learns from inspecting a container
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContainerInspection {
+    /// Unix time the container was last started
+    pub started_at: Option<i64>,
+    pub security_risks: Vec<SecurityRisk>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeviceRequestInfo {
+    pub driver: String,
+    /// Number of devices, -1 for all of them; ignored when `device_ids` is set
+    pub count: i64,
+    pub device_ids: Vec<String>,
+    pub capabilities: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HealthCheck {
+    pub command: String,
+    pub interval: Duration,
+    pub timeout: Duration,
+    pub retries: i64,
+    pub start_period: Duration,
+    /// Recent probe runs, newest first
+    pub probes: Vec<HealthProbe>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HealthProbe {
+    pub started_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub exit_code: i64,
+    pub output: String,
+}
+
+/// How a container is attached to one network; empty strings for unset values
+#[derive(Debug, Clone, PartialEq)]
+pub struct NetworkAttachment {
+    pub name: String,
+    pub ip: String,
+    pub gateway: String,
+    pub mac: String,
+    pub aliases: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MountInfo {
+    pub kind: MountKind,
+    /// Volume name for named volumes, empty otherwise
+    pub name: String,
+    pub source: String,
+    pub destination: String,
+    /// Bind mounts and local-driver volumes, whose source is a host folder
+    pub on_host: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MountKind {
+    Bind,
+    Volume,
+    /// tmpfs, npipe and cluster mounts, which have no host path to show
+    Other,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RestartPolicy {
+    No,
+    OnFailure,
+    UnlessStopped,
+    Always,
+}
+
+impl RestartPolicy {
+    pub const ALL: [RestartPolicy; 4] = [
+        RestartPolicy::No,
+        RestartPolicy::OnFailure,
+        RestartPolicy::UnlessStopped,
+        RestartPolicy::Always,
+    ];
+
+    fn from_name(name: Option<RestartPolicyNameEnum>) -> Self {
+        match name {
+            Some(RestartPolicyNameEnum::ON_FAILURE) => RestartPolicy::OnFailure,
+            Some(RestartPolicyNameEnum::UNLESS_STOPPED) => RestartPolicy::UnlessStopped,
+            Some(RestartPolicyNameEnum::ALWAYS) => RestartPolicy::Always,
+            _ => RestartPolicy::No,
+        }
+    }
+
+    fn name(self) -> RestartPolicyNameEnum {
+        match self {
+            RestartPolicy::No => RestartPolicyNameEnum::NO,
+            RestartPolicy::OnFailure => RestartPolicyNameEnum::ON_FAILURE,
+            RestartPolicy::UnlessStopped => RestartPolicyNameEnum::UNLESS_STOPPED,
+            RestartPolicy::Always => RestartPolicyNameEnum::ALWAYS,
+        }
+    }
+}
+
+/// Options offered when deleting a container
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RemoveFlags {
+    /// Kill the container first if it is still running
+    pub force: bool,
+    /// Also remove anonymous volumes attached to the container
+    pub volumes: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct VolumeInfo {
+    pub name: String,
+    pub driver: String,
+    pub mountpoint: String,
+    /// RFC 3339 timestamp, if the driver reports one
+    pub created: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NetworkInfo {
+    pub id: String,
+    pub name: String,
+    pub driver: String,
+    pub scope: String,
+}
+
+impl NetworkInfo {
+    /// The daemon's own networks, which can't be removed
+    pub fn is_predefined(&self) -> bool {
+        matches!(self.name.as_str(), "bridge" | "host" | "none")
+    }
+}
+
+/// Space used by one kind of Docker object, in bytes
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct UsageSummary {
+    pub count: usize,
+    pub size: i64,
+    /// Space that pruning unused objects of this kind would free
+    pub reclaimable: i64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DiskUsage {
+    pub images: UsageSummary,
+    pub containers: UsageSummary,
+    pub volumes: UsageSummary,
+    pub build_cache: UsageSummary,
+    /// Every container's footprint, largest writable layer first
+    pub container_sizes: Vec<ContainerSize>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContainerSize {
+    pub id: String,
+    pub name: String,
+    /// Files the container created or changed on top of its image, in bytes
+    pub size_rw: i64,
+    /// Image plus writable layer, in bytes
+    pub size_root_fs: i64,
+}
+
+/// What a guided prune should remove
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PruneTargets {
+    pub stopped_containers: bool,
+    pub dangling_images: bool,
+    pub unused_networks: bool,
+    pub build_cache: bool,
+}
+
+impl Default for PruneTargets {
+    fn default() -> Self {
+        Self {
+            stopped_containers: true,
+            dangling_images: true,
+            unused_networks: true,
+            build_cache: true,
+        }
+    }
+}
+
+/// Where a Compose project lives, read from its containers' labels
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ComposeProject {
+    pub name: String,
+    pub working_dir: String,
+    pub config_files: Vec<String>,
+}
+
+impl ComposeProject {
+    pub fn from_containers<'a>(
+        name: &str,
+        containers: impl IntoIterator<Item = &'a ContainerInfo>,
+    ) -> Option<Self> {
+        let labels = containers
+            .into_iter()
+            .map(|c| &c.labels)
+            .find(|l| l.get("com.docker.compose.project").map(String::as_str) == Some(name))?;
+        Some(Self {
+            name: name.to_string(),
+            working_dir: labels
+                .get("com.docker.compose.project.working_dir")?
+                .clone(),
+            config_files: labels
+                .get("com.docker.compose.project.config_files")
+                .map(|files| {
+                    files
+                        .split(',')
+                        .filter(|f| !f.is_empty())
+                        .map(str::to_string)
+                        .collect()
+                })
+                .unwrap_or_default(),
+        })
+    }
+}
+
+impl ComposeProject {
+    /// The main compose file, resolved against the project directory
+    pub fn compose_file(&self) -> Option<PathBuf> {
+        let file = self.config_files.first()?;
+        Some(Path::new(&self.working_dir).join(file))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ComposeAction {
+    Up,
+    Down,
+    /// Pull newer images, then recreate what changed
+    Update,
+}
+
+impl ComposeAction {
+    /// `docker compose` subcommands to run, in order
+    fn steps(self) -> Vec<Vec<&'static str>> {
+        match self {
+            ComposeAction::Up => vec![vec!["up", "-d"]],
+            ComposeAction::Down => vec![vec!["down"]],
+            ComposeAction::Update => vec![vec!["pull"], vec!["up", "-d"]],
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImageInfo {
+    pub id: String,
+    pub tags: Vec<String>,
+    /// Size in bytes
+    pub size: i64,
+    /// Unix timestamp
+    pub created: i64,
+    /// Containers using the image, -1 if the daemon did not count them
+    pub containers: i64,
+}
+
+impl ImageInfo {
+    /// First `repo:tag`, or the short ID for untagged images
+    pub fn display_name(&self) -> String {
+        self.tags
+            .iter()
+            .find(|t| t.as_str() != "<none>:<none>")
+            .cloned()
+            .unwrap_or_else(|| {
+                let id = self.id.trim_start_matches("sha256:");
+                id[..id.len().min(12)].to_string()
+            })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImageLayer {
+    /// Dockerfile instruction that produced the layer
+    pub created_by: String,
+    /// Size in bytes, 0 for metadata-only steps like ENV or CMD
+    pub size: i64,
+    /// Unix timestamp
+    pub created: i64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileEntry {
+    pub name: String,
+    pub is_dir: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProcessInfo {
+    /// PID as seen from the host, which is what the top API reports
+    pub pid: String,
+    pub user: String,
+    pub cpu: String,
+    pub command: String,
 }
 
 #[derive(Debug, Clone)]
@@ -47,11 +423,32 @@ pub struct ContainerInfo {
     pub id: String,
     pub name: String,
     pub image: String,
+    pub image_id: String,
     pub state: ContainerState,
     pub status: String,
     pub ports: Vec<PortMapping>,
     pub labels: HashMap<String, String>,
     pub created: Option<i64>,
+    /// Names of the volumes the container mounts
+    pub volumes: Vec<String>,
+    /// Names of the networks the container is attached to
+    pub networks: Vec<String>,
+}
+
+impl ContainerInfo {
+    /// The Compose service this container was created for
+    pub fn compose_service(&self) -> Option<&str> {
+        self.labels
+            .get("com.docker.compose.service")
+            .map(String::as_str)
+    }
+
+    /// Replica index within the service, as compose numbers scaled containers
+    pub fn compose_replica(&self) -> Option<&str> {
+        self.labels
+            .get("com.docker.compose.container-number")
+            .map(String::as_str)
+    }
 }
 
 #[derive(Debug, Clone, Default)]
@@ -60,14 +457,76 @@ pub struct ContainerStats {
     pub memory_usage_mb: f64,
     pub memory_limit_mb: f64,
     pub memory_percent: f64,
+    /// Processes and threads running in the container
+    pub pids: u64,
+    /// CPUs available to the container; `cpu_percent` reaches 100 per CPU
+    pub online_cpus: u32,
+    /// Throughput since the previous sample; `None` until two samples exist
+    pub io_rates: Option<IoRates>,
+}
+
+/// Bytes per second derived from consecutive stats samples
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct IoRates {
+    pub net_rx: f64,
+    pub net_tx: f64,
+    pub block_read: f64,
+    pub block_write: f64,
+}
+
+/// Cumulative byte counters as reported by the stats API
+#[derive(Debug, Clone, Copy, Default)]
+struct IoCounters {
+    net_rx: u64,
+    net_tx: u64,
+    block_read: u64,
+    block_write: u64,
+}
+
+impl IoCounters {
+    fn rates_since(&self, previous: &IoCounters, elapsed: Duration) -> IoRates {
+        let secs = elapsed.as_secs_f64().max(0.001);
+        // Counters reset when a container restarts, so never go negative
+        let rate = |now: u64, before: u64| now.saturating_sub(before) as f64 / secs;
+        IoRates {
+            net_rx: rate(self.net_rx, previous.net_rx),
+            net_tx: rate(self.net_tx, previous.net_tx),
+            block_read: rate(self.block_read, previous.block_read),
+            block_write: rate(self.block_write, previous.block_write),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
 pub enum DockerEvent {
     ContainersUpdated(Result<Vec<ContainerInfo>, String>),
+    DaemonDisconnected,
+    /// The daemon answers again after being unreachable
+    DaemonReconnected,
     StatsUpdated(HashMap<String, ContainerStats>),
     HealthUpdated(HashMap<String, HealthStatus>),
-    LogLine(String, String),
+    LogLine(String, LogStream, String),
+    /// Container ID and why its log stream could not be followed
+    LogStreamFailed(String, String),
+    /// Project name, line prefix and one line of its output
+    ProjectLogLine(String, String, String),
+    ErrorLogged(String),
+    /// Container ID, the alert pattern that matched and the matching line
+    LogAlertMatched(String, String, String),
+    ExecOutput(u64, String),
+    ProcessesUpdated(Result<(String, Vec<ProcessInfo>), String>),
+    /// Image references whose registry digest differs from the local one
+    ImageUpdates(HashSet<String>),
+    /// Containers whose image tag now points at a newer local image
+    SupersededUpdated(HashSet<String>),
+    /// Writable layer size in bytes of every container, by ID
+    LayerSizesUpdated(HashMap<String, i64>),
+    /// Bytes of an image or volume archive written to disk so far
+    ArchiveProgress(u64),
+    ArchiveFinished(Result<PathBuf, String>),
+    ExecFinished(u64, Result<i64, String>),
+    ComposeOutput(u64, String),
+    ComposeFinished(u64, Result<(), String>),
     ContainerLifecycleEvent {
         action: String,
         container_id: String,
@@ -76,6 +535,24 @@ pub enum DockerEvent {
     },
 }
 
+pub fn set_socket_override(path: Option<PathBuf>) {
+    if let Ok(mut socket) = SOCKET_OVERRIDE.write() {
+        *socket = path;
+    }
+}
+
+fn connect() -> Result<Docker, bollard::errors::Error> {
+    let socket = SOCKET_OVERRIDE.read().ok().and_then(|s| s.clone());
+    match socket {
+        Some(path) => Docker::connect_with_unix(
+            &path.to_string_lossy(),
+            120,
+            bollard::API_DEFAULT_VERSION,
+        ),
+        None => Docker::connect_with_local_defaults(),
+    }
+}
+
 fn parse_state(state: &str) -> ContainerState {
     match state {
         "running" => ContainerState::Running,
@@ -103,6 +580,31 @@ fn calculate_cpu_percent(stats: &Stats) -> f64 {
     }
 }
 
+fn calculate_io_counters(stats: &Stats) -> IoCounters {
+    let mut counters = IoCounters::default();
+
+    for network in stats.networks.iter().flat_map(|n| n.values()) {
+        counters.net_rx += network.rx_bytes;
+        counters.net_tx += network.tx_bytes;
+    }
+
+    // cgroup v1 reports "Read"/"Write", v2 reports "read"/"write"
+    for entry in stats
+        .blkio_stats
+        .io_service_bytes_recursive
+        .iter()
+        .flatten()
+    {
+        if entry.op.eq_ignore_ascii_case("read") {
+            counters.block_read += entry.value;
+        } else if entry.op.eq_ignore_ascii_case("write") {
+            counters.block_write += entry.value;
+        }
+    }
+
+    counters
+}
+
 fn calculate_memory(stats: &Stats) -> (f64, f64, f64) {
     let usage = stats.memory_stats.usage.unwrap_or(0) as f64;
     let limit = stats.memory_stats.limit.unwrap_or(1) as f64;
@@ -152,35 +654,165 @@ pub fn container_list_subscription(popup_open: bool) -> Subscription<DockerEvent
     )
 }
 
-pub fn container_stats_subscription(container_ids: Vec<String>) -> Subscription<DockerEvent> {
+pub fn container_stats_subscription(
+    container_ids: Vec<String>,
+    background: bool,
+) -> Subscription<DockerEvent> {
     if container_ids.is_empty() {
         return Subscription::none();
     }
 
+    let interval = if background {
+        Duration::from_secs(15)
+    } else {
+        Duration::from_secs(1)
+    };
+
+    let id = if background {
+        "docker-stats-background"
+    } else {
+        "docker-stats"
+    };
+
+    // Keyed by the watched IDs too, so a different set restarts the stream
     Subscription::run_with_id(
-        "docker-stats",
+        (id, container_ids.clone()),
         stream::channel(10, move |mut output| async move {
-            loop {
-                let stats = fetch_stats(&container_ids).await;
-                let _ = output.send(DockerEvent::StatsUpdated(stats)).await;
-                tokio::time::sleep(Duration::from_secs(3)).await;
+            let docker = match connect() {
+                Ok(d) => d,
+                Err(_) => return,
+            };
+
+            if background {
+                poll_stats(&docker, &container_ids, interval, &mut output).await;
+                return;
+            }
+
+            // The daemon pushes a sample per container every second over one
+            // long-lived request each; the latest ones are forwarded on every tick
+            let streams = container_ids.into_iter().map(|id| {
+                let options = StatsOptions {
+                    stream: true,
+                    one_shot: false,
+                };
+                docker
+                    .stats(&id, Some(options))
+                    .filter_map(|sample| async move { sample.ok() })
+                    .map(move |sample| StatsItem::Sample(id.clone(), Box::new(sample)))
+                    .boxed()
+            });
+            let ticks = futures::stream::unfold((), move |()| async move {
+                tokio::time::sleep(interval).await;
+                Some((StatsItem::Tick, ()))
+            })
+            .boxed();
+            let mut items = futures::stream::select_all(streams.chain(std::iter::once(ticks)));
+
+            let mut latest: HashMap<String, ContainerStats> = HashMap::new();
+            let mut previous: HashMap<String, (Instant, IoCounters)> = HashMap::new();
+            while let Some(item) = items.next().await {
+                match item {
+                    StatsItem::Sample(id, sample) => {
+                        let sampled_at = Instant::now();
+                        let (mut container_stats, counters) = container_stats(&sample);
+                        // The first sample has no previous CPU reading to diff against,
+                        // so it only primes the counters
+                        let Some((before, prev)) =
+                            previous.insert(id.clone(), (sampled_at, counters))
+                        else {
+                            continue;
+                        };
+                        container_stats.io_rates =
+                            Some(counters.rates_since(&prev, sampled_at - before));
+                        latest.insert(id, container_stats);
+                    }
+                    StatsItem::Tick => {
+                        if !latest.is_empty() {
+                            let _ = output.send(DockerEvent::StatsUpdated(latest.clone())).await;
+                        }
+                    }
+                }
             }
         }),
     )
 }
 
+enum StatsItem {
+    Sample(String, Box<Stats>),
+    Tick,
+}
+
+/// Samples each container once per `interval` instead of holding a stream open per
+/// container, for the background sampler. One-shot samples carry no previous CPU
+/// reading, so each is diffed against the container's sample from the last poll.
+async fn poll_stats(
+    docker: &Docker,
+    container_ids: &[String],
+    interval: Duration,
+    output: &mut mpsc::Sender<DockerEvent>,
+) {
+    let mut previous: HashMap<String, (Instant, IoCounters, CPUStats)> = HashMap::new();
+    let mut primed = false;
+    loop {
+        let mut latest: HashMap<String, ContainerStats> = HashMap::new();
+        for id in container_ids {
+            let options = StatsOptions {
+                stream: false,
+                one_shot: true,
+            };
+            let Some(Ok(mut sample)) = docker.stats(id, Some(options)).next().await else {
+                continue;
+            };
+            let sampled_at = Instant::now();
+            let cpu = sample.cpu_stats.clone();
+            let Some((before, prev, prev_cpu)) = previous.insert(
+                id.clone(),
+                (sampled_at, calculate_io_counters(&sample), cpu),
+            ) else {
+                continue;
+            };
+            sample.precpu_stats = prev_cpu;
+            let (mut container_stats, counters) = container_stats(&sample);
+            container_stats.io_rates = Some(counters.rates_since(&prev, sampled_at - before));
+            latest.insert(id.clone(), container_stats);
+        }
+        if !latest.is_empty() {
+            let _ = output.send(DockerEvent::StatsUpdated(latest)).await;
+        }
+        // The first pass only primes the counters, so follow it up quickly
+        let wait = if primed {
+            interval
+        } else {
+            Duration::from_secs(1)
+        };
+        primed = true;
+        tokio::time::sleep(wait).await;
+    }
+}
+
 pub fn docker_events_subscription() -> Subscription<DockerEvent> {
     Subscription::run_with_id(
         "docker-events",
         stream::channel(20, move |mut output| async move {
+            let mut connected = true;
             loop {
-                let docker = match Docker::connect_with_local_defaults() {
-                    Ok(d) => d,
-                    Err(_) => {
-                        tokio::time::sleep(Duration::from_secs(5)).await;
+                // Ping quickly while the daemon is down so a restart is picked
+                // up right away instead of on the next list poll
+                let docker = match connect() {
+                    Ok(d) if d.ping().await.is_ok() => d,
+                    _ => {
+                        if connected {
+                            connected = false;
+                            let _ = output.send(DockerEvent::DaemonDisconnected).await;
+                        }
+                        tokio::time::sleep(Duration::from_secs(1)).await;
                         continue;
                     }
                 };
+                if !connected {
+                    connected = true;
+                    let _ = output.send(DockerEvent::DaemonReconnected).await;
+                }
 
                 let options = EventsOptions::<String> {
                     ..Default::default()
@@ -215,41 +847,116 @@ pub fn docker_events_subscription() -> Subscription<DockerEvent> {
                     }
                 }
 
-                // Stream ended, reconnect after a delay
-                tokio::time::sleep(Duration::from_secs(2)).await;
+                // Stream ended; the ping above tells a restart from a hiccup
+                connected = false;
+                let _ = output.send(DockerEvent::DaemonDisconnected).await;
+                tokio::time::sleep(Duration::from_secs(1)).await;
             }
         }),
     )
 }
 
-pub fn log_streaming_subscription(container_id: String) -> Subscription<DockerEvent> {
+/// The output stream a log frame was written to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LogStream {
+    Stdout,
+    Stderr,
+}
+
+/// Which part of a container's log history to load before following it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LogWindow {
+    /// Lines to start with, counted from the end; `None` for all of them
+    pub tail: Option<usize>,
+    pub since: LogSince,
+    /// Start every line with its RFC 3339 time as recorded by the daemon
+    pub timestamps: bool,
+}
+
+impl Default for LogWindow {
+    fn default() -> Self {
+        Self {
+            tail: Some(200),
+            since: LogSince::All,
+            timestamps: false,
+        }
+    }
+}
+
+/// How far back a log window reaches
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LogSince {
+    All,
+    /// Only lines from the last this many seconds
+    Last(u64),
+    /// Only lines from the container's current run
+    LastStart,
+}
+
+pub fn log_streaming_subscription(
+    container_id: String,
+    window: LogWindow,
+) -> Subscription<DockerEvent> {
     Subscription::run_with_id(
-        format!("docker-logs-{}", container_id),
+        ("docker-logs", container_id.clone(), window),
         stream::channel(100, move |mut output| async move {
-            let docker = match Docker::connect_with_local_defaults() {
+            let docker = match connect() {
                 Ok(d) => d,
                 Err(_) => return,
             };
 
+            let info = docker
+                .inspect_container(&container_id, None::<InspectContainerOptions>)
+                .await
+                .ok();
+            let since = match window.since {
+                LogSince::All => 0,
+                LogSince::Last(secs) => {
+                    let now = std::time::SystemTime::now()
+                        .duration_since(std::time::UNIX_EPOCH)
+                        .map(|d| d.as_secs())
+                        .unwrap_or(0);
+                    now.saturating_sub(secs) as i64
+                }
+                LogSince::LastStart => info.as_ref().and_then(started_at).unwrap_or(0),
+            };
+
+            let driver = info
+                .as_ref()
+                .and_then(|info| info.host_config.as_ref()?.log_config.as_ref()?.typ.clone());
+
             let options = LogsOptions::<String> {
                 follow: true,
                 stdout: true,
                 stderr: true,
-                tail: "200".to_string(),
+                since,
+                tail: window
+                    .tail
+                    .map_or_else(|| "all".to_string(), |tail| tail.to_string()),
+                timestamps: window.timestamps,
                 ..Default::default()
             };
 
             let mut log_stream = docker.logs(&container_id, Some(options));
+            let mut received = false;
             while let Some(log_result) = log_stream.next().await {
                 match log_result {
                     Ok(line) => {
+                        received = true;
                         let _ = output
                             .send(DockerEvent::LogLine(
                                 container_id.clone(),
+                                log_stream_of(&line),
                                 line.to_string(),
                             ))
                             .await;
                     }
+                    // Daemons without journald read support refuse the request outright;
+                    // the journal itself still has the lines
+                    Err(_) if !received && driver.as_deref() == Some("journald") => {
+                        follow_journal(&container_id, window, since, &mut output).await;
+                        break;
+                    }
                     Err(_) => break,
                 }
             }
@@ -257,207 +964,2051 @@ pub fn log_streaming_subscription(container_id: String) -> Subscription<DockerEv
     )
 }
 
-pub fn health_subscription(container_ids: Vec<String>) -> Subscription<DockerEvent> {
-    if container_ids.is_empty() {
-        return Subscription::none();
+/// Unix time the container was last started, from its inspect state
+fn started_at(info: &ContainerInspectResponse) -> Option<i64> {
+    let started = info.state.as_ref()?.started_at.as_ref()?;
+    chrono::DateTime::parse_from_rfc3339(started)
+        .ok()
+        .map(|time| time.timestamp())
+}
+
+/// Streams a container's entries from the systemd journal, for journald containers the
+/// daemon cannot read logs back from. Lines are sent in the same shape as the daemon's
+/// log output; a failing `journalctl` is reported with its exit status and stderr.
+async fn follow_journal(
+    container_id: &str,
+    window: LogWindow,
+    since: i64,
+    output: &mut mpsc::Sender<DockerEvent>,
+) {
+    let mut command = tokio::process::Command::new("journalctl");
+    command
+        .args(["--no-pager", "--follow", "--output=json"])
+        .arg(format!(
+            "--lines={}",
+            window
+                .tail
+                .map_or_else(|| "all".to_string(), |tail| tail.to_string())
+        ))
+        .arg(format!("CONTAINER_ID_FULL={}", container_id))
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .kill_on_drop(true);
+    if since > 0 {
+        command.arg(format!("--since=@{}", since));
     }
+    let mut child = match command.spawn() {
+        Ok(child) => child,
+        Err(e) => {
+            let _ = output
+                .send(DockerEvent::LogStreamFailed(
+                    container_id.to_string(),
+                    format!("journalctl: {}", e),
+                ))
+                .await;
+            return;
+        }
+    };
+    let Some(stdout) = child.stdout.take() else {
+        return;
+    };
+
+    let mut lines = line_stream(stdout);
+    while let Some(line) = lines.next().await {
+        let Some((stream, message)) = journal_entry(&line, window.timestamps) else {
+            continue;
+        };
+        let _ = output
+            .send(DockerEvent::LogLine(container_id.to_string(), stream, message))
+            .await;
+    }
+
+    // Stdout only closes once journalctl exits, which it never does while following
+    let error = match child.wait_with_output().await {
+        Ok(result) if result.status.success() => return,
+        Ok(result) => format!(
+            "journalctl {}: {}",
+            result.status,
+            String::from_utf8_lossy(&result.stderr).trim()
+        ),
+        Err(e) => format!("journalctl: {}", e),
+    };
+    let _ = output
+        .send(DockerEvent::LogStreamFailed(container_id.to_string(), error))
+        .await;
+}
+
+/// Turns one `journalctl --output=json` record into a log line. The journald driver
+/// logs stderr at priority 3 and stdout at priority 6.
+fn journal_entry(record: &str, timestamps: bool) -> Option<(LogStream, String)> {
+    let entry: serde_json::Value = serde_json::from_str(record).ok()?;
+    let message = entry.get("MESSAGE")?.as_str()?;
+    let stream = match entry.get("PRIORITY").and_then(|p| p.as_str()) {
+        Some("3") => LogStream::Stderr,
+        _ => LogStream::Stdout,
+    };
+    let line = if timestamps {
+        let micros: i64 = entry
+            .get("__REALTIME_TIMESTAMP")
+            .and_then(|t| t.as_str())
+            .and_then(|t| t.parse().ok())
+            .unwrap_or(0);
+        let time = chrono::DateTime::from_timestamp_micros(micros).unwrap_or_default();
+        format!(
+            "{} {}\n",
+            time.to_rfc3339_opts(chrono::SecondsFormat::Nanos, true),
+            message
+        )
+    } else {
+        format!("{}\n", message)
+    };
+    Some((stream, line))
+}
 
+/// Follows the logs of every container in a project at once, like
+/// `docker compose logs -f`. `containers` pairs each ID with its line prefix.
+pub fn project_logs_subscription(
+    project: String,
+    containers: Vec<(String, String)>,
+) -> Subscription<DockerEvent> {
+    let ids: Vec<String> = containers.iter().map(|(id, _)| id.clone()).collect();
     Subscription::run_with_id(
-        "docker-health",
-        stream::channel(10, move |mut output| async move {
-            loop {
-                let statuses = fetch_health_statuses(&container_ids).await;
-                let _ = output.send(DockerEvent::HealthUpdated(statuses)).await;
-                tokio::time::sleep(Duration::from_secs(10)).await;
+        ("docker-project-logs", project.clone(), ids),
+        stream::channel(100, move |mut output| async move {
+            let docker = match connect() {
+                Ok(d) => d,
+                Err(_) => return,
+            };
+
+            let streams = containers.into_iter().map(|(id, prefix)| {
+                let options = LogsOptions::<String> {
+                    follow: true,
+                    stdout: true,
+                    stderr: true,
+                    tail: "100".to_string(),
+                    ..Default::default()
+                };
+                docker
+                    .logs(&id, Some(options))
+                    .map(move |chunk| (prefix.clone(), chunk))
+                    .boxed()
+            });
+            let mut log_stream = futures::stream::select_all(streams);
+            while let Some((prefix, chunk)) = log_stream.next().await {
+                let Ok(chunk) = chunk else {
+                    continue;
+                };
+                for line in chunk.to_string().lines() {
+                    let _ = output
+                        .send(DockerEvent::ProjectLogLine(
+                            project.clone(),
+                            prefix.clone(),
+                            line.to_string(),
+                        ))
+                        .await;
+                }
             }
         }),
     )
 }
 
-async fn fetch_containers() -> Result<Vec<ContainerInfo>, String> {
-    let docker = Docker::connect_with_local_defaults().map_err(|e| e.to_string())?;
+/// Level markers, panics and tracebacks that make a log line an error
+static ERROR_LINE: once_cell::sync::Lazy<regex::Regex> = once_cell::sync::Lazy::new(|| {
+    regex::Regex::new(
+        r#"\b(ERROR|FATAL|CRITICAL|PANIC)\b|(?i:level"?\s*[=:]\s*"?(error|fatal|crit))|\bpanicked at\b|^panic:|^Traceback \(most recent call last\)"#,
+    )
+    .expect("valid error pattern")
+});
 
-    let options = ListContainersOptions::<String> {
-        all: true,
-        ..Default::default()
-    };
+static WARNING_LINE: once_cell::sync::Lazy<regex::Regex> = once_cell::sync::Lazy::new(|| {
+    regex::Regex::new(r#"\b(WARN|WARNING)\b|(?i:level"?\s*[=:]\s*"?warn)"#)
+        .expect("valid warning pattern")
+});
 
-    let containers = docker
-        .list_containers(Some(options))
-        .await
-        .map_err(|e| e.to_string())?;
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogSeverity {
+    Error,
+    Warning,
+}
 
-    Ok(containers
-        .into_iter()
-        .map(|c| {
-            let id = c.id.unwrap_or_default();
-            let name = c
-                .names
-                .and_then(|n| n.first().cloned())
-                .unwrap_or_default()
-                .trim_start_matches('/')
-                .to_string();
-            let image = c.image.unwrap_or_default();
-            let state_str = c.state.unwrap_or_default();
-            let status = c.status.unwrap_or_default();
+/// Classifies a log line (without escape codes) by the severity markers it contains
+pub fn log_severity(line: &str) -> Option<LogSeverity> {
+    if ERROR_LINE.is_match(line) {
+        Some(LogSeverity::Error)
+    } else if WARNING_LINE.is_match(line) {
+        Some(LogSeverity::Warning)
+    } else {
+        None
+    }
+}
 
-            let ports = c
-                .ports
-                .unwrap_or_default()
-                .into_iter()
-                .map(|p| PortMapping {
-                    public_port: p.public_port.map(|pp| pp as u16),
-                    private_port: p.private_port as u16,
-                    protocol: match p.typ {
-                        Some(PortTypeEnum::TCP) => "tcp".to_string(),
-                        Some(PortTypeEnum::UDP) => "udp".to_string(),
-                        Some(PortTypeEnum::SCTP) => "sctp".to_string(),
-                        _ => "tcp".to_string(),
-                    },
-                })
-                .collect();
+/// Follows new log output of a container and reports each error-level line.
+pub fn error_count_subscription(container_id: String) -> Subscription<DockerEvent> {
+    Subscription::run_with_id(
+        format!("docker-errors-{}", container_id),
+        stream::channel(10, move |mut output| async move {
+            let docker = match connect() {
+                Ok(d) => d,
+                Err(_) => return,
+            };
+            let options = LogsOptions::<String> {
+                follow: true,
+                stdout: true,
+                stderr: true,
+                tail: "0".to_string(),
+                ..Default::default()
+            };
+
+            let mut log_stream = docker.logs(&container_id, Some(options));
+            while let Some(Ok(chunk)) = log_stream.next().await {
+                let chunk = chunk.to_string();
+                for _ in chunk.lines().filter(|line| ERROR_LINE.is_match(line)) {
+                    let _ = output
+                        .send(DockerEvent::ErrorLogged(container_id.clone()))
+                        .await;
+                }
+            }
+        }),
+    )
+}
+
+/// Follows new log output of a container and reports lines matching any of `patterns`.
+pub fn log_alert_subscription(
+    container_id: String,
+    patterns: Vec<String>,
+) -> Subscription<DockerEvent> {
+    Subscription::run_with_id(
+        ("docker-alerts", container_id.clone(), patterns.clone()),
+        stream::channel(10, move |mut output| async move {
+            let docker = match connect() {
+                Ok(d) => d,
+                Err(_) => return,
+            };
+            // Patterns are validated when added, so a bad one only means it never fires
+            let matchers: Vec<(String, regex::Regex)> = patterns
+                .into_iter()
+                .filter_map(|p| regex::Regex::new(&p).ok().map(|re| (p, re)))
+                .collect();
+
+            let options = LogsOptions::<String> {
+                follow: true,
+                stdout: true,
+                stderr: true,
+                tail: "0".to_string(),
+                ..Default::default()
+            };
+
+            let mut log_stream = docker.logs(&container_id, Some(options));
+            while let Some(Ok(chunk)) = log_stream.next().await {
+                let chunk = chunk.to_string();
+                for line in chunk.lines() {
+                    for (pattern, matcher) in &matchers {
+                        if matcher.is_match(line) {
+                            let _ = output
+                                .send(DockerEvent::LogAlertMatched(
+                                    container_id.clone(),
+                                    pattern.clone(),
+                                    line.to_string(),
+                                ))
+                                .await;
+                        }
+                    }
+                }
+            }
+        }),
+    )
+}
+
+/// Runs `command` through `sh -c` inside the container, streaming its output.
+/// `run` tells apart repeated runs of the same command.
+pub fn exec_subscription(
+    container_id: String,
+    command: String,
+    run: u64,
+) -> Subscription<DockerEvent> {
+    Subscription::run_with_id(
+        format!("docker-exec-{}-{}", container_id, run),
+        stream::channel(100, move |mut output| async move {
+            let result = async {
+                let docker = connect().map_err(|e| e.to_string())?;
+                let exec = docker
+                    .create_exec(
+                        &container_id,
+                        CreateExecOptions {
+                            attach_stdout: Some(true),
+                            attach_stderr: Some(true),
+                            cmd: Some(vec!["sh".to_string(), "-c".to_string(), command]),
+                            ..Default::default()
+                        },
+                    )
+                    .await
+                    .map_err(|e| e.to_string())?;
+
+                if let StartExecResults::Attached { output: mut stream, .. } = docker
+                    .start_exec(&exec.id, None)
+                    .await
+                    .map_err(|e| e.to_string())?
+                {
+                    while let Some(Ok(chunk)) = stream.next().await {
+                        let _ = output
+                            .send(DockerEvent::ExecOutput(run, chunk.to_string()))
+                            .await;
+                    }
+                }
+
+                let exit_code = docker
+                    .inspect_exec(&exec.id)
+                    .await
+                    .map_err(|e| e.to_string())?
+                    .exit_code
+                    .unwrap_or(0);
+                Ok::<_, String>(exit_code)
+            }
+            .await;
+            let _ = output.send(DockerEvent::ExecFinished(run, result)).await;
+        }),
+    )
+}
+
+/// Runs `docker compose` for a project from its own directory and compose
+/// files, streaming its output line by line.
+pub fn compose_subscription(
+    project: ComposeProject,
+    action: ComposeAction,
+    run: u64,
+) -> Subscription<DockerEvent> {
+    Subscription::run_with_id(
+        ("docker-compose", project.name.clone(), run),
+        stream::channel(100, move |mut output| async move {
+            let mut result = Ok(());
+            for step in action.steps() {
+                result = run_compose_step(&project, &step, run, &mut output).await;
+                if result.is_err() {
+                    break;
+                }
+            }
+            let _ = output.send(DockerEvent::ComposeFinished(run, result)).await;
+        }),
+    )
+}
+
+/// Services declared in a project's compose files, as compose itself resolves them
+pub async fn fetch_compose_services(project: ComposeProject) -> Result<HashSet<String>, String> {
+    let output = tokio::process::Command::new("docker")
+        // Every profile, or services behind one would look removed
+        .args(docker_cli_args(compose_args(
+            &project,
+            &["--profile", "*", "config", "--services"],
+        )))
+        .current_dir(&project.working_dir)
+        .output()
+        .await
+        .map_err(|e| e.to_string())?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+fn compose_args(project: &ComposeProject, step: &[&str]) -> Vec<String> {
+    let mut args = vec![
+        "compose".to_string(),
+        "--ansi".to_string(),
+        "never".to_string(),
+        "--project-name".to_string(),
+        project.name.clone(),
+        "--project-directory".to_string(),
+        project.working_dir.clone(),
+    ];
+    for file in &project.config_files {
+        args.push("--file".to_string());
+        args.push(file.clone());
+    }
+    args.extend(step.iter().map(|s| s.to_string()));
+    args
+}
+
+async fn run_compose_step(
+    project: &ComposeProject,
+    step: &[&str],
+    run: u64,
+    output: &mut mpsc::Sender<DockerEvent>,
+) -> Result<(), String> {
+    let _ = output
+        .send(DockerEvent::ComposeOutput(run, format!("$ docker compose {}", step.join(" "))))
+        .await;
+    let mut child = tokio::process::Command::new("docker")
+        .args(docker_cli_args(compose_args(project, step)))
+        .current_dir(&project.working_dir)
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .map_err(|e| e.to_string())?;
+
+    // Compose reports progress on stderr, so follow both streams
+    let stdout = child.stdout.take().map(line_stream);
+    let stderr = child.stderr.take().map(line_stream);
+    let mut lines = std::pin::pin!(futures::stream::select(
+        futures::stream::iter(stdout).flatten(),
+        futures::stream::iter(stderr).flatten(),
+    ));
+    while let Some(line) = lines.next().await {
+        let _ = output.send(DockerEvent::ComposeOutput(run, line)).await;
+    }
+
+    let status = child.wait().await.map_err(|e| e.to_string())?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("docker compose {} failed ({})", step.join(" "), status))
+    }
+}
+
+fn line_stream<R>(reader: R) -> impl Stream<Item = String>
+where
+    R: tokio::io::AsyncRead + Unpin,
+{
+    let lines = tokio::io::AsyncBufReadExt::lines(tokio::io::BufReader::new(reader));
+    futures::stream::unfold(lines, |mut lines| async move {
+        let line = lines.next_line().await.ok().flatten()?;
+        Some((line, lines))
+    })
+    .boxed()
+}
+
+/// Refreshes the process list of a container while its details are open.
+pub fn processes_subscription(container_id: String) -> Subscription<DockerEvent> {
+    Subscription::run_with_id(
+        format!("docker-top-{}", container_id),
+        stream::channel(10, move |mut output| async move {
+            loop {
+                let result = fetch_processes(container_id.clone()).await;
+                let _ = output.send(DockerEvent::ProcessesUpdated(result)).await;
+                tokio::time::sleep(Duration::from_secs(3)).await;
+            }
+        }),
+    )
+}
+
+/// Periodically checks whether newer versions of `images` exist upstream.
+pub fn image_update_subscription(
+    images: Vec<String>,
+    registries: Vec<RegistryEntry>,
+) -> Subscription<DockerEvent> {
+    if images.is_empty() {
+        return Subscription::none();
+    }
+
+    Subscription::run_with_id(
+        ("docker-image-updates", images.clone()),
+        stream::channel(10, move |mut output| async move {
+            loop {
+                let mut outdated = HashSet::new();
+                for image in &images {
+                    match image_is_outdated(image, &registries).await {
+                        Ok(true) => {
+                            outdated.insert(image.clone());
+                        }
+                        Ok(false) => {}
+                        Err(e) => tracing::debug!("Update check for {} failed: {}", image, e),
+                    }
+                }
+                let _ = output.send(DockerEvent::ImageUpdates(outdated)).await;
+                tokio::time::sleep(Duration::from_secs(6 * 60 * 60)).await;
+            }
+        }),
+    )
+}
+
+/// Periodically checks whether the tag each container was created from now
+/// resolves to a different local image, e.g. after a `docker pull`.
+pub fn superseded_subscription(containers: Vec<(String, String)>) -> Subscription<DockerEvent> {
+    if containers.is_empty() {
+        return Subscription::none();
+    }
+
+    Subscription::run_with_id(
+        ("docker-superseded", containers.clone()),
+        stream::channel(10, move |mut output| async move {
+            loop {
+                let mut superseded = HashSet::new();
+                if let Ok(docker) = connect() {
+                    for (id, image_id) in &containers {
+                        if is_superseded(&docker, id, image_id).await {
+                            superseded.insert(id.clone());
+                        }
+                    }
+                }
+                let _ = output.send(DockerEvent::SupersededUpdated(superseded)).await;
+                tokio::time::sleep(Duration::from_secs(60)).await;
+            }
+        }),
+    )
+}
+
+/// Periodically measures every container's writable layer. The daemon walks the
+/// layer's files to size it, so this runs far less often than the container list.
+pub fn layer_size_subscription() -> Subscription<DockerEvent> {
+    Subscription::run_with_id(
+        "docker-layer-sizes",
+        stream::channel(10, move |mut output| async move {
+            loop {
+                match fetch_layer_sizes().await {
+                    Ok(sizes) => {
+                        let _ = output.send(DockerEvent::LayerSizesUpdated(sizes)).await;
+                    }
+                    Err(e) => tracing::debug!("Failed to measure writable layers: {}", e),
+                }
+                tokio::time::sleep(Duration::from_secs(10 * 60)).await;
+            }
+        }),
+    )
+}
+
+async fn fetch_layer_sizes() -> Result<HashMap<String, i64>, String> {
+    let docker = connect().map_err(|e| e.to_string())?;
+    let options = ListContainersOptions::<String> {
+        all: true,
+        size: true,
+        ..Default::default()
+    };
+    let containers = docker
+        .list_containers(Some(options))
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(containers
+        .into_iter()
+        .filter_map(|c| Some((c.id?, c.size_rw?)))
+        .collect())
+}
+
+async fn is_superseded(docker: &Docker, id: &str, image_id: &str) -> bool {
+    let Ok(inspect) = docker
+        .inspect_container(id, None::<InspectContainerOptions>)
+        .await
+    else {
+        return false;
+    };
+    // Containers created straight from an image ID can't be superseded
+    let Some(reference) = inspect
+        .config
+        .and_then(|c| c.image)
+        .filter(|r| !r.starts_with("sha256:"))
+    else {
+        return false;
+    };
+    match docker.inspect_image(&reference).await {
+        Ok(image) => image.id.is_some_and(|current| current != image_id),
+        Err(_) => false,
+    }
+}
+
+/// Compares the digest the image was pulled by with the registry's current one.
+async fn image_is_outdated(image: &str, registries: &[RegistryEntry]) -> Result<bool, String> {
+    // Pinned by digest or referenced by ID, nothing to compare against
+    if image.contains('@') || image.starts_with("sha256:") {
+        return Ok(false);
+    }
+
+    let docker = connect().map_err(|e| e.to_string())?;
+    let local = docker
+        .inspect_image(image)
+        .await
+        .map_err(|e| e.to_string())?
+        .repo_digests
+        .unwrap_or_default();
+    if local.is_empty() {
+        // Built locally, never pulled
+        return Ok(false);
+    }
+
+    let credentials = registry::credentials_for_image(registries, image).await;
+    let remote = docker
+        .inspect_registry_image(image, credentials)
+        .await
+        .map_err(|e| e.to_string())?
+        .descriptor
+        .digest
+        .ok_or_else(|| "Registry returned no digest".to_string())?;
+
+    Ok(!local
+        .iter()
+        .any(|d| d.rsplit_once('@').is_some_and(|(_, digest)| digest == remote)))
+}
+
+pub fn health_subscription(container_ids: Vec<String>) -> Subscription<DockerEvent> {
+    if container_ids.is_empty() {
+        return Subscription::none();
+    }
+
+    Subscription::run_with_id(
+        "docker-health",
+        stream::channel(10, move |mut output| async move {
+            loop {
+                let statuses = fetch_health_statuses(&container_ids).await;
+                let _ = output.send(DockerEvent::HealthUpdated(statuses)).await;
+                tokio::time::sleep(Duration::from_secs(10)).await;
+            }
+        }),
+    )
+}
+
+pub async fn fetch_containers() -> Result<Vec<ContainerInfo>, String> {
+    let docker = connect().map_err(|e| e.to_string())?;
+
+    let options = ListContainersOptions::<String> {
+        all: true,
+        ..Default::default()
+    };
+
+    let containers = docker
+        .list_containers(Some(options))
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(containers
+        .into_iter()
+        .map(|c| {
+            let id = c.id.unwrap_or_default();
+            let name = c
+                .names
+                .and_then(|n| n.first().cloned())
+                .unwrap_or_default()
+                .trim_start_matches('/')
+                .to_string();
+            let image = c.image.unwrap_or_default();
+            let image_id = c.image_id.unwrap_or_default();
+            let state_str = c.state.unwrap_or_default();
+            let status = c.status.unwrap_or_default();
+
+            let ports = c
+                .ports
+                .unwrap_or_default()
+                .into_iter()
+                .map(|p| PortMapping {
+                    public_port: p.public_port.map(|pp| pp as u16),
+                    private_port: p.private_port as u16,
+                    protocol: match p.typ {
+                        Some(PortTypeEnum::TCP) => "tcp".to_string(),
+                        Some(PortTypeEnum::UDP) => "udp".to_string(),
+                        Some(PortTypeEnum::SCTP) => "sctp".to_string(),
+                        _ => "tcp".to_string(),
+                    },
+                })
+                .collect();
 
             let labels = c.labels.unwrap_or_default();
             let created = c.created;
+            let volumes = c
+                .mounts
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|m| m.typ == Some(MountPointTypeEnum::VOLUME))
+                .filter_map(|m| m.name)
+                .collect();
+            let networks = c
+                .network_settings
+                .and_then(|n| n.networks)
+                .map(|n| n.into_keys().collect())
+                .unwrap_or_default();
 
             ContainerInfo {
                 id,
                 name,
                 image,
+                image_id,
                 state: parse_state(&state_str),
                 status,
                 ports,
                 labels,
                 created,
+                volumes,
+                networks,
+            }
+        })
+        .collect())
+}
+
+fn container_stats(stats: &Stats) -> (ContainerStats, IoCounters) {
+    let (mem_usage, mem_limit, mem_percent) = calculate_memory(stats);
+    (
+        ContainerStats {
+            cpu_percent: calculate_cpu_percent(stats),
+            memory_usage_mb: mem_usage,
+            memory_limit_mb: mem_limit,
+            memory_percent: mem_percent,
+            pids: stats.pids_stats.current.unwrap_or(0),
+            online_cpus: stats.cpu_stats.online_cpus.unwrap_or(1).max(1) as u32,
+            io_rates: None,
+        },
+        calculate_io_counters(stats),
+    )
+}
+
+/// Starts every stopped container listed by name or belonging to one of the
+/// compose projects. Returns the names started and the failures.
+pub async fn start_autostart(
+    names: Vec<String>,
+    groups: Vec<String>,
+) -> Result<(Vec<String>, Vec<String>), String> {
+    let containers = fetch_containers().await?;
+
+    let mut started = Vec::new();
+    let mut failed = Vec::new();
+    for container in containers {
+        let in_group = container
+            .labels
+            .get("com.docker.compose.project")
+            .is_some_and(|project| groups.contains(project));
+        if container.state == ContainerState::Running
+            || !(names.contains(&container.name) || in_group)
+        {
+            continue;
+        }
+        match start_container(container.id).await {
+            Ok(_) => started.push(container.name),
+            Err(e) => failed.push(format!("{} ({})", container.name, e)),
+        }
+    }
+    Ok((started, failed))
+}
+
+/// Creates a new container from an existing one's configuration. Published
+/// host ports are shifted by `port_offset` so the copy can run alongside.
+pub async fn create_container_from(
+    id: String,
+    name: String,
+    port_offset: u16,
+) -> Result<String, String> {
+    let docker = connect().map_err(|e| e.to_string())?;
+
+    let inspect = docker
+        .inspect_container(&id, None::<InspectContainerOptions>)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let mut config: Config<String> = inspect
+        .config
+        .ok_or_else(|| "Container has no configuration".to_string())?
+        .into();
+
+    // The default hostname is the source's short ID; let the daemon pick a new one
+    if config
+        .hostname
+        .as_ref()
+        .is_some_and(|hostname| id.starts_with(hostname.as_str()))
+    {
+        config.hostname = None;
+    }
+    config.mac_address = None;
+    // A copy is not part of the compose project, and compose would treat it as
+    // a duplicate service container
+    if let Some(labels) = &mut config.labels {
+        labels.retain(|key, _| !key.starts_with("com.docker.compose."));
+    }
+
+    let mut host_config = inspect.host_config.unwrap_or_default();
+    if port_offset > 0 {
+        for binding in host_config
+            .port_bindings
+            .iter_mut()
+            .flat_map(|bindings| bindings.values_mut())
+            .flatten()
+            .flatten()
+        {
+            if let Some(port) = binding.host_port.as_ref().and_then(|p| p.parse::<u16>().ok()) {
+                let shifted = port
+                    .checked_add(port_offset)
+                    .ok_or_else(|| format!("Port {} + {} is out of range", port, port_offset))?;
+                binding.host_port = Some(shifted.to_string());
+            }
+        }
+    }
+    config.host_config = Some(host_config);
+
+    let response = docker
+        .create_container(
+            Some(CreateContainerOptions {
+                name: name.as_str(),
+                platform: None,
+            }),
+            config,
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(response.id)
+}
+
+/// Replaces a container with a fresh one from the current image of its tag,
+/// keeping its name, configuration and networks. The old container is only
+/// removed once the new one has been created. Returns the new container's ID and
+/// whether the old one was running; starting the new one is left to the caller.
+pub async fn recreate_container(id: String) -> Result<(String, bool), String> {
+    let docker = connect().map_err(|e| e.to_string())?;
+
+    let inspect = docker
+        .inspect_container(&id, None::<InspectContainerOptions>)
+        .await
+        .map_err(|e| e.to_string())?;
+    let name = inspect
+        .name
+        .clone()
+        .unwrap_or_default()
+        .trim_start_matches('/')
+        .to_string();
+    let was_running = inspect
+        .state
+        .as_ref()
+        .and_then(|s| s.running)
+        .unwrap_or(false);
+
+    let mut config: Config<String> = inspect
+        .config
+        .ok_or_else(|| "Container has no configuration".to_string())?
+        .into();
+    if config
+        .hostname
+        .as_ref()
+        .is_some_and(|hostname| id.starts_with(hostname.as_str()))
+    {
+        config.hostname = None;
+    }
+    config.host_config = inspect.host_config;
+    let mut endpoints: HashMap<String, EndpointSettings> = inspect
+        .network_settings
+        .and_then(|n| n.networks)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(network, endpoint)| {
+            let settings = EndpointSettings {
+                ipam_config: endpoint.ipam_config,
+                links: endpoint.links,
+                aliases: endpoint.aliases,
+                driver_opts: endpoint.driver_opts,
+                ..Default::default()
+            };
+            (network, settings)
+        })
+        .collect();
+    // Daemons before API 1.44 take a single endpoint at create time, so only the
+    // primary network goes in the config and the others are connected afterwards
+    let primary = config
+        .host_config
+        .as_ref()
+        .and_then(|host| host.network_mode.clone())
+        .filter(|mode| endpoints.contains_key(mode))
+        .or_else(|| endpoints.keys().min().cloned());
+    if let Some((network, settings)) = primary.and_then(|p| endpoints.remove_entry(&p)) {
+        config.networking_config = Some(NetworkingConfig {
+            endpoints_config: HashMap::from([(network, settings)]),
+        });
+    }
+
+    if was_running {
+        stop_container(id.clone()).await?;
+    }
+    let backup_name = format!("{}-old-{}", name, &id[..12.min(id.len())]);
+    rename_container(id.clone(), backup_name).await?;
+
+    let created = docker
+        .create_container(
+            Some(CreateContainerOptions {
+                name: name.as_str(),
+                platform: None,
+            }),
+            config,
+        )
+        .await;
+    let new_id = match created {
+        Ok(response) => response.id,
+        Err(e) => {
+            restore_original(id, name, was_running).await;
+            return Err(e.to_string());
+        }
+    };
+    for (network, endpoint_config) in endpoints {
+        let connected = docker
+            .connect_network(
+                &network,
+                ConnectNetworkOptions {
+                    container: new_id.as_str(),
+                    endpoint_config,
+                },
+            )
+            .await;
+        if let Err(e) = connected {
+            let _ = remove_container(new_id, RemoveFlags::default()).await;
+            restore_original(id, name, was_running).await;
+            return Err(e.to_string());
+        }
+    }
+
+    remove_container(id.clone(), RemoveFlags::default()).await?;
+    Ok((new_id, was_running))
+}
+
+/// Puts a container set aside by `recreate_container` back the way it was
+async fn restore_original(id: String, name: String, was_running: bool) {
+    let _ = rename_container(id.clone(), name).await;
+    if was_running {
+        let _ = start_container(id).await;
+    }
+}
+
+pub async fn list_images() -> Result<Vec<ImageInfo>, String> {
+    let docker = connect().map_err(|e| e.to_string())?;
+
+    let mut images: Vec<ImageInfo> = docker
+        .list_images(Some(ListImagesOptions::<String> {
+            all: false,
+            ..Default::default()
+        }))
+        .await
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .map(|image| ImageInfo {
+            id: image.id,
+            tags: image.repo_tags,
+            size: image.size,
+            created: image.created,
+            containers: image.containers,
+        })
+        .collect();
+    images.sort_by(|a, b| b.created.cmp(&a.created));
+    Ok(images)
+}
+
+pub async fn remove_image(id: String) -> Result<String, String> {
+    let docker = connect().map_err(|e| e.to_string())?;
+    docker
+        .remove_image(&id, Some(RemoveImageOptions::default()), None)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(id)
+}
+
+pub async fn fetch_volumes() -> Result<Vec<VolumeInfo>, String> {
+    let docker = connect().map_err(|e| e.to_string())?;
+
+    let mut volumes: Vec<VolumeInfo> = docker
+        .list_volumes(None::<ListVolumesOptions<String>>)
+        .await
+        .map_err(|e| e.to_string())?
+        .volumes
+        .unwrap_or_default()
+        .into_iter()
+        .map(|volume| VolumeInfo {
+            name: volume.name,
+            driver: volume.driver,
+            mountpoint: volume.mountpoint,
+            created: volume.created_at,
+        })
+        .collect();
+    volumes.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(volumes)
+}
+
+/// Volume sizes in bytes from the daemon's disk usage scan, which walks every
+/// volume and can take a while. Drivers that can't tell report -1.
+pub async fn fetch_volume_sizes() -> Result<HashMap<String, i64>, String> {
+    let docker = connect().map_err(|e| e.to_string())?;
+    let usage = docker.df().await.map_err(|e| e.to_string())?;
+    Ok(usage
+        .volumes
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|volume| Some((volume.name, volume.usage_data?.size)))
+        .collect())
+}
+
+/// Creates a named volume. `labels` are `key=value` pairs.
+pub async fn create_volume(
+    name: String,
+    driver: String,
+    labels: Vec<String>,
+) -> Result<String, String> {
+    let labels = labels
+        .iter()
+        .map(|label| match label.split_once('=') {
+            Some((key, value)) => (key.to_string(), value.to_string()),
+            None => (label.clone(), String::new()),
+        })
+        .collect();
+    let docker = connect().map_err(|e| e.to_string())?;
+    let volume = docker
+        .create_volume(CreateVolumeOptions {
+            name,
+            driver,
+            driver_opts: HashMap::new(),
+            labels,
+        })
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(volume.name)
+}
+
+/// Names of volumes no container references, i.e. what a prune would delete.
+pub async fn fetch_unused_volumes() -> Result<Vec<String>, String> {
+    let docker = connect().map_err(|e| e.to_string())?;
+    let mut names: Vec<String> = docker
+        .list_volumes(Some(ListVolumesOptions {
+            filters: HashMap::from([("dangling", vec!["true"])]),
+        }))
+        .await
+        .map_err(|e| e.to_string())?
+        .volumes
+        .unwrap_or_default()
+        .into_iter()
+        .map(|volume| volume.name)
+        .collect();
+    names.sort();
+    Ok(names)
+}
+
+/// Removes exactly the given volumes, never one that wasn't listed, and
+/// returns the names removed and a `name: error` line per failure.
+pub async fn remove_volumes(names: Vec<String>) -> (Vec<String>, Vec<String>) {
+    let mut removed = Vec::new();
+    let mut failed = Vec::new();
+    for name in names {
+        match remove_volume(name.clone()).await {
+            Ok(name) => removed.push(name),
+            // e.g. a container started using it since it was listed
+            Err(e) => failed.push(format!("{}: {}", name, e)),
+        }
+    }
+    (removed, failed)
+}
+
+/// Summarizes `docker system df`.
+pub async fn fetch_disk_usage() -> Result<DiskUsage, String> {
+    let docker = connect().map_err(|e| e.to_string())?;
+    let usage = docker.df().await.map_err(|e| e.to_string())?;
+
+    let images = usage.images.unwrap_or_default();
+    // Layers shared between images would be counted twice by summing sizes
+    let image_size = usage
+        .layers_size
+        .unwrap_or_else(|| images.iter().map(|i| i.size).sum());
+    let images_summary = UsageSummary {
+        count: images.len(),
+        size: image_size,
+        reclaimable: images
+            .iter()
+            .filter(|i| i.containers == 0)
+            .map(|i| (i.size - i.shared_size.max(0)).max(0))
+            .sum(),
+    };
+
+    let containers = usage.containers.unwrap_or_default();
+    let containers_summary = UsageSummary {
+        count: containers.len(),
+        size: containers.iter().filter_map(|c| c.size_rw).sum(),
+        reclaimable: containers
+            .iter()
+            .filter(|c| c.state.as_deref() != Some("running"))
+            .filter_map(|c| c.size_rw)
+            .sum(),
+    };
+
+    let mut container_sizes: Vec<ContainerSize> = containers
+        .iter()
+        .map(|c| ContainerSize {
+            id: c.id.clone().unwrap_or_default(),
+            name: c
+                .names
+                .as_ref()
+                .and_then(|n| n.first())
+                .map(|n| n.trim_start_matches('/').to_string())
+                .unwrap_or_default(),
+            size_rw: c.size_rw.unwrap_or(0),
+            size_root_fs: c.size_root_fs.unwrap_or(0),
+        })
+        .collect();
+    container_sizes.sort_by(|a, b| b.size_rw.cmp(&a.size_rw));
+
+    let volumes = usage.volumes.unwrap_or_default();
+    let volume_usage = |unused_only: bool| -> i64 {
+        volumes
+            .iter()
+            .filter_map(|v| v.usage_data.as_ref())
+            .filter(|u| !unused_only || u.ref_count == 0)
+            .map(|u| u.size.max(0))
+            .sum()
+    };
+    let volumes_summary = UsageSummary {
+        count: volumes.len(),
+        size: volume_usage(false),
+        reclaimable: volume_usage(true),
+    };
+
+    let build_cache = usage.build_cache.unwrap_or_default();
+    let build_cache_summary = UsageSummary {
+        count: build_cache.len(),
+        size: build_cache.iter().filter_map(|b| b.size).sum(),
+        reclaimable: build_cache
+            .iter()
+            .filter(|b| !b.in_use.unwrap_or(false) && !b.shared.unwrap_or(false))
+            .filter_map(|b| b.size)
+            .sum(),
+    };
+
+    Ok(DiskUsage {
+        images: images_summary,
+        containers: containers_summary,
+        volumes: volumes_summary,
+        build_cache: build_cache_summary,
+        container_sizes,
+    })
+}
+
+/// Runs the selected prunes and returns the total space reclaimed.
+pub async fn prune_system(targets: PruneTargets) -> Result<i64, String> {
+    let docker = connect().map_err(|e| e.to_string())?;
+    let mut reclaimed = 0;
+    if targets.stopped_containers {
+        reclaimed += docker
+            .prune_containers(None::<PruneContainersOptions<String>>)
+            .await
+            .map_err(|e| e.to_string())?
+            .space_reclaimed
+            .unwrap_or(0);
+    }
+    if targets.dangling_images {
+        reclaimed += prune_images().await?;
+    }
+    if targets.unused_networks {
+        docker
+            .prune_networks(None::<PruneNetworksOptions<String>>)
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+    if targets.build_cache {
+        reclaimed += prune_build_cache().await?;
+    }
+    Ok(reclaimed)
+}
+
+/// Calls the Engine's `/build/prune` endpoint and returns `SpaceReclaimed`.
+/// The pinned bollard release has no method for it, so the request is made
+/// on the daemon socket directly, from a thread since it blocks.
+async fn prune_build_cache() -> Result<i64, String> {
+    let socket = local_socket()?;
+    let (sender, receiver) = futures::channel::oneshot::channel();
+    std::thread::spawn(move || {
+        let _ = sender.send(post_build_prune(&socket));
+    });
+    let response = receiver
+        .await
+        .map_err(|_| "Build cache prune was interrupted".to_string())??;
+    Ok(response.space_reclaimed.unwrap_or(0))
+}
+
+/// The Unix socket the client talks to, honoring the elevated relay and `DOCKER_HOST`
+fn local_socket() -> Result<PathBuf, String> {
+    if let Some(path) = SOCKET_OVERRIDE.read().ok().and_then(|s| s.clone()) {
+        return Ok(path);
+    }
+    match std::env::var("DOCKER_HOST") {
+        Ok(host) if !host.is_empty() => host
+            .strip_prefix("unix://")
+            .map(PathBuf::from)
+            .ok_or_else(|| format!("Pruning the build cache needs a local socket, not {}", host)),
+        _ => Ok(PathBuf::from("/var/run/docker.sock")),
+    }
+}
+
+fn post_build_prune(socket: &Path) -> Result<BuildPruneResponse, String> {
+    let mut stream = std::os::unix::net::UnixStream::connect(socket).map_err(|e| e.to_string())?;
+    // HTTP/1.0 keeps the body unchunked and the connection closes after it
+    stream
+        .write_all(b"POST /build/prune HTTP/1.0\r\nHost: docker\r\nContent-Length: 0\r\n\r\n")
+        .map_err(|e| e.to_string())?;
+    let mut response = Vec::new();
+    stream
+        .read_to_end(&mut response)
+        .map_err(|e| e.to_string())?;
+
+    let split = response
+        .windows(4)
+        .position(|w| w == b"\r\n\r\n")
+        .ok_or_else(|| "Malformed response from the Docker daemon".to_string())?;
+    let (head, body) = (&response[..split], &response[split + 4..]);
+    let status = String::from_utf8_lossy(head)
+        .split_whitespace()
+        .nth(1)
+        .and_then(|code| code.parse::<u16>().ok())
+        .unwrap_or(0);
+    if status != 200 {
+        // Errors come back as {"message": "..."}
+        let message = serde_json::from_slice::<serde_json::Value>(body)
+            .ok()
+            .and_then(|v| v.get("message")?.as_str().map(str::to_string))
+            .unwrap_or_else(|| format!("Docker daemon returned status {}", status));
+        return Err(message);
+    }
+    serde_json::from_slice(body).map_err(|e| e.to_string())
+}
+
+pub async fn remove_volume(name: String) -> Result<String, String> {
+    let docker = connect().map_err(|e| e.to_string())?;
+    docker
+        .remove_volume(&name, None)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(name)
+}
+
+pub async fn fetch_networks() -> Result<Vec<NetworkInfo>, String> {
+    let docker = connect().map_err(|e| e.to_string())?;
+
+    let mut networks: Vec<NetworkInfo> = docker
+        .list_networks(None::<ListNetworksOptions<String>>)
+        .await
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .map(|network| NetworkInfo {
+            id: network.id.unwrap_or_default(),
+            name: network.name.unwrap_or_default(),
+            driver: network.driver.unwrap_or_default(),
+            scope: network.scope.unwrap_or_default(),
+        })
+        .collect();
+    networks.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(networks)
+}
+
+/// Attaches a container to an existing network, optionally under an alias.
+pub async fn connect_network(
+    container_id: String,
+    network: String,
+    alias: Option<String>,
+) -> Result<String, String> {
+    let docker = connect().map_err(|e| e.to_string())?;
+    docker
+        .connect_network(
+            &network,
+            ConnectNetworkOptions {
+                container: container_id.as_str(),
+                endpoint_config: EndpointSettings {
+                    aliases: alias.map(|alias| vec![alias]),
+                    ..Default::default()
+                },
+            },
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(network)
+}
+
+pub async fn disconnect_network(container_id: String, network: String) -> Result<String, String> {
+    let docker = connect().map_err(|e| e.to_string())?;
+    docker
+        .disconnect_network(
+            &network,
+            DisconnectNetworkOptions {
+                container: container_id.as_str(),
+                force: false,
+            },
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(network)
+}
+
+pub async fn remove_network(id: String) -> Result<String, String> {
+    let docker = connect().map_err(|e| e.to_string())?;
+    docker
+        .remove_network(&id)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(id)
+}
+
+/// Applies `reference` (`repo[:tag]`, tag defaulting to `latest`) to an image.
+pub async fn tag_image(id: String, reference: String) -> Result<String, String> {
+    let docker = connect().map_err(|e| e.to_string())?;
+    let (repo, tag) = split_image_tag(&reference);
+    if tag.is_empty() {
+        return Err("Digests cannot be used as a tag".to_string());
+    }
+    docker
+        .tag_image(&id, Some(TagImageOptions { repo, tag }))
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(reference)
+}
+
+/// Image used for the throwaway container that reads a volume during backup
+const BACKUP_HELPER_IMAGE: &str = "busybox:latest";
+
+/// Streams `docker save` output for `image` into `dest`, reporting progress
+/// as the archive is written.
+pub fn image_export_subscription(image: String, dest: PathBuf) -> Subscription<DockerEvent> {
+    Subscription::run_with_id(
+        ("docker-image-export", image.clone(), dest.clone()),
+        stream::channel(100, move |mut output| async move {
+            let result = async {
+                let docker = connect().map_err(|e| e.to_string())?;
+                write_archive(docker.export_image(&image), &dest, &mut output).await
+            }
+            .await;
+            finish_archive(result, &dest, &mut output).await;
+        }),
+    )
+}
+
+/// Backs up a volume as a tar archive at `dest`. The volume is read through a
+/// helper container that mounts it read-only and is removed afterwards; the
+/// helper is never started, the daemon mounts its volumes for the copy.
+pub fn volume_backup_subscription(volume: String, dest: PathBuf) -> Subscription<DockerEvent> {
+    Subscription::run_with_id(
+        ("docker-volume-backup", volume.clone(), dest.clone()),
+        stream::channel(100, move |mut output| async move {
+            let result = async {
+                let docker = connect().map_err(|e| e.to_string())?;
+                let helper = create_backup_helper(&docker, &volume).await?;
+                let archive = docker.download_from_container(
+                    &helper,
+                    Some(DownloadFromContainerOptions { path: "/volume" }),
+                );
+                let result = write_archive(archive, &dest, &mut output).await;
+                let _ = docker
+                    .remove_container(
+                        &helper,
+                        Some(RemoveContainerOptions {
+                            force: true,
+                            ..Default::default()
+                        }),
+                    )
+                    .await;
+                result
+            }
+            .await;
+            finish_archive(result, &dest, &mut output).await;
+        }),
+    )
+}
+
+async fn create_backup_helper(docker: &Docker, volume: &str) -> Result<String, String> {
+    if docker.inspect_image(BACKUP_HELPER_IMAGE).await.is_err() {
+        pull_image(BACKUP_HELPER_IMAGE.to_string(), None).await?;
+    }
+    let config = Config {
+        image: Some(BACKUP_HELPER_IMAGE.to_string()),
+        cmd: Some(vec!["true".to_string()]),
+        host_config: Some(bollard::models::HostConfig {
+            binds: Some(vec![format!("{}:/volume:ro", volume)]),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let response = docker
+        .create_container(None::<CreateContainerOptions<String>>, config)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(response.id)
+}
+
+/// Writes a tar stream to `dest`, reporting the bytes written so far.
+async fn write_archive<B, E>(
+    archive: impl Stream<Item = Result<B, E>>,
+    dest: &Path,
+    output: &mut mpsc::Sender<DockerEvent>,
+) -> Result<PathBuf, String>
+where
+    B: AsRef<[u8]>,
+    E: std::fmt::Display,
+{
+    use tokio::io::AsyncWriteExt;
+
+    let mut archive = std::pin::pin!(archive);
+    let mut file = tokio::fs::File::create(dest)
+        .await
+        .map_err(|e| e.to_string())?;
+    let mut written = 0u64;
+    let mut last_report = Instant::now();
+    while let Some(chunk) = archive.next().await {
+        let chunk = chunk.map_err(|e| e.to_string())?;
+        file.write_all(chunk.as_ref())
+            .await
+            .map_err(|e| e.to_string())?;
+        written += chunk.as_ref().len() as u64;
+        if last_report.elapsed() >= Duration::from_millis(250) {
+            last_report = Instant::now();
+            let _ = output.send(DockerEvent::ArchiveProgress(written)).await;
+        }
+    }
+    file.flush().await.map_err(|e| e.to_string())?;
+    Ok(dest.to_path_buf())
+}
+
+/// Reports the outcome of an archive write, removing a partial file on failure.
+async fn finish_archive(
+    result: Result<PathBuf, String>,
+    dest: &Path,
+    output: &mut mpsc::Sender<DockerEvent>,
+) {
+    if result.is_err() {
+        let _ = tokio::fs::remove_file(dest).await;
+    }
+    let _ = output.send(DockerEvent::ArchiveFinished(result)).await;
+}
+
+/// Layer history of an image, oldest layer first, and the image's total size.
+pub async fn fetch_image_history(image: String) -> Result<(Vec<ImageLayer>, i64), String> {
+    let docker = connect().map_err(|e| e.to_string())?;
+
+    let total_size = docker
+        .inspect_image(&image)
+        .await
+        .map_err(|e| e.to_string())?
+        .size
+        .unwrap_or(0);
+
+    let mut layers: Vec<ImageLayer> = docker
+        .image_history(&image)
+        .await
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .map(|item| ImageLayer {
+            created_by: item
+                .created_by
+                .trim_start_matches("/bin/sh -c #(nop) ")
+                .trim()
+                .to_string(),
+            size: item.size,
+            created: item.created,
+        })
+        .collect();
+    // The API lists the newest layer first
+    layers.reverse();
+
+    Ok((layers, total_size))
+}
+
+/// Removes dangling images and returns the bytes reclaimed.
+pub async fn prune_images() -> Result<i64, String> {
+    let docker = connect().map_err(|e| e.to_string())?;
+    let response = docker
+        .prune_images(Some(PruneImagesOptions {
+            filters: HashMap::from([("dangling", vec!["true"])]),
+        }))
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(response.space_reclaimed.unwrap_or(0))
+}
+
+/// Parses `host:container[/proto]` or `container[/proto]` into the container
+/// port key (e.g. `80/tcp`) and the host port, if any.
+fn parse_port_spec(spec: &str) -> Result<(String, Option<String>), String> {
+    let (ports, proto) = spec.split_once('/').unwrap_or((spec, "tcp"));
+    let (host, container) = match ports.split_once(':') {
+        Some((host, container)) => (Some(host), container),
+        None => (None, ports),
+    };
+    let valid = |p: &str| p.parse::<u16>().is_ok();
+    if !valid(container) || host.is_some_and(|h| !valid(h)) {
+        return Err(format!("Invalid port mapping: {}", spec));
+    }
+    Ok((format!("{}/{}", container, proto), host.map(str::to_string)))
+}
+
+/// Creates a container from `image`, pulling it first when it is not available
+/// locally. Returns the new container's ID; starting it is left to the caller.
+pub async fn create_container(
+    image: String,
+    name: Option<String>,
+    ports: Vec<String>,
+    env: Vec<String>,
+    credentials: Option<DockerCredentials>,
+) -> Result<String, String> {
+    let mut exposed_ports = HashMap::new();
+    let mut port_bindings = HashMap::new();
+    for spec in &ports {
+        let (key, host_port) = parse_port_spec(spec)?;
+        exposed_ports.insert(key.clone(), HashMap::new());
+        if let Some(host_port) = host_port {
+            port_bindings.insert(
+                key,
+                Some(vec![bollard::models::PortBinding {
+                    host_ip: None,
+                    host_port: Some(host_port),
+                }]),
+            );
+        }
+    }
+
+    let docker = connect().map_err(|e| e.to_string())?;
+    if docker.inspect_image(&image).await.is_err() {
+        pull_image(image.clone(), credentials).await?;
+    }
+
+    let config = Config {
+        image: Some(image),
+        env: Some(env),
+        exposed_ports: Some(exposed_ports),
+        host_config: Some(bollard::models::HostConfig {
+            port_bindings: Some(port_bindings),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let response = docker
+        .create_container(
+            name.as_deref().map(|name| CreateContainerOptions {
+                name,
+                platform: None,
+            }),
+            config,
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(response.id)
+}
+
+pub async fn rename_container(id: String, name: String) -> Result<(String, String), String> {
+    let docker = connect().map_err(|e| e.to_string())?;
+    docker
+        .rename_container(&id, RenameContainerOptions { name: name.as_str() })
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok((id, name))
+}
+
+pub async fn start_container(id: String) -> Result<String, String> {
+    let docker = connect().map_err(|e| e.to_string())?;
+    docker
+        .start_container(&id, None::<StartContainerOptions<String>>)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(id)
+}
+
+pub async fn stop_container(id: String) -> Result<String, String> {
+    let docker = connect().map_err(|e| e.to_string())?;
+    docker
+        .stop_container(&id, Some(StopContainerOptions { t: 10 }))
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(id)
+}
+
+pub async fn pause_container(id: String) -> Result<String, String> {
+    let docker = connect().map_err(|e| e.to_string())?;
+    docker
+        .pause_container(&id)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(id)
+}
+
+pub async fn unpause_container(id: String) -> Result<String, String> {
+    let docker = connect().map_err(|e| e.to_string())?;
+    docker
+        .unpause_container(&id)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(id)
+}
+
+pub async fn restart_container(id: String) -> Result<String, String> {
+    let docker = connect().map_err(|e| e.to_string())?;
+    docker
+        .restart_container(&id, Some(RestartContainerOptions { t: 10 }))
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(id)
+}
+
+pub async fn remove_container(id: String, flags: RemoveFlags) -> Result<String, String> {
+    let docker = connect().map_err(|e| e.to_string())?;
+    docker
+        .remove_container(
+            &id,
+            Some(RemoveContainerOptions {
+                force: flags.force,
+                v: flags.volumes,
+                ..Default::default()
+            }),
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(id)
+}
+
+pub async fn fetch_container_details(id: String) -> Result<(String, ContainerDetails), String> {
+    let docker = connect().map_err(|e| e.to_string())?;
+
+    let inspect = docker
+        .inspect_container(&id, None::<InspectContainerOptions>)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let raw_json = serde_json::to_string_pretty(&inspect).unwrap_or_default();
+
+    let memory_limit = inspect
+        .host_config
+        .as_ref()
+        .and_then(|hc| hc.memory)
+        .unwrap_or(0);
+    let cpu_limit = inspect
+        .host_config
+        .as_ref()
+        .map(|hc| cpu_limit_cores(hc.nano_cpus, hc.cpu_quota, hc.cpu_period))
+        .unwrap_or(0.0);
+    let memory_swap = inspect
+        .host_config
+        .as_ref()
+        .and_then(|hc| hc.memory_swap)
+        .unwrap_or(0);
+    let cpu_shares = inspect
+        .host_config
+        .as_ref()
+        .and_then(|hc| hc.cpu_shares)
+        .unwrap_or(0);
+    let pids_limit = inspect
+        .host_config
+        .as_ref()
+        .and_then(|hc| hc.pids_limit)
+        .filter(|limit| *limit > 0)
+        .unwrap_or(0);
+
+    let restart_policy = RestartPolicy::from_name(
+        inspect
+            .host_config
+            .as_ref()
+            .and_then(|hc| hc.restart_policy.as_ref())
+            .and_then(|rp| rp.name),
+    );
+
+    // `Image` is the ID the container was created from, even if its tag has moved since
+    let image = match &inspect.image {
+        Some(image_id) => docker.inspect_image(image_id).await.ok(),
+        None => None,
+    };
+    let repository = inspect
+        .config
+        .as_ref()
+        .and_then(|c| c.image.as_deref())
+        .map(|reference| split_image_tag(reference).0);
+    let digests = image
+        .as_ref()
+        .and_then(|i| i.repo_digests.clone())
+        .unwrap_or_default();
+    let image_digest = digests
+        .iter()
+        .find(|digest| {
+            repository
+                .as_deref()
+                .is_some_and(|repo| digest.split('@').next() == Some(repo))
+        })
+        .or(digests.first())
+        .cloned();
+    let platform = image
+        .as_ref()
+        .map(|i| {
+            [&i.os, &i.architecture, &i.variant]
+                .into_iter()
+                .flatten()
+                .filter(|part| !part.is_empty())
+                .cloned()
+                .collect::<Vec<_>>()
+                .join("/")
+        })
+        .unwrap_or_default();
+    let image_arch = image.as_ref().and_then(|i| i.architecture.clone());
+    let image_created = image
+        .and_then(|i| i.created)
+        .as_deref()
+        .and_then(parse_inspect_time);
+
+    let runtime = inspect
+        .host_config
+        .as_ref()
+        .and_then(|hc| hc.runtime.clone())
+        .unwrap_or_else(|| "runc".to_string());
+    let devices = inspect
+        .host_config
+        .as_ref()
+        .and_then(|hc| hc.devices.clone())
+        .unwrap_or_default()
+        .into_iter()
+        .map(|d| {
+            (
+                d.path_on_host.unwrap_or_default(),
+                d.path_in_container.unwrap_or_default(),
+            )
+        })
+        .collect();
+    let device_requests = inspect
+        .host_config
+        .as_ref()
+        .and_then(|hc| hc.device_requests.clone())
+        .unwrap_or_default()
+        .into_iter()
+        .map(|r| DeviceRequestInfo {
+            driver: r.driver.unwrap_or_default(),
+            count: r.count.unwrap_or(0),
+            device_ids: r.device_ids.unwrap_or_default(),
+            capabilities: r.capabilities.unwrap_or_default().concat(),
+        })
+        .collect();
+
+    let security_risks = security_risks(&inspect);
+
+    let info = docker.info().await.ok();
+    let host_memory = info.as_ref().and_then(|i| i.mem_total).unwrap_or(0);
+    let host_cpus = info.as_ref().and_then(|i| i.ncpu).unwrap_or(1) as f64;
+    let host_arch = info.as_ref().and_then(|i| i.architecture.as_deref());
+    let emulated = match (image_arch.as_deref(), host_arch) {
+        (Some(image), Some(host)) => image != oci_architecture(host),
+        _ => false,
+    };
+    let hostname = inspect
+        .config
+        .as_ref()
+        .and_then(|c| c.hostname.clone())
+        .unwrap_or_default();
+
+    let env_vars = inspect
+        .config
+        .as_ref()
+        .and_then(|c| c.env.clone())
+        .unwrap_or_default();
+    let entrypoint = inspect
+        .config
+        .as_ref()
+        .and_then(|c| c.entrypoint.clone())
+        .unwrap_or_default();
+    let cmd = inspect
+        .config
+        .as_ref()
+        .and_then(|c| c.cmd.clone())
+        .unwrap_or_default();
+    let mut labels: Vec<(String, String)> = inspect
+        .config
+        .as_ref()
+        .and_then(|c| c.labels.clone())
+        .unwrap_or_default()
+        .into_iter()
+        .collect();
+    labels.sort();
+
+    let state = inspect.state.clone().unwrap_or_default();
+    let exit_code = if state.running == Some(true) {
+        None
+    } else {
+        state.exit_code
+    };
+    let restart_count = inspect.restart_count.unwrap_or(0);
+    let created = inspect.created.as_deref().and_then(parse_inspect_time);
+    let started_at = state.started_at.as_deref().and_then(parse_inspect_time);
+    let healthcheck = inspect
+        .config
+        .as_ref()
+        .and_then(|c| c.healthcheck.as_ref())
+        .and_then(|config| {
+            let probes = state
+                .health
+                .as_ref()
+                .and_then(|h| h.log.clone())
+                .unwrap_or_default();
+            health_check(config, probes)
+        });
+    let finished_at = state.finished_at.as_deref().and_then(parse_inspect_time);
+
+    let volumes = inspect
+        .mounts
+        .unwrap_or_default()
+        .into_iter()
+        .map(|m| {
+            let (kind, on_host) = match m.typ {
+                Some(MountPointTypeEnum::BIND) => (MountKind::Bind, true),
+                Some(MountPointTypeEnum::VOLUME) => {
+                    (MountKind::Volume, m.driver.as_deref() == Some("local"))
+                }
+                _ => (MountKind::Other, false),
+            };
+            MountInfo {
+                kind,
+                name: m.name.unwrap_or_default(),
+                source: m.source.unwrap_or_default(),
+                destination: m.destination.unwrap_or_default(),
+                on_host,
             }
         })
-        .collect())
+        .collect();
+
+    let networks = inspect
+        .network_settings
+        .and_then(|ns| ns.networks)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(name, config)| NetworkAttachment {
+            name,
+            ip: config.ip_address.unwrap_or_default(),
+            gateway: config.gateway.unwrap_or_default(),
+            mac: config.mac_address.unwrap_or_default(),
+            aliases: config.aliases.unwrap_or_default(),
+        })
+        .collect();
+
+    Ok((
+        id,
+        ContainerDetails {
+            env_vars,
+            volumes,
+            networks,
+            raw_json,
+            memory_limit,
+            host_memory,
+            memory_swap,
+            cpu_limit,
+            host_cpus,
+            cpu_shares,
+            pids_limit,
+            restart_policy,
+            exit_code,
+            restart_count,
+            entrypoint,
+            cmd,
+            created,
+            started_at,
+            finished_at,
+            labels,
+            healthcheck,
+            image_digest,
+            image_created,
+            runtime,
+            devices,
+            device_requests,
+            security_risks,
+            hostname,
+            platform,
+            emulated,
+        },
+    ))
+}
+
+/// Maps a kernel architecture name, as the daemon reports it, to the OCI one images use.
+fn oci_architecture(uname: &str) -> &str {
+    match uname {
+        "x86_64" => "amd64",
+        "aarch64" => "arm64",
+        "armv7l" | "armv6l" => "arm",
+        "i386" | "i686" => "386",
+        other => other,
+    }
+}
+
+fn security_risks(inspect: &ContainerInspectResponse) -> Vec<SecurityRisk> {
+    let mut risks = Vec::new();
+    if let Some(hc) = &inspect.host_config {
+        if hc.privileged == Some(true) {
+            risks.push(SecurityRisk::Privileged);
+        }
+        if let Some(caps) = hc.cap_add.clone().filter(|caps| !caps.is_empty()) {
+            risks.push(SecurityRisk::Capabilities(caps));
+        }
+        if hc.network_mode.as_deref() == Some("host") {
+            risks.push(SecurityRisk::HostNetwork);
+        }
+        if hc.pid_mode.as_deref() == Some("host") {
+            risks.push(SecurityRisk::HostPid);
+        }
+    }
+    let user = inspect.config.as_ref().and_then(|c| c.user.as_deref());
+    let user = user.map(|u| u.split(':').next().unwrap_or(u));
+    if matches!(user, Some("root" | "0")) {
+        risks.push(SecurityRisk::RootUser);
+    }
+    risks
 }
 
-async fn fetch_stats(container_ids: &[String]) -> HashMap<String, ContainerStats> {
-    let docker = match Docker::connect_with_local_defaults() {
+/// Inspects each container once for its start time and the settings that weaken
+/// its isolation. Containers that can't be inspected are left out.
+pub async fn inspect_containers(
+    container_ids: Vec<String>,
+) -> HashMap<String, ContainerInspection> {
+    let docker = match connect() {
         Ok(d) => d,
         Err(_) => return HashMap::new(),
     };
 
     let mut results = HashMap::new();
-
     for id in container_ids {
-        let options = StatsOptions {
-            stream: false,
-            one_shot: true,
-        };
-
-        let mut stats_stream = docker.stats(id, Some(options));
-        if let Some(Ok(stats)) = stats_stream.next().await {
-            let cpu = calculate_cpu_percent(&stats);
-            let (mem_usage, mem_limit, mem_percent) = calculate_memory(&stats);
-            results.insert(
-                id.clone(),
-                ContainerStats {
-                    cpu_percent: cpu,
-                    memory_usage_mb: mem_usage,
-                    memory_limit_mb: mem_limit,
-                    memory_percent: mem_percent,
-                },
-            );
+        if let Ok(info) = docker
+            .inspect_container(&id, None::<InspectContainerOptions>)
+            .await
+        {
+            let inspection = ContainerInspection {
+                started_at: started_at(&info),
+                security_risks: security_risks(&info),
+            };
+            results.insert(id, inspection);
         }
     }
-
     results
 }
 
-pub async fn start_container(id: String) -> Result<String, String> {
-    let docker = Docker::connect_with_local_defaults().map_err(|e| e.to_string())?;
-    docker
-        .start_container(&id, None::<StartContainerOptions<String>>)
+/// Describes a health check config with Docker's defaults filled in for unset values.
+fn health_check(
+    config: &bollard::models::HealthConfig,
+    log: Vec<bollard::models::HealthcheckResult>,
+) -> Option<HealthCheck> {
+    let test = config.test.clone().unwrap_or_default();
+    let command = match test.split_first() {
+        Some((kind, args)) if kind == "CMD" || kind == "CMD-SHELL" => args.join(" "),
+        _ => return None,
+    };
+    let nanos_or = |nanos: Option<i64>, default: Duration| {
+        nanos
+            .filter(|n| *n > 0)
+            .map_or(default, |n| Duration::from_nanos(n as u64))
+    };
+    let probes = log
+        .into_iter()
+        .rev()
+        .map(|result| HealthProbe {
+            started_at: result.start.as_deref().and_then(parse_inspect_time),
+            exit_code: result.exit_code.unwrap_or(-1),
+            output: result.output.unwrap_or_default().trim_end().to_string(),
+        })
+        .collect();
+
+    Some(HealthCheck {
+        command,
+        interval: nanos_or(config.interval, Duration::from_secs(30)),
+        timeout: nanos_or(config.timeout, Duration::from_secs(30)),
+        retries: config.retries.filter(|r| *r > 0).unwrap_or(3),
+        start_period: nanos_or(config.start_period, Duration::ZERO),
+        probes,
+    })
+}
+
+/// Parses an inspect timestamp. Docker reports `0001-01-01T00:00:00Z` for events
+/// that never happened, which comes back as `None`.
+fn parse_inspect_time(value: &str) -> Option<chrono::DateTime<chrono::Utc>> {
+    let time = chrono::DateTime::parse_from_rfc3339(value).ok()?;
+    (time.timestamp() > 0).then(|| time.with_timezone(&chrono::Utc))
+}
+
+/// CPU limit in cores from either `--cpus` or `--cpu-quota`/`--cpu-period`.
+fn cpu_limit_cores(nano_cpus: Option<i64>, quota: Option<i64>, period: Option<i64>) -> f64 {
+    match (nano_cpus, quota) {
+        (Some(nano), _) if nano > 0 => nano as f64 / 1e9,
+        (_, Some(quota)) if quota > 0 => {
+            quota as f64 / period.filter(|p| *p > 0).unwrap_or(100_000) as f64
+        }
+        _ => 0.0,
+    }
+}
+
+/// Sets the CPU limit in cores, 0 meaning unlimited. The daemon refuses to mix
+/// `NanoCpus` with a CPU quota, so whichever the container already uses is kept.
+/// A zero `NanoCpus` leaves the limit unchanged, so lifting it clears the quota
+/// instead. Returns the limit the daemon reports afterwards.
+pub async fn update_cpu_limit(id: String, cores: f64) -> Result<f64, String> {
+    const PERIOD: i64 = 100_000;
+
+    let docker = connect().map_err(|e| e.to_string())?;
+    let inspect = docker
+        .inspect_container(&id, None::<InspectContainerOptions>)
         .await
         .map_err(|e| e.to_string())?;
-    Ok(id)
-}
+    let uses_quota = inspect
+        .host_config
+        .as_ref()
+        .and_then(|hc| hc.cpu_quota)
+        .is_some_and(|quota| quota > 0);
+
+    let options = if uses_quota || cores <= 0.0 {
+        UpdateContainerOptions::<String> {
+            cpu_period: Some(PERIOD),
+            cpu_quota: Some(if cores > 0.0 {
+                (cores * PERIOD as f64) as i64
+            } else {
+                -1
+            }),
+            ..Default::default()
+        }
+    } else {
+        UpdateContainerOptions::<String> {
+            nano_cpus: Some((cores * 1e9) as i64),
+            ..Default::default()
+        }
+    };
 
-pub async fn stop_container(id: String) -> Result<String, String> {
-    let docker = Docker::connect_with_local_defaults().map_err(|e| e.to_string())?;
     docker
-        .stop_container(&id, Some(StopContainerOptions { t: 10 }))
+        .update_container(&id, options)
         .await
         .map_err(|e| e.to_string())?;
-    Ok(id)
-}
 
-pub async fn restart_container(id: String) -> Result<String, String> {
-    let docker = Docker::connect_with_local_defaults().map_err(|e| e.to_string())?;
-    docker
-        .restart_container(&id, Some(RestartContainerOptions { t: 10 }))
+    let updated = docker
+        .inspect_container(&id, None::<InspectContainerOptions>)
         .await
         .map_err(|e| e.to_string())?;
-    Ok(id)
+    Ok(updated
+        .host_config
+        .map_or(0.0, |hc| cpu_limit_cores(hc.nano_cpus, hc.cpu_quota, hc.cpu_period)))
 }
 
-pub async fn remove_container(id: String) -> Result<String, String> {
-    let docker = Docker::connect_with_local_defaults().map_err(|e| e.to_string())?;
+pub async fn update_restart_policy(
+    id: String,
+    policy: RestartPolicy,
+) -> Result<RestartPolicy, String> {
+    let docker = connect().map_err(|e| e.to_string())?;
     docker
-        .remove_container(
+        .update_container(
             &id,
-            Some(RemoveContainerOptions {
-                force: false,
-                v: false,
+            UpdateContainerOptions::<String> {
+                restart_policy: Some(bollard::models::RestartPolicy {
+                    name: Some(policy.name()),
+                    maximum_retry_count: None,
+                }),
                 ..Default::default()
-            }),
+            },
         )
         .await
         .map_err(|e| e.to_string())?;
-    Ok(id)
+    Ok(policy)
 }
 
-pub async fn fetch_container_details(id: String) -> Result<(String, ContainerDetails), String> {
-    let docker = Docker::connect_with_local_defaults().map_err(|e| e.to_string())?;
-
+/// Sets the memory limit in bytes. A swap cap the container already has is kept,
+/// and only raised when it would fall below the new limit, which the daemon rejects.
+pub async fn update_memory_limit(id: String, bytes: i64) -> Result<i64, String> {
+    let docker = connect().map_err(|e| e.to_string())?;
     let inspect = docker
         .inspect_container(&id, None::<InspectContainerOptions>)
         .await
         .map_err(|e| e.to_string())?;
-
-    let env_vars = inspect
-        .config
-        .as_ref()
-        .and_then(|c| c.env.clone())
-        .unwrap_or_default();
-
-    let volumes = inspect
-        .mounts
-        .unwrap_or_default()
-        .into_iter()
-        .map(|m| {
-            let source = m.source.unwrap_or_default();
-            let destination = m.destination.unwrap_or_default();
-            (source, destination)
-        })
-        .collect();
-
-    let networks = inspect
-        .network_settings
-        .and_then(|ns| ns.networks)
-        .unwrap_or_default()
-        .into_iter()
-        .map(|(name, config)| {
-            let ip = config.ip_address.unwrap_or_default();
-            (name, ip)
-        })
-        .collect();
-
-    Ok((
-        id,
-        ContainerDetails {
-            env_vars,
-            volumes,
-            networks,
-        },
-    ))
+    let memory_swap = inspect
+        .host_config
+        .and_then(|hc| hc.memory_swap)
+        .filter(|swap| *swap > 0)
+        .map(|swap| swap.max(bytes));
+    docker
+        .update_container(
+            &id,
+            UpdateContainerOptions::<String> {
+                memory: Some(bytes),
+                memory_swap,
+                ..Default::default()
+            },
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(bytes)
 }
 
 async fn fetch_health_statuses(container_ids: &[String]) -> HashMap<String, HealthStatus> {
-    let docker = match Docker::connect_with_local_defaults() {
+    let docker = match connect() {
         Ok(d) => d,
         Err(_) => return HashMap::new(),
     };
@@ -489,3 +3040,482 @@ async fn fetch_health_statuses(container_ids: &[String]) -> HashMap<String, Heal
     results
 }
 
+/// Runs a non-interactive command in a running container and returns its
+/// combined stdout/stderr. A non-zero exit code is reported as an error.
+pub async fn exec_output(id: &str, cmd: Vec<String>) -> Result<String, String> {
+    let docker = connect().map_err(|e| e.to_string())?;
+
+    let exec = docker
+        .create_exec(
+            id,
+            CreateExecOptions {
+                attach_stdout: Some(true),
+                attach_stderr: Some(true),
+                cmd: Some(cmd),
+                ..Default::default()
+            },
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let mut output = String::new();
+    if let StartExecResults::Attached { output: mut stream, .. } = docker
+        .start_exec(&exec.id, None)
+        .await
+        .map_err(|e| e.to_string())?
+    {
+        while let Some(Ok(chunk)) = stream.next().await {
+            output.push_str(&chunk.to_string());
+        }
+    }
+
+    let exit_code = docker
+        .inspect_exec(&exec.id)
+        .await
+        .ok()
+        .and_then(|i| i.exit_code)
+        .unwrap_or(0);
+    if exit_code != 0 {
+        return Err(output.trim().to_string());
+    }
+    Ok(output)
+}
+
+/// Picks the best interactive shell available in the container.
+pub async fn detect_shell(id: &str) -> String {
+    for shell in ["/bin/bash", "/bin/ash", "/bin/sh"] {
+        if exec_output(id, vec![shell.into(), "-c".into(), "exit 0".into()])
+            .await
+            .is_ok()
+        {
+            return shell.to_string();
+        }
+    }
+    "sh".to_string()
+}
+
+/// `docker` CLI invocation for an interactive shell, pointed at the same
+/// socket the applet uses.
+pub async fn shell_command(id: String) -> Vec<String> {
+    let shell = detect_shell(&id).await;
+    let mut command = vec!["docker".to_string()];
+    command.extend(docker_cli_args(["exec".to_string(), "-it".to_string(), id, shell]));
+    command
+}
+
+/// Arguments for the `docker` CLI, pointed at the same daemon as the applet.
+fn docker_cli_args<S: Into<String>>(args: impl IntoIterator<Item = S>) -> Vec<String> {
+    let mut command = Vec::new();
+    if let Some(socket) = SOCKET_OVERRIDE.read().ok().and_then(|s| s.clone()) {
+        command.push("-H".to_string());
+        command.push(format!("unix://{}", socket.display()));
+    }
+    command.extend(args.into_iter().map(Into::into));
+    command
+}
+
+pub async fn list_directory(id: String, path: String) -> Result<(String, Vec<FileEntry>), String> {
+    let output = exec_output(
+        &id,
+        vec!["ls".into(), "-1Ap".into(), "--".into(), path.clone()],
+    )
+    .await?;
+
+    let mut entries: Vec<FileEntry> = output
+        .lines()
+        .filter(|l| !l.is_empty())
+        .map(|line| match line.strip_suffix('/') {
+            Some(dir) => FileEntry {
+                name: dir.to_string(),
+                is_dir: true,
+            },
+            None => FileEntry {
+                name: line.to_string(),
+                is_dir: false,
+            },
+        })
+        .collect();
+    // Directories first, then alphabetical
+    entries.sort_by(|a, b| b.is_dir.cmp(&a.is_dir).then_with(|| a.name.cmp(&b.name)));
+
+    Ok((path, entries))
+}
+
+/// Fetches `path` from a container through the archive API and streams it to the
+/// file `choose_dest` picks for the suggested name. A single regular file is
+/// unpacked so it is saved as-is; anything else is saved as the tar the daemon sends.
+/// A partly written file is removed on failure.
+pub async fn download_path<F, Fut>(
+    id: String,
+    path: String,
+    choose_dest: F,
+) -> Result<PathBuf, String>
+where
+    F: FnOnce(String) -> Fut,
+    Fut: std::future::Future<Output = Result<PathBuf, String>>,
+{
+    use futures::io::AsyncReadExt;
+    use futures::TryStreamExt;
+    use tokio::io::AsyncWriteExt;
+
+    let docker = connect().map_err(|e| e.to_string())?;
+    let mut archive = docker
+        .download_from_container(&id, Some(DownloadFromContainerOptions { path: path.clone() }))
+        .map_err(std::io::Error::other)
+        .boxed()
+        .into_async_read();
+
+    // Read up to the first real entry to tell a single file from anything else,
+    // keeping the bytes in case the archive is saved whole
+    let mut head = Vec::new();
+    let single_file = loop {
+        let mut block = [0u8; 512];
+        archive
+            .read_exact(&mut block)
+            .await
+            .map_err(|e| e.to_string())?;
+        head.extend_from_slice(&block);
+        if block.iter().all(|b| *b == 0) {
+            return Err(format!("{} is empty", path));
+        }
+        let header = tar::Header::from_byte_slice(&block);
+        let size = header.entry_size().map_err(|e| e.to_string())?;
+        let kind = header.entry_type();
+        if kind.is_gnu_longname()
+            || kind.is_gnu_longlink()
+            || kind.is_pax_local_extensions()
+            || kind.is_pax_global_extensions()
+        {
+            // Metadata for the entry that follows; 512-byte aligned
+            let mut data = vec![0u8; size.div_ceil(512) as usize * 512];
+            archive
+                .read_exact(&mut data)
+                .await
+                .map_err(|e| e.to_string())?;
+            head.extend_from_slice(&data);
+            continue;
+        }
+        break kind.is_file().then_some(size);
+    };
+
+    let base_name = path
+        .trim_end_matches('/')
+        .rsplit('/')
+        .next()
+        .filter(|n| !n.is_empty())
+        .unwrap_or("root")
+        .to_string();
+    let file_name = match single_file {
+        Some(_) => base_name,
+        None => format!("{}.tar", base_name),
+    };
+    let dest = choose_dest(file_name).await?;
+
+    let result = async {
+        let mut file = tokio::fs::File::create(&dest).await?;
+        let mut remaining = match single_file {
+            Some(size) => size,
+            None => {
+                file.write_all(&head).await?;
+                u64::MAX
+            }
+        };
+        let mut buf = vec![0u8; 64 * 1024];
+        while remaining > 0 {
+            let want = buf.len().min(remaining.try_into().unwrap_or(usize::MAX));
+            let read = archive.read(&mut buf[..want]).await?;
+            if read == 0 {
+                if single_file.is_some() {
+                    return Err(std::io::ErrorKind::UnexpectedEof.into());
+                }
+                break;
+            }
+            file.write_all(&buf[..read]).await?;
+            remaining -= read as u64;
+        }
+        file.flush().await
+    }
+    .await;
+    if let Err(e) = result {
+        let _ = tokio::fs::remove_file(&dest).await;
+        return Err(e.to_string());
+    }
+    Ok(dest)
+}
+
+/// Packs `(file name, contents)` pairs into an in-memory tar archive.
+pub fn build_tar(files: &[(String, Vec<u8>)]) -> Result<Vec<u8>, String> {
+    let mtime = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+
+    let mut builder = tar::Builder::new(Vec::new());
+    for (file_name, contents) in files {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(contents.len() as u64);
+        header.set_mode(0o644);
+        header.set_mtime(mtime);
+        header.set_cksum();
+        builder
+            .append_data(&mut header, file_name, contents.as_slice())
+            .map_err(|e| e.to_string())?;
+    }
+    builder.into_inner().map_err(|e| e.to_string())
+}
+
+/// Logs written between two unix times, fetched once instead of followed
+pub async fn fetch_logs_between(
+    id: String,
+    since: i64,
+    until: i64,
+    timestamps: bool,
+) -> Result<Vec<(LogStream, String)>, String> {
+    let docker = connect().map_err(|e| e.to_string())?;
+
+    let options = LogsOptions::<String> {
+        stdout: true,
+        stderr: true,
+        since,
+        until,
+        timestamps,
+        tail: "all".to_string(),
+        ..Default::default()
+    };
+
+    let mut logs = Vec::new();
+    let mut log_stream = docker.logs(&id, Some(options));
+    while let Some(line) = log_stream.next().await {
+        let line = line.map_err(|e| e.to_string())?;
+        logs.push((log_stream_of(&line), line.to_string()));
+    }
+    Ok(logs)
+}
+
+fn log_stream_of(frame: &LogOutput) -> LogStream {
+    // Containers with a TTY only have the merged console stream
+    match frame {
+        LogOutput::StdErr { .. } => LogStream::Stderr,
+        _ => LogStream::Stdout,
+    }
+}
+
+/// Fetches the last `tail` log lines of a container without following.
+pub async fn fetch_recent_logs(id: String, tail: usize) -> Result<String, String> {
+    let docker = connect().map_err(|e| e.to_string())?;
+
+    let options = LogsOptions::<String> {
+        stdout: true,
+        stderr: true,
+        timestamps: true,
+        tail: tail.to_string(),
+        ..Default::default()
+    };
+
+    let mut logs = String::new();
+    let mut log_stream = docker.logs(&id, Some(options));
+    while let Some(line) = log_stream.next().await {
+        logs.push_str(&line.map_err(|e| e.to_string())?.to_string());
+    }
+    Ok(logs)
+}
+
+/// Copies a single file into `dir` inside the container via the archive API.
+pub async fn upload_file(
+    id: String,
+    dir: String,
+    file_name: String,
+    contents: Vec<u8>,
+) -> Result<(), String> {
+    let docker = connect().map_err(|e| e.to_string())?;
+    let archive = build_tar(&[(file_name, contents)])?;
+
+    docker
+        .upload_to_container(
+            &id,
+            Some(UploadToContainerOptions {
+                path: dir,
+                ..Default::default()
+            }),
+            archive.into(),
+        )
+        .await
+        .map_err(|e| e.to_string())
+}
+
+pub async fn fetch_processes(id: String) -> Result<(String, Vec<ProcessInfo>), String> {
+    let docker = connect().map_err(|e| e.to_string())?;
+
+    let top = docker
+        .top_processes(
+            &id,
+            Some(TopOptions {
+                ps_args: "-eo pid,user,pcpu,args",
+            }),
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let titles = top.titles.unwrap_or_default();
+    let column = |name: &str| titles.iter().position(|t| t == name);
+    let pid_col = column("PID");
+    let user_col = column("USER").or_else(|| column("UID"));
+    let cpu_col = column("%CPU").or_else(|| column("C"));
+    let cmd_col = column("CMD").or_else(|| column("COMMAND"));
+
+    let processes = top
+        .processes
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|row| {
+            let pid = row.get(pid_col?)?.clone();
+            let field =
+                |col: Option<usize>| col.and_then(|c| row.get(c).cloned()).unwrap_or_default();
+            Some(ProcessInfo {
+                pid,
+                user: field(user_col),
+                cpu: field(cpu_col),
+                command: field(cmd_col),
+            })
+        })
+        .collect();
+
+    Ok((id, processes))
+}
+
+/// Sends SIGTERM to a process via `exec kill`. The top API reports host PIDs,
+/// so translate to the container's PID namespace first when we can.
+pub async fn kill_process(id: String, host_pid: String) -> Result<String, String> {
+    let pid = namespace_pid(&host_pid);
+    exec_output(&id, vec!["kill".into(), pid]).await?;
+    Ok(id)
+}
+
+fn namespace_pid(host_pid: &str) -> String {
+    std::fs::read_to_string(format!("/proc/{}/status", host_pid))
+        .ok()
+        .and_then(|status| {
+            status
+                .lines()
+                .find_map(|l| l.strip_prefix("NSpid:"))
+                .and_then(|pids| pids.split_whitespace().last().map(str::to_string))
+        })
+        .unwrap_or_else(|| host_pid.to_string())
+}
+
+pub async fn fetch_comparable_config(id: String) -> Result<ComparableConfig, String> {
+    let docker = connect().map_err(|e| e.to_string())?;
+
+    let inspect = docker
+        .inspect_container(&id, None::<InspectContainerOptions>)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let name = inspect
+        .name
+        .unwrap_or_default()
+        .trim_start_matches('/')
+        .to_string();
+    let config = inspect.config.unwrap_or_default();
+
+    let image = config
+        .image
+        .map(|image| [("image".to_string(), image)].into_iter().collect())
+        .unwrap_or_default();
+    let env = compare::env_map(&config.env.unwrap_or_default());
+    let labels = config.labels.unwrap_or_default().into_iter().collect();
+
+    let mounts = inspect
+        .mounts
+        .unwrap_or_default()
+        .into_iter()
+        .map(|m| (m.destination.unwrap_or_default(), m.source.unwrap_or_default()))
+        .collect();
+
+    let ports = inspect
+        .host_config
+        .and_then(|hc| hc.port_bindings)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(port, bindings)| {
+            let bound = bindings
+                .unwrap_or_default()
+                .into_iter()
+                .map(|b| {
+                    format!(
+                        "{}:{}",
+                        b.host_ip.unwrap_or_default(),
+                        b.host_port.unwrap_or_default()
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            (port, bound)
+        })
+        .collect();
+
+    Ok(ComparableConfig {
+        name,
+        image,
+        env,
+        labels,
+        mounts,
+        ports,
+    })
+}
+
+/// Splits `repo:tag` so pulls don't fall back to fetching every tag.
+fn split_image_tag(image: &str) -> (String, String) {
+    if image.contains('@') {
+        return (image.to_string(), String::new());
+    }
+    let name_start = image.rfind('/').map(|i| i + 1).unwrap_or(0);
+    match image[name_start..].rfind(':') {
+        Some(i) => (
+            image[..name_start + i].to_string(),
+            image[name_start + i + 1..].to_string(),
+        ),
+        None => (image.to_string(), "latest".to_string()),
+    }
+}
+
+pub async fn pull_image(
+    image: String,
+    credentials: Option<DockerCredentials>,
+) -> Result<String, String> {
+    let docker = connect().map_err(|e| e.to_string())?;
+    let (from_image, tag) = split_image_tag(&image);
+
+    let mut stream = std::pin::pin!(docker.create_image(
+        Some(CreateImageOptions {
+            from_image,
+            tag,
+            ..Default::default()
+        }),
+        None,
+        credentials,
+    ));
+    while let Some(progress) = stream.next().await {
+        progress.map_err(|e| e.to_string())?;
+    }
+    Ok(image)
+}
+
+/// Pushes a `repo:tag` reference to its registry
+pub async fn push_image(
+    image: String,
+    credentials: Option<DockerCredentials>,
+) -> Result<String, String> {
+    let docker = connect().map_err(|e| e.to_string())?;
+    let (name, tag) = split_image_tag(&image);
+
+    let mut stream = std::pin::pin!(docker.push_image(
+        &name,
+        Some(PushImageOptions { tag }),
+        credentials
+    ));
+    while let Some(progress) = stream.next().await {
+        progress.map_err(|e| e.to_string())?;
+    }
+    Ok(image)
+}
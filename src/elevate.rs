@@ -0,0 +1,180 @@
+//! Elevated access for systems where the Docker socket is root-only.
+//!
+//! Instead of asking the user to join the `docker` group, the applet re-runs
+//! itself through `pkexec` as a tiny relay: the root helper listens on a
+//! socket owned by the user and forwards every connection to the real Docker
+//! socket. The helper exits as soon as the applet that spawned it goes away.
+
+use std::io::{self, Read};
+use std::os::unix::fs::{DirBuilderExt, MetadataExt};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::Mutex;
+use std::time::Duration;
+
+pub const PROXY_FLAG: &str = "--docker-proxy";
+const DOCKER_SOCKET: &str = "/var/run/docker.sock";
+const SOCKET_NAME: &str = "cosmic-applet-docker.sock";
+
+/// Write end of the helper's stdin. It is never written to: when the applet
+/// exits the pipe closes and the helper sees EOF.
+static HELPER_LIFELINE: Mutex<Option<tokio::process::ChildStdin>> = Mutex::new(None);
+
+/// Whether a connection error means the socket exists but we may not use it.
+pub fn is_permission_denied(error: &str) -> bool {
+    let error = error.to_lowercase();
+    error.contains("permission denied") || error.contains("os error 13")
+}
+
+fn current_uid() -> u32 {
+    // SAFETY: getuid takes no arguments and cannot fail
+    unsafe { libc::getuid() }
+}
+
+/// The relay socket lives in a directory only the user can enter: the session's
+/// runtime directory, or a private 0700 directory when there is none.
+fn proxy_socket_path() -> io::Result<PathBuf> {
+    let dir = match std::env::var_os("XDG_RUNTIME_DIR") {
+        Some(dir) => PathBuf::from(dir),
+        None => {
+            let dir =
+                std::env::temp_dir().join(format!("cosmic-applet-docker-{}", current_uid()));
+            match std::fs::DirBuilder::new().mode(0o700).create(&dir) {
+                Err(e) if e.kind() != io::ErrorKind::AlreadyExists => return Err(e),
+                // An existing directory is checked by the helper like any other
+                _ => dir,
+            }
+        }
+    };
+    Ok(dir.join(SOCKET_NAME))
+}
+
+/// Spawns the relay through pkexec and waits for its socket to appear.
+pub async fn start_helper() -> Result<PathBuf, String> {
+    let exe = std::env::current_exe().map_err(|e| e.to_string())?;
+    let socket = proxy_socket_path().map_err(|e| e.to_string())?;
+    // A socket left by an earlier helper; removed as the user, not as root
+    let _ = std::fs::remove_file(&socket);
+
+    let mut child = tokio::process::Command::new("pkexec")
+        .arg(exe)
+        .arg(PROXY_FLAG)
+        .arg(&socket)
+        .stdin(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to run pkexec: {}", e))?;
+    let lifeline = child.stdin.take();
+
+    // Leave time for the polkit password prompt
+    for _ in 0..120 {
+        if socket.exists() {
+            if let Ok(mut held) = HELPER_LIFELINE.lock() {
+                *held = lifeline;
+            }
+            return Ok(socket);
+        }
+        if let Ok(Some(status)) = child.try_wait() {
+            return Err(format!("Elevated helper exited: {}", status));
+        }
+        tokio::time::sleep(Duration::from_millis(500)).await;
+    }
+
+    let _ = child.kill().await;
+    Err("Timed out waiting for the elevated helper".to_string())
+}
+
+/// Entry point of the root helper: `--docker-proxy <socket>`. The user the socket
+/// is made for comes from `PKEXEC_UID`, which pkexec sets and callers cannot choose.
+pub fn run_proxy(args: &[String]) -> io::Result<()> {
+    let [socket] = args else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "usage: --docker-proxy <socket>",
+        ));
+    };
+    let uid: u32 = std::env::var("PKEXEC_UID")
+        .ok()
+        .and_then(|uid| uid.parse().ok())
+        .ok_or_else(|| {
+            io::Error::new(io::ErrorKind::PermissionDenied, "not started through pkexec")
+        })?;
+    let socket = Path::new(socket);
+    check_socket_dir(socket, uid)?;
+
+    // Bind with the filesystem uid switched to the user: the path is resolved
+    // with their permissions only and the node is created owned by them, 0600
+    // from the start, so nothing is chown'ed, chmod'ed or unlinked by path as root.
+    // Still single-threaded here, so the per-thread fsuid covers the bind alone.
+    // SAFETY: plain syscalls without pointers
+    let previous_fsuid = unsafe {
+        libc::umask(0o177);
+        libc::setfsuid(uid)
+    };
+    let listener = UnixListener::bind(socket);
+    // SAFETY: as above
+    unsafe {
+        libc::setfsuid(previous_fsuid as libc::uid_t);
+    }
+    let listener = listener?;
+
+    // Exit together with the applet that started us: it holds the write end
+    // of our stdin, which closes when it goes away however it ends
+    std::thread::spawn(|| {
+        let mut buf = [0u8; 64];
+        while matches!(io::stdin().read(&mut buf), Ok(n) if n > 0) {}
+        std::process::exit(0);
+    });
+
+    for client in listener.incoming() {
+        let client = match client {
+            Ok(c) => c,
+            Err(_) => continue,
+        };
+        std::thread::spawn(move || {
+            if let Ok(upstream) = UnixStream::connect(DOCKER_SOCKET) {
+                relay(client, upstream);
+            }
+        });
+    }
+    Ok(())
+}
+
+/// Only accepts a socket directly inside a real directory that belongs to the
+/// user and is closed to everyone else.
+fn check_socket_dir(socket: &Path, uid: u32) -> io::Result<()> {
+    let rejected = |reason: &str| {
+        io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            format!("refusing socket {}: {}", socket.display(), reason),
+        )
+    };
+    if !socket.is_absolute() || socket.file_name() != Some(std::ffi::OsStr::new(SOCKET_NAME)) {
+        return Err(rejected("unexpected path"));
+    }
+    let dir = socket.parent().ok_or_else(|| rejected("no parent directory"))?;
+    let metadata = std::fs::symlink_metadata(dir)?;
+    if !metadata.is_dir() {
+        return Err(rejected("parent is not a directory"));
+    }
+    if metadata.uid() != uid || metadata.mode() & 0o077 != 0 {
+        return Err(rejected("parent directory is not private to the user"));
+    }
+    Ok(())
+}
+
+fn relay(client: UnixStream, upstream: UnixStream) {
+    let (Ok(mut client_read), Ok(mut upstream_write)) = (client.try_clone(), upstream.try_clone())
+    else {
+        return;
+    };
+    let (mut upstream_read, mut client_write) = (upstream, client);
+
+    let forward = std::thread::spawn(move || {
+        let _ = io::copy(&mut client_read, &mut upstream_write);
+        let _ = upstream_write.shutdown(std::net::Shutdown::Write);
+    });
+    let _ = io::copy(&mut upstream_read, &mut client_write);
+    let _ = client_write.shutdown(std::net::Shutdown::Write);
+    let _ = forward.join();
+}
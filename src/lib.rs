@@ -1,6 +1,14 @@
+mod ansi;
 mod app;
+mod compare;
 mod config;
 mod docker;
+pub mod elevate;
 mod localize;
+mod policy;
+mod portal;
+mod redact;
+mod registry;
+mod service;
 
 pub use app::DockerApplet;
@@ -1,6 +1,16 @@
-use cosmic_applet_docker::DockerApplet;
+use cosmic_applet_docker::{elevate, DockerApplet};
 
 fn main() -> cosmic::iced::Result {
     tracing_subscriber::fmt::init();
+
+    let args: Vec<String> = std::env::args().collect();
+    if args.get(1).map(String::as_str) == Some(elevate::PROXY_FLAG) {
+        if let Err(e) = elevate::run_proxy(&args[2..]) {
+            tracing::error!("Docker proxy failed: {}", e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
     cosmic::applet::run::<DockerApplet>(())
 }
@@ -0,0 +1,70 @@
+use crate::config::Config;
+use serde::{Deserialize, Serialize};
+
+/// How risky an action is; decides which confirmation setting applies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActionCategory {
+    /// Start, stop, restart and friends
+    Normal,
+    /// Removes something that can be recreated (delete, down, prune)
+    Destructive,
+    /// Loses data or state outright (force kill, volume removal)
+    Dangerous,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Confirmation {
+    /// Run immediately
+    Never,
+    /// Ask yes/no before running
+    Ask,
+    /// Require typing the target's name before running
+    TypeName,
+}
+
+impl Confirmation {
+    pub const ALL: [Confirmation; 3] =
+        [Confirmation::Never, Confirmation::Ask, Confirmation::TypeName];
+}
+
+impl ActionCategory {
+    pub fn confirmation(self, config: &Config) -> Confirmation {
+        match self {
+            ActionCategory::Normal => config.confirm_normal,
+            ActionCategory::Destructive => config.confirm_destructive,
+            ActionCategory::Dangerous => config.confirm_dangerous,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn each_category_reads_its_own_setting() {
+        let config = Config {
+            confirm_normal: Confirmation::Ask,
+            confirm_destructive: Confirmation::TypeName,
+            confirm_dangerous: Confirmation::Never,
+            ..Config::default()
+        };
+        assert_eq!(ActionCategory::Normal.confirmation(&config), Confirmation::Ask);
+        assert_eq!(
+            ActionCategory::Destructive.confirmation(&config),
+            Confirmation::TypeName
+        );
+        assert_eq!(ActionCategory::Dangerous.confirmation(&config), Confirmation::Never);
+    }
+
+    #[test]
+    fn defaults_escalate_with_risk() {
+        let config = Config::default();
+        assert_eq!(ActionCategory::Normal.confirmation(&config), Confirmation::Never);
+        assert_eq!(ActionCategory::Destructive.confirmation(&config), Confirmation::Ask);
+        assert_eq!(
+            ActionCategory::Dangerous.confirmation(&config),
+            Confirmation::TypeName
+        );
+    }
+}
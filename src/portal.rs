@@ -0,0 +1,33 @@
+//! File chooser helpers backed by the XDG desktop portal.
+
+use cosmic::dialog::file_chooser;
+use std::path::PathBuf;
+
+/// Asks the user where to save a file, suggesting `file_name`.
+pub async fn choose_save_path(title: String, file_name: String) -> Result<PathBuf, String> {
+    let response = file_chooser::save::Dialog::new()
+        .title(title)
+        .file_name(file_name)
+        .save_file()
+        .await
+        .map_err(|e| format!("{:?}", e))?;
+
+    response
+        .url()
+        .and_then(|url| url.to_file_path().ok())
+        .ok_or_else(|| "No file selected".to_string())
+}
+
+/// Asks the user to pick an existing file.
+pub async fn choose_open_path(title: String) -> Result<PathBuf, String> {
+    let response = file_chooser::open::Dialog::new()
+        .title(title)
+        .open_file()
+        .await
+        .map_err(|e| format!("{:?}", e))?;
+
+    response
+        .url()
+        .to_file_path()
+        .map_err(|_| "Selected file is not local".to_string())
+}
@@ -0,0 +1,114 @@
+//! Private registry logins. The server and username live in the applet
+//! config; passwords are kept in the Secret Service keyring via `secret-tool`.
+
+use bollard::auth::DockerCredentials;
+use serde::{Deserialize, Serialize};
+use std::process::Stdio;
+use tokio::io::AsyncWriteExt;
+
+const SERVICE: &str = "cosmic-applet-docker";
+const DOCKER_HUB: &str = "docker.io";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RegistryEntry {
+    pub server: String,
+    pub username: String,
+}
+
+fn secret_attributes(entry: &RegistryEntry) -> [&str; 6] {
+    [
+        "service",
+        SERVICE,
+        "server",
+        &entry.server,
+        "username",
+        &entry.username,
+    ]
+}
+
+pub async fn store_password(entry: &RegistryEntry, password: &str) -> Result<(), String> {
+    let mut child = tokio::process::Command::new("secret-tool")
+        .arg("store")
+        .arg(format!("--label=Docker registry {}", entry.server))
+        .args(secret_attributes(entry))
+        .stdin(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to run secret-tool: {}", e))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(password.as_bytes())
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+
+    let status = child.wait().await.map_err(|e| e.to_string())?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("secret-tool store failed: {}", status))
+    }
+}
+
+pub async fn lookup_password(entry: &RegistryEntry) -> Option<String> {
+    let output = tokio::process::Command::new("secret-tool")
+        .arg("lookup")
+        .args(secret_attributes(entry))
+        .output()
+        .await
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+pub async fn clear_password(entry: &RegistryEntry) {
+    let _ = tokio::process::Command::new("secret-tool")
+        .arg("clear")
+        .args(secret_attributes(entry))
+        .status()
+        .await;
+}
+
+/// Registry host an image reference points at, e.g. `ghcr.io` for
+/// `ghcr.io/owner/app:1.0` and `docker.io` for `nginx`.
+pub fn registry_host(image: &str) -> &str {
+    match image.split_once('/') {
+        Some((first, _)) if first.contains('.') || first.contains(':') || first == "localhost" => {
+            first
+        }
+        _ => DOCKER_HUB,
+    }
+}
+
+fn normalize_server(server: &str) -> &str {
+    let server = server
+        .trim_start_matches("https://")
+        .trim_start_matches("http://")
+        .trim_end_matches('/');
+    let server = server.split('/').next().unwrap_or(server);
+    match server {
+        "index.docker.io" | "registry-1.docker.io" | "registry.hub.docker.com" => DOCKER_HUB,
+        other => other,
+    }
+}
+
+/// Credentials for pulling or pushing `image`, if a login is configured for
+/// its registry.
+pub async fn credentials_for_image(
+    registries: &[RegistryEntry],
+    image: &str,
+) -> Option<DockerCredentials> {
+    let host = registry_host(image);
+    let entry = registries
+        .iter()
+        .find(|r| normalize_server(&r.server) == host)?;
+    let password = lookup_password(entry).await?;
+    Some(DockerCredentials {
+        username: Some(entry.username.clone()),
+        password: Some(password),
+        serveraddress: Some(entry.server.clone()),
+        ..Default::default()
+    })
+}
@@ -0,0 +1,66 @@
+//! Starting the Docker daemon through systemd when it isn't running.
+//!
+//! Rootless installs ship user units that can be started directly; the system
+//! daemon is started through `pkexec` so polkit can ask for a password.
+
+use std::process::Stdio;
+
+/// A systemd unit that provides the Docker daemon.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DockerUnit {
+    pub name: String,
+    /// Whether this is a user unit (rootless Docker)
+    pub user: bool,
+}
+
+/// Looks for a Docker unit, preferring the socket so the daemon is started
+/// the way the system normally activates it.
+pub async fn detect() -> Option<DockerUnit> {
+    for user in [true, false] {
+        for name in ["docker.socket", "docker.service"] {
+            if unit_exists(name, user).await {
+                return Some(DockerUnit {
+                    name: name.to_string(),
+                    user,
+                });
+            }
+        }
+    }
+    None
+}
+
+async fn unit_exists(name: &str, user: bool) -> bool {
+    let mut command = tokio::process::Command::new("systemctl");
+    if user {
+        command.arg("--user");
+    }
+    command
+        .args(["cat", "--", name])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .await
+        .is_ok_and(|status| status.success())
+}
+
+pub async fn start(unit: DockerUnit) -> Result<(), String> {
+    let mut command = if unit.user {
+        let mut command = tokio::process::Command::new("systemctl");
+        command.arg("--user");
+        command
+    } else {
+        let mut command = tokio::process::Command::new("pkexec");
+        command.arg("systemctl");
+        command
+    };
+    let output = command
+        .args(["start", "--", &unit.name])
+        .output()
+        .await
+        .map_err(|e| e.to_string())?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+    }
+}